@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use causal_lib::transcription::dsp::{DspChain, DspConfig};
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// One 50ms chunk at the streaming rate, as produced by audio capture
+const CHUNK_SAMPLES: usize = 800;
+
+fn speech_like_chunk() -> Vec<f32> {
+    (0..CHUNK_SAMPLES)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            0.05 * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+                + 0.02 * (2.0 * std::f32::consts::PI * 1800.0 * t).sin()
+                + 0.01
+        })
+        .collect()
+}
+
+fn stage_configs() -> Vec<(&'static str, DspConfig)> {
+    let mut high_pass = DspConfig::bypass();
+    high_pass.high_pass.enabled = true;
+
+    let mut noise_gate = DspConfig::bypass();
+    noise_gate.noise_gate.enabled = true;
+
+    let mut agc = DspConfig::bypass();
+    agc.agc.enabled = true;
+
+    let mut limiter = DspConfig::bypass();
+    limiter.limiter.enabled = true;
+
+    let mut full = DspConfig::default();
+    full.noise_gate.enabled = true;
+
+    vec![
+        ("bypass", DspConfig::bypass()),
+        ("high_pass", high_pass),
+        ("noise_gate", noise_gate),
+        ("agc", agc),
+        ("limiter", limiter),
+        ("default", DspConfig::default()),
+        ("all_stages", full),
+    ]
+}
+
+fn bench_dsp_chunk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dsp_chunk");
+    let input = speech_like_chunk();
+
+    for (name, config) in stage_configs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &config, |b, config| {
+            let mut chain = DspChain::new(config, SAMPLE_RATE);
+            let mut buffer = input.clone();
+            b.iter(|| {
+                buffer.copy_from_slice(&input);
+                chain.process(black_box(&mut buffer));
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_dsp_chain_creation(c: &mut Criterion) {
+    let config = DspConfig::default();
+
+    c.bench_function("dsp_chain_creation", |b| {
+        b.iter(|| DspChain::new(black_box(&config), SAMPLE_RATE));
+    });
+}
+
+criterion_group!(benches, bench_dsp_chunk, bench_dsp_chain_creation);
+criterion_main!(benches);
@@ -1,5 +1,9 @@
-use super::models::{Project, Recording};
+use super::models::{Project, Recording, RecordingWord};
 use super::store::Database;
+use crate::transcription::assemblyai::StreamingConfig;
+use crate::transcription::subtitles::{self, SubtitleConfig, SubtitleFormat};
+use crate::transcription::language::{self, PromptSettings};
+use crate::transcription::vocabulary;
 use crate::LoggingState;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -87,11 +91,63 @@ pub async fn update_project(
     Ok(result)
 }
 
+/// Set (or clear, with `None`) the turn detection settings new sessions in
+/// the project start with
+#[tauri::command]
+pub async fn update_project_streaming_config(
+    app: AppHandle,
+    db: State<'_, Database>,
+    id: String,
+    streaming_config: Option<StreamingConfig>,
+) -> Result<Project, String> {
+    tracing::info!("Updating streaming config for project: {}", id);
+    if let Some(config) = &streaming_config {
+        config.validate()?;
+    }
+    let result = db.update_project_streaming_config(&id, streaming_config).await?;
+
+    if let Err(e) = app.emit("project_updated", &result) {
+        tracing::error!("Failed to emit project_updated event: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// Replace the key terms boosted during recognition and spelled out in the
+/// enhancement and refinement prompts
+#[tauri::command]
+pub async fn update_project_vocabulary(
+    app: AppHandle,
+    db: State<'_, Database>,
+    id: String,
+    vocabulary: Vec<String>,
+) -> Result<Project, String> {
+    tracing::info!("Updating vocabulary for project: {}", id);
+    let vocabulary = vocabulary::normalize(vocabulary)?;
+    let result = db.update_project_vocabulary(&id, vocabulary).await?;
+
+    if let Err(e) = app.emit("project_updated", &result) {
+        tracing::error!("Failed to emit project_updated event: {}", e);
+    }
+
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn delete_project(app: AppHandle, db: State<'_, Database>, id: String) -> Result<(), String> {
     tracing::info!("Deleting project: {}", id);
+    // Recordings go with the project, so their audio files do too
+    let audio_paths: Vec<String> = db
+        .list_recordings(&id)
+        .await
+        .map(|recordings| recordings.into_iter().filter_map(|r| r.audio_path).collect())
+        .unwrap_or_default();
     db.delete_project(&id).await?;
 
+    for audio_path in audio_paths {
+        crate::transcription::recording_commands::remove_session_audio(&audio_path);
+    }
+
     // Emit real-time event for project deletion
     if let Err(e) = app.emit("project_deleted", serde_json::json!({"id": id})) {
         tracing::error!("Failed to emit project_deleted event: {}", e);
@@ -169,11 +225,48 @@ pub async fn update_recording_name(
     Ok(result)
 }
 
+/// Timed words of a recording, optionally only those overlapping
+/// `start_ms..end_ms` (ms from the start of the session)
+#[tauri::command]
+pub async fn get_recording_words(
+    db: State<'_, Database>,
+    recording_id: String,
+    start_ms: Option<u32>,
+    end_ms: Option<u32>,
+) -> Result<Vec<RecordingWord>, String> {
+    db.get_recording_words(&recording_id, start_ms, end_ms).await
+}
+
+/// Show a speaker in a recording under a real name, e.g. "Speaker A" as
+/// "Dana". `None` goes back to the label.
+#[tauri::command]
+pub async fn rename_speaker(
+    app: AppHandle,
+    db: State<'_, Database>,
+    recording_id: String,
+    label: String,
+    name: Option<String>,
+) -> Result<Recording, String> {
+    tracing::info!("Renaming speaker {} in recording: {}", label, recording_id);
+    let result = db.update_speaker_name(&recording_id, &label, name).await?;
+
+    if let Err(e) = app.emit("recording_updated", &result) {
+        tracing::error!("Failed to emit recording_updated event: {}", e);
+    }
+
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn delete_recording(app: AppHandle, db: State<'_, Database>, id: String) -> Result<(), String> {
     tracing::info!("Deleting recording: {}", id);
+    let audio_path = db.get_recording(&id).await.ok().and_then(|r| r.audio_path);
     db.delete_recording(&id).await?;
 
+    if let Some(audio_path) = audio_path {
+        crate::transcription::recording_commands::remove_session_audio(&audio_path);
+    }
+
     // Emit real-time event for recording deletion
     if let Err(e) = app.emit("recording_deleted", serde_json::json!({"id": id})) {
         tracing::error!("Failed to emit recording_deleted event: {}", e);
@@ -201,24 +294,25 @@ pub async fn generate_recording_summary(
     db: State<'_, Database>,
     recording_id: String,
     claude_api_key: String,
+    output_language: Option<String>,
 ) -> Result<Recording, String> {
     tracing::info!("Generating summary for recording: {}", recording_id);
+    if let Some(output_language) = &output_language {
+        language::validate_output(output_language)?;
+    }
 
     // Get the recording
     let mut recording = db.get_recording(&recording_id).await?;
 
-    // Use enhanced transcript if available, otherwise fall back to raw
-    let transcript_text = if !recording.enhanced_transcript.is_empty() {
-        recording.enhanced_transcript.clone()
-    } else {
-        recording.raw_transcript.clone()
-    };
+    // Use enhanced transcript if available, with speakers under their real names
+    let transcript_text = recording.summary_transcript();
 
     // Calculate approximate chunk count from metadata
     let chunk_count = recording.metadata.turn_count.max(1) as u32;
 
     // Generate summary using the summary service
-    let summary_service = crate::transcription::summary::SummaryService::new(claude_api_key);
+    let summary_service = crate::transcription::summary::SummaryService::new(claude_api_key)
+        .with_prompt_settings(PromptSettings::default().with_output_language(output_language));
     let summary = summary_service
         .summarize(transcript_text, chunk_count)
         .await?;
@@ -251,16 +345,60 @@ pub struct DatabaseStats {
     pub recording_count: usize,
 }
 
-/// Export a recording to a text file
+/// File format for `export_recording`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Summary, key points and transcripts as plain text
+    #[default]
+    Txt,
+    /// SubRip captions
+    Srt,
+    /// WebVTT captions
+    Vtt,
+}
+
+/// Captions for a recording from its stored word timings
+fn recording_subtitles(
+    recording: &Recording,
+    words: &[RecordingWord],
+    format: SubtitleFormat,
+    config: &SubtitleConfig,
+) -> Result<String, String> {
+    if words.is_empty() {
+        return Err("This recording has no word timings to build subtitles from".to_string());
+    }
+    let cues = subtitles::build_cues(words, |label| recording.speaker_display_name(label), config);
+    Ok(subtitles::render(&cues, format))
+}
+
+/// Export a recording to a text file, or to SRT/WebVTT captions
 #[tauri::command]
 pub async fn export_recording(
     db: State<'_, Database>,
     recording_id: String,
     output_path: String,
+    format: Option<ExportFormat>,
+    subtitle_config: Option<SubtitleConfig>,
 ) -> Result<String, String> {
     // Get the recording
     let recording = db.get_recording(&recording_id).await?;
 
+    let subtitle_format = match format.unwrap_or_default() {
+        ExportFormat::Txt => None,
+        ExportFormat::Srt => Some(SubtitleFormat::Srt),
+        ExportFormat::Vtt => Some(SubtitleFormat::Vtt),
+    };
+    if let Some(subtitle_format) = subtitle_format {
+        let config = subtitle_config.unwrap_or_default();
+        config.validate()?;
+        let words = db.get_recording_words(&recording_id, None, None).await?;
+        let content = recording_subtitles(&recording, &words, subtitle_format, &config)?;
+
+        fs::write(&output_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+        return Ok(format!("Subtitles exported to {}", output_path));
+    }
+
     // Format the content
     let timestamp = recording
         .created_at
@@ -20,7 +20,7 @@
 //!
 //! ## Example Usage
 //!
-//! ```rust,no_run
+//! ```rust,ignore
 //! use causal_lib::database::{Database, models::Project};
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
@@ -47,6 +47,9 @@ mod serde_helpers;
 pub mod store;
 
 pub use commands::*;
-pub use models::{Project, Recording, RecordingMetadata};
+pub use models::{
+    PausedInterval, Project, ProviderSession, Recording, RecordingMetadata, RecordingWord,
+    SpeakerStats,
+};
 pub use secure_settings_commands::*;
 pub use store::Database;
@@ -1,3 +1,4 @@
+use crate::transcription::assemblyai::StreamingConfig;
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
@@ -7,6 +8,12 @@ pub struct Project {
     pub name: String,
     pub description: String,
     pub api_key_reference: Option<String>, // References key in secure_settings table
+    /// Default turn detection settings for sessions in this project
+    #[serde(default)]
+    pub streaming_config: Option<StreamingConfig>,
+    /// Key terms boosted during recognition and spelled out in prompts
+    #[serde(default)]
+    pub vocabulary: Vec<String>,
     #[serde(with = "crate::database::serde_helpers")]
     pub created_at: SystemTime,
     #[serde(with = "crate::database::serde_helpers")]
@@ -21,6 +28,8 @@ impl Project {
             name,
             description,
             api_key_reference: None, // No API key configured by default
+            streaming_config: None,
+            vocabulary: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -34,6 +43,109 @@ pub struct RecordingMetadata {
     pub chunk_count: usize,
     pub turn_count: usize,
     pub average_confidence: f64,
+    /// Per-speaker breakdown, in order of first appearance
+    #[serde(default)]
+    pub speakers: Vec<SpeakerStats>,
+    /// Language most of the recording was spoken in (e.g. "de")
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Sessions opened with the transcription provider, in order
+    #[serde(default)]
+    pub provider_sessions: Vec<ProviderSession>,
+    /// Fraction of the captured audio detected as silence (0.0 - 1.0);
+    /// `None` for recordings saved before it was measured
+    #[serde(default)]
+    pub silence_ratio: Option<f64>,
+    /// Off-the-record stretches, in order
+    #[serde(default)]
+    pub paused_intervals: Vec<PausedInterval>,
+}
+
+/// A stretch of the session during which no audio was transcribed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PausedInterval {
+    /// Seconds from the session start
+    pub start_secs: f64,
+    /// Seconds from the session start; `None` while still paused
+    pub end_secs: Option<f64>,
+}
+
+/// How much one speaker said in a session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeakerStats {
+    /// Label from transcription, e.g. "Speaker A" or a track name like "them"
+    pub label: String,
+    /// Real name given by the user, shown in place of the label
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Seconds spoken, from word timestamps
+    pub talk_time_seconds: f64,
+    pub word_count: usize,
+    pub turn_count: usize,
+}
+
+impl SpeakerStats {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            name: None,
+            talk_time_seconds: 0.0,
+            word_count: 0,
+            turn_count: 0,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.label)
+    }
+}
+
+/// A streaming session with the transcription provider. AssemblyAI opens one
+/// per connection, so a reconnect or a second track adds another. The id and
+/// durations match the provider's own records, for billing reconciliation and
+/// support requests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderSession {
+    /// Session id assigned by the provider
+    pub id: String,
+    /// Track the session transcribed, for multi-track sources
+    #[serde(default)]
+    pub track: Option<String>,
+    /// When the provider ends the session, as a Unix timestamp in seconds
+    pub expires_at: u64,
+    /// Audio processed, as reported when the session ended
+    #[serde(default)]
+    pub audio_duration_seconds: Option<f64>,
+    /// How long the session was open, as reported when it ended
+    #[serde(default)]
+    pub session_duration_seconds: Option<f64>,
+}
+
+impl ProviderSession {
+    pub fn new(id: String, track: Option<String>, expires_at: u64) -> Self {
+        Self {
+            id,
+            track,
+            expires_at,
+            audio_duration_seconds: None,
+            session_duration_seconds: None,
+        }
+    }
+}
+
+/// One transcribed word of a recording, for seeking and subtitles
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordingWord {
+    /// Turn the word was spoken in
+    pub turn_order: usize,
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub text: String,
+    /// Start of the word, in ms from the start of the session
+    pub start_ms: u32,
+    /// End of the word, in ms from the start of the session
+    pub end_ms: u32,
+    pub confidence: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +170,9 @@ pub struct Recording {
     pub status: RecordingStatus,
     #[serde(with = "crate::database::serde_helpers")]
     pub created_at: SystemTime,
+    /// WAV file with the session audio, if it was recorded
+    #[serde(default)]
+    pub audio_path: Option<String>,
 }
 
 impl Recording {
@@ -82,9 +197,15 @@ impl Recording {
                 chunk_count: 0,
                 turn_count: 0,
                 average_confidence: 0.0,
+                speakers: Vec::new(),
+                language: None,
+                provider_sessions: Vec::new(),
+                silence_ratio: None,
+                paused_intervals: Vec::new(),
             },
             status: RecordingStatus::Completed,
             created_at: SystemTime::now(),
+            audio_path: None,
         }
     }
 
@@ -104,4 +225,37 @@ impl Recording {
         self.metadata = metadata;
         self
     }
+
+    pub fn with_audio_path(mut self, audio_path: Option<String>) -> Self {
+        self.audio_path = audio_path;
+        self
+    }
+
+    /// Name to show for a speaker label: the user's name for them, if given
+    pub fn speaker_display_name(&self, label: &str) -> String {
+        self.metadata
+            .speakers
+            .iter()
+            .find(|speaker| speaker.label == label)
+            .map_or(label, SpeakerStats::display_name)
+            .to_string()
+    }
+
+    /// Transcript to summarize: the enhanced one if available, with speaker
+    /// labels replaced by the names the user gave them
+    pub fn summary_transcript(&self) -> String {
+        let mut transcript = if !self.enhanced_transcript.is_empty() {
+            self.enhanced_transcript.clone()
+        } else {
+            self.raw_transcript.clone()
+        };
+
+        for speaker in &self.metadata.speakers {
+            if let Some(name) = &speaker.name {
+                transcript =
+                    transcript.replace(&format!("{}: ", speaker.label), &format!("{}: ", name));
+            }
+        }
+        transcript
+    }
 }
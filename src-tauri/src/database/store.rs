@@ -1,5 +1,6 @@
-use super::models::{Project, Recording, RecordingStatus};
+use super::models::{Project, Recording, RecordingStatus, RecordingWord};
 use crate::encryption::{EncryptedData, SettingsEncryption};
+use crate::transcription::assemblyai::StreamingConfig;
 use rusqlite::{params, Connection, Result as SqlResult};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -37,14 +38,18 @@ impl Database {
     }
 
     fn get_db_path() -> Result<PathBuf, String> {
+        Ok(Self::app_data_dir()?.join("causal.db"))
+    }
+
+    /// Directory holding the database and other app data (`~/.causal`)
+    pub fn app_data_dir() -> Result<PathBuf, String> {
         // For now, use a simple path in the home directory
         // In a real deployment, we'd get the proper app data directory from Tauri's AppHandle
         let home_dir = std::env::var("HOME")
             .or_else(|_| std::env::var("USERPROFILE"))
             .map_err(|_| "Failed to get home directory".to_string())?;
 
-        let app_data_dir = PathBuf::from(home_dir).join(".causal");
-        Ok(app_data_dir.join("causal.db"))
+        Ok(PathBuf::from(home_dir).join(".causal"))
     }
 
     fn init_schema(&self) -> Result<(), String> {
@@ -60,8 +65,10 @@ impl Database {
                 name TEXT NOT NULL UNIQUE,
                 description TEXT NOT NULL,
                 api_key_reference TEXT,
+                streaming_config TEXT,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                vocabulary TEXT
             )",
             [],
         )
@@ -84,6 +91,12 @@ impl Database {
                 metadata_average_confidence REAL NOT NULL,
                 status TEXT NOT NULL,
                 created_at INTEGER NOT NULL,
+                audio_path TEXT,
+                metadata_speakers TEXT,
+                metadata_language TEXT,
+                metadata_provider_sessions TEXT,
+                metadata_silence_ratio REAL,
+                metadata_paused_intervals TEXT,
                 FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
             )",
             [],
@@ -96,6 +109,30 @@ impl Database {
         )
         .map_err(|e| format!("Failed to create index: {}", e))?;
 
+        // Word-level timestamps, one row per transcribed word
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS recording_words (
+                recording_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                turn_order INTEGER NOT NULL,
+                speaker TEXT,
+                text TEXT NOT NULL,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER NOT NULL,
+                confidence REAL NOT NULL,
+                PRIMARY KEY(recording_id, position),
+                FOREIGN KEY(recording_id) REFERENCES recordings(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create recording_words table: {}", e))?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_recording_words_start ON recording_words(recording_id, start_ms)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create index: {}", e))?;
+
         // Create secure_settings table for encrypted API keys and sensitive configuration
         conn.execute(
             "CREATE TABLE IF NOT EXISTS secure_settings (
@@ -117,6 +154,54 @@ impl Database {
         );
         // Note: This will fail if column already exists, which is expected behavior
 
+        // Migration: Add audio_path column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN audio_path TEXT",
+            [],
+        );
+
+        // Migration: Add streaming_config column to projects table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE projects ADD COLUMN streaming_config TEXT",
+            [],
+        );
+
+        // Migration: Add metadata_speakers column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN metadata_speakers TEXT",
+            [],
+        );
+
+        // Migration: Add metadata_language column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN metadata_language TEXT",
+            [],
+        );
+
+        // Migration: Add metadata_provider_sessions column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN metadata_provider_sessions TEXT",
+            [],
+        );
+
+        // Migration: Add metadata_silence_ratio column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN metadata_silence_ratio REAL",
+            [],
+        );
+
+        // Migration: Add metadata_paused_intervals column to recordings table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE recordings ADD COLUMN metadata_paused_intervals TEXT",
+            [],
+        );
+
+        // Migration: Add vocabulary column to projects table if it doesn't exist
+        let _migration_result = conn.execute(
+            "ALTER TABLE projects ADD COLUMN vocabulary TEXT",
+            [],
+        );
+
         // Apply database performance optimizations
         // Note: Some PRAGMA statements return values, so we need to handle them properly
 
@@ -161,19 +246,44 @@ impl Database {
         SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(timestamp as u64)
     }
 
+    // Helper to serialize an optional streaming config as JSON
+    fn streaming_config_to_json(config: &Option<StreamingConfig>) -> Result<Option<String>, String> {
+        config
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize streaming_config: {}", e))
+    }
+
+    // Helper to read a streaming config column; unreadable values fall back to none
+    fn streaming_config_from_json(json: Option<String>) -> Option<StreamingConfig> {
+        json.and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    // Helper to read a vocabulary column; missing or unreadable values mean no terms
+    fn vocabulary_from_json(json: Option<String>) -> Vec<String> {
+        json.and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
     // Project operations
     pub async fn create_project(&self, project: Project) -> Result<Project, String> {
         let conn = self.connection.lock().await;
+        let streaming_config_json = Self::streaming_config_to_json(&project.streaming_config)?;
+        let vocabulary_json = serde_json::to_string(&project.vocabulary)
+            .map_err(|e| format!("Failed to serialize vocabulary: {}", e))?;
 
         conn.execute(
-            "INSERT INTO projects (id, name, description, api_key_reference, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO projects (id, name, description, api_key_reference, streaming_config, created_at, updated_at, vocabulary) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 project.id,
                 project.name,
                 project.description,
                 project.api_key_reference,
+                streaming_config_json,
                 Self::system_time_to_timestamp(project.created_at),
                 Self::system_time_to_timestamp(project.updated_at),
+                vocabulary_json,
             ],
         )
         .map_err(|e| {
@@ -192,7 +302,7 @@ impl Database {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, api_key_reference, created_at, updated_at FROM projects WHERE id = ?1",
+                "SELECT id, name, description, api_key_reference, streaming_config, created_at, updated_at, vocabulary FROM projects WHERE id = ?1",
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
@@ -203,8 +313,10 @@ impl Database {
                     name: row.get(1)?,
                     description: row.get(2)?,
                     api_key_reference: row.get(3)?,
-                    created_at: Self::timestamp_to_system_time(row.get(4)?),
-                    updated_at: Self::timestamp_to_system_time(row.get(5)?),
+                    streaming_config: Self::streaming_config_from_json(row.get(4)?),
+                    vocabulary: Self::vocabulary_from_json(row.get(7)?),
+                    created_at: Self::timestamp_to_system_time(row.get(5)?),
+                    updated_at: Self::timestamp_to_system_time(row.get(6)?),
                 })
             })
             .map_err(|_| "Project not found".to_string())?;
@@ -216,7 +328,7 @@ impl Database {
         let conn = self.connection.lock().await;
 
         let mut stmt = conn
-            .prepare("SELECT id, name, description, api_key_reference, created_at, updated_at FROM projects ORDER BY created_at DESC")
+            .prepare("SELECT id, name, description, api_key_reference, streaming_config, created_at, updated_at, vocabulary FROM projects ORDER BY created_at DESC")
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
         let projects = stmt
@@ -226,8 +338,10 @@ impl Database {
                     name: row.get(1)?,
                     description: row.get(2)?,
                     api_key_reference: row.get(3)?,
-                    created_at: Self::timestamp_to_system_time(row.get(4)?),
-                    updated_at: Self::timestamp_to_system_time(row.get(5)?),
+                    streaming_config: Self::streaming_config_from_json(row.get(4)?),
+                    vocabulary: Self::vocabulary_from_json(row.get(7)?),
+                    created_at: Self::timestamp_to_system_time(row.get(5)?),
+                    updated_at: Self::timestamp_to_system_time(row.get(6)?),
                 })
             })
             .map_err(|e| format!("Failed to query projects: {}", e))?
@@ -315,6 +429,59 @@ impl Database {
         Ok(())
     }
 
+    /// Set or clear the default streaming config for a project
+    pub async fn update_project_streaming_config(
+        &self,
+        project_id: &str,
+        streaming_config: Option<StreamingConfig>,
+    ) -> Result<Project, String> {
+        let streaming_config_json = Self::streaming_config_to_json(&streaming_config)?;
+        let conn = self.connection.lock().await;
+
+        let now = Self::system_time_to_timestamp(SystemTime::now());
+
+        let updated = conn
+            .execute(
+                "UPDATE projects SET streaming_config = ?1, updated_at = ?2 WHERE id = ?3",
+                params![streaming_config_json, now, project_id],
+            )
+            .map_err(|e| format!("Failed to update project streaming config: {}", e))?;
+
+        if updated == 0 {
+            return Err("Project not found".to_string());
+        }
+
+        drop(conn);
+        self.get_project(project_id).await
+    }
+
+    /// Replace the vocabulary for a project
+    pub async fn update_project_vocabulary(
+        &self,
+        project_id: &str,
+        vocabulary: Vec<String>,
+    ) -> Result<Project, String> {
+        let vocabulary_json = serde_json::to_string(&vocabulary)
+            .map_err(|e| format!("Failed to serialize vocabulary: {}", e))?;
+        let conn = self.connection.lock().await;
+
+        let now = Self::system_time_to_timestamp(SystemTime::now());
+
+        let updated = conn
+            .execute(
+                "UPDATE projects SET vocabulary = ?1, updated_at = ?2 WHERE id = ?3",
+                params![vocabulary_json, now, project_id],
+            )
+            .map_err(|e| format!("Failed to update project vocabulary: {}", e))?;
+
+        if updated == 0 {
+            return Err("Project not found".to_string());
+        }
+
+        drop(conn);
+        self.get_project(project_id).await
+    }
+
     pub async fn delete_project(&self, id: &str) -> Result<(), String> {
         let conn = self.connection.lock().await;
 
@@ -357,6 +524,12 @@ impl Database {
             .map_err(|e| format!("Failed to serialize key_points: {}", e))?;
         let action_items_json = serde_json::to_string(&recording.action_items)
             .map_err(|e| format!("Failed to serialize action_items: {}", e))?;
+        let speakers_json = serde_json::to_string(&recording.metadata.speakers)
+            .map_err(|e| format!("Failed to serialize speakers: {}", e))?;
+        let provider_sessions_json = serde_json::to_string(&recording.metadata.provider_sessions)
+            .map_err(|e| format!("Failed to serialize provider sessions: {}", e))?;
+        let paused_intervals_json = serde_json::to_string(&recording.metadata.paused_intervals)
+            .map_err(|e| format!("Failed to serialize paused intervals: {}", e))?;
         let status_str = match recording.status {
             RecordingStatus::Recording => "Recording",
             RecordingStatus::Processing => "Processing",
@@ -369,8 +542,9 @@ impl Database {
                 id, project_id, name, raw_transcript, enhanced_transcript, summary,
                 key_points, action_items, metadata_duration_seconds, metadata_word_count,
                 metadata_chunk_count, metadata_turn_count, metadata_average_confidence,
-                status, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                status, created_at, audio_path, metadata_speakers, metadata_language,
+                metadata_provider_sessions, metadata_silence_ratio, metadata_paused_intervals
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
             params![
                 recording.id,
                 recording.project_id,
@@ -387,6 +561,12 @@ impl Database {
                 recording.metadata.average_confidence,
                 status_str,
                 Self::system_time_to_timestamp(recording.created_at),
+                recording.audio_path,
+                speakers_json,
+                recording.metadata.language,
+                provider_sessions_json,
+                recording.metadata.silence_ratio,
+                paused_intervals_json,
             ],
         )
         .map_err(|e| format!("Failed to create recording: {}", e))?;
@@ -402,7 +582,8 @@ impl Database {
                 "SELECT id, project_id, name, raw_transcript, enhanced_transcript, summary,
                  key_points, action_items, metadata_duration_seconds, metadata_word_count,
                  metadata_chunk_count, metadata_turn_count, metadata_average_confidence,
-                 status, created_at FROM recordings WHERE id = ?1",
+                 status, created_at, audio_path, metadata_speakers, metadata_language, metadata_provider_sessions,
+                 metadata_silence_ratio, metadata_paused_intervals FROM recordings WHERE id = ?1",
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
@@ -411,6 +592,9 @@ impl Database {
                 let key_points_json: String = row.get(6)?;
                 let action_items_json: String = row.get(7)?;
                 let status_str: String = row.get(13)?;
+                let speakers_json: Option<String> = row.get(16)?;
+                let provider_sessions_json: Option<String> = row.get(18)?;
+                let paused_intervals_json: Option<String> = row.get(20)?;
 
                 let key_points: Vec<String> =
                     serde_json::from_str(&key_points_json).unwrap_or_default();
@@ -439,9 +623,21 @@ impl Database {
                         chunk_count: row.get::<_, i64>(10)? as usize,
                         turn_count: row.get::<_, i64>(11)? as usize,
                         average_confidence: row.get(12)?,
+                        speakers: speakers_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        language: row.get(17)?,
+                        provider_sessions: provider_sessions_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        silence_ratio: row.get(19)?,
+                        paused_intervals: paused_intervals_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
                     },
                     status,
                     created_at: Self::timestamp_to_system_time(row.get(14)?),
+                    audio_path: row.get(15)?,
                 })
             })
             .map_err(|_| "Recording not found".to_string())?;
@@ -470,7 +666,8 @@ impl Database {
                 "SELECT id, project_id, name, raw_transcript, enhanced_transcript, summary,
                  key_points, action_items, metadata_duration_seconds, metadata_word_count,
                  metadata_chunk_count, metadata_turn_count, metadata_average_confidence,
-                 status, created_at FROM recordings WHERE project_id = ?1 ORDER BY created_at DESC",
+                 status, created_at, audio_path, metadata_speakers, metadata_language, metadata_provider_sessions,
+                 metadata_silence_ratio, metadata_paused_intervals FROM recordings WHERE project_id = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
@@ -479,6 +676,9 @@ impl Database {
                 let key_points_json: String = row.get(6)?;
                 let action_items_json: String = row.get(7)?;
                 let status_str: String = row.get(13)?;
+                let speakers_json: Option<String> = row.get(16)?;
+                let provider_sessions_json: Option<String> = row.get(18)?;
+                let paused_intervals_json: Option<String> = row.get(20)?;
 
                 let key_points: Vec<String> =
                     serde_json::from_str(&key_points_json).unwrap_or_default();
@@ -507,9 +707,21 @@ impl Database {
                         chunk_count: row.get::<_, i64>(10)? as usize,
                         turn_count: row.get::<_, i64>(11)? as usize,
                         average_confidence: row.get(12)?,
+                        speakers: speakers_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        language: row.get(17)?,
+                        provider_sessions: provider_sessions_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        silence_ratio: row.get(19)?,
+                        paused_intervals: paused_intervals_json
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
                     },
                     status,
                     created_at: Self::timestamp_to_system_time(row.get(14)?),
+                    audio_path: row.get(15)?,
                 })
             })
             .map_err(|e| format!("Failed to query recordings: {}", e))?
@@ -532,6 +744,127 @@ impl Database {
         self.get_recording(id).await
     }
 
+    /// Store the timed words of a recording, replacing any already stored
+    pub async fn save_recording_words(
+        &self,
+        recording_id: &str,
+        words: &[RecordingWord],
+    ) -> Result<(), String> {
+        let mut conn = self.connection.lock().await;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        tx.execute(
+            "DELETE FROM recording_words WHERE recording_id = ?1",
+            params![recording_id],
+        )
+        .map_err(|e| format!("Failed to clear recording words: {}", e))?;
+
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT INTO recording_words (
+                        recording_id, position, turn_order, speaker, text, start_ms, end_ms, confidence
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            for (position, word) in words.iter().enumerate() {
+                stmt.execute(params![
+                    recording_id,
+                    position as i64,
+                    word.turn_order as i64,
+                    word.speaker,
+                    word.text,
+                    word.start_ms,
+                    word.end_ms,
+                    word.confidence,
+                ])
+                .map_err(|e| format!("Failed to save recording words: {}", e))?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Failed to save recording words: {}", e))
+    }
+
+    /// Timed words of a recording in transcript order, optionally only those
+    /// overlapping `start_ms..end_ms`
+    pub async fn get_recording_words(
+        &self,
+        recording_id: &str,
+        start_ms: Option<u32>,
+        end_ms: Option<u32>,
+    ) -> Result<Vec<RecordingWord>, String> {
+        let conn = self.connection.lock().await;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT turn_order, speaker, text, start_ms, end_ms, confidence
+                 FROM recording_words
+                 WHERE recording_id = ?1 AND end_ms >= ?2 AND start_ms <= ?3
+                 ORDER BY position",
+            )
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+        let words = stmt
+            .query_map(
+                params![
+                    recording_id,
+                    start_ms.unwrap_or(0),
+                    end_ms.unwrap_or(u32::MAX)
+                ],
+                |row| {
+                    Ok(RecordingWord {
+                        turn_order: row.get::<_, i64>(0)? as usize,
+                        speaker: row.get(1)?,
+                        text: row.get(2)?,
+                        start_ms: row.get(3)?,
+                        end_ms: row.get(4)?,
+                        confidence: row.get(5)?,
+                    })
+                },
+            )
+            .map_err(|e| format!("Failed to query recording words: {}", e))?
+            .collect::<SqlResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect recording words: {}", e))?;
+
+        Ok(words)
+    }
+
+    /// Show a speaker under a real name (or their label again, for `None`)
+    pub async fn update_speaker_name(
+        &self,
+        recording_id: &str,
+        label: &str,
+        name: Option<String>,
+    ) -> Result<Recording, String> {
+        let mut recording = self.get_recording(recording_id).await?;
+
+        let speaker = recording
+            .metadata
+            .speakers
+            .iter_mut()
+            .find(|speaker| speaker.label == label)
+            .ok_or_else(|| format!("Speaker '{}' not found in recording", label))?;
+        speaker.name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+
+        let speakers_json = serde_json::to_string(&recording.metadata.speakers)
+            .map_err(|e| format!("Failed to serialize speakers: {}", e))?;
+
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "UPDATE recordings SET metadata_speakers = ?1 WHERE id = ?2",
+            params![speakers_json, recording_id],
+        )
+        .map_err(|e| format!("Failed to update recording: {}", e))?;
+
+        Ok(recording)
+    }
+
     pub async fn update_recording_summary(
         &self,
         id: &str,
@@ -782,7 +1115,7 @@ impl Default for Database {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::database::models::Project;
+    use crate::database::models::{PausedInterval, Project, ProviderSession, SpeakerStats};
 
     fn test_db() -> Database {
         // Use in-memory database for tests
@@ -794,6 +1127,12 @@ mod tests {
         db
     }
 
+    async fn create_test_project(db: &Database, name: &str) -> Project {
+        db.create_project(Project::new(name.to_string(), "".to_string()))
+            .await
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn test_create_and_get_project() {
         let db = test_db();
@@ -837,4 +1176,196 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Project with this name already exists");
     }
+
+    #[tokio::test]
+    async fn test_recording_metadata_round_trip() {
+        let db = test_db();
+        let project = create_test_project(&db, "Vertrieb").await;
+
+        let mut session = ProviderSession::new("session-1".to_string(), None, 1_700_000_000);
+        session.audio_duration_seconds = Some(10.5);
+        session.session_duration_seconds = Some(11.0);
+        let mut recording = Recording::new(
+            project.id.clone(),
+            "Wochenrunde".to_string(),
+            "Guten Morgen".to_string(),
+            String::new(),
+        )
+        .with_audio_path(Some("/tmp/call.wav".to_string()));
+        recording.metadata.language = Some("de".to_string());
+        recording.metadata.provider_sessions = vec![session];
+        recording.metadata.silence_ratio = Some(0.25);
+        recording.metadata.paused_intervals = vec![PausedInterval {
+            start_secs: 12.5,
+            end_secs: Some(30.0),
+        }];
+        let id = recording.id.clone();
+        db.create_recording(recording.clone()).await.unwrap();
+
+        let retrieved = db.get_recording(&id).await.unwrap();
+        let listed = db.list_recordings(&project.id).await.unwrap();
+        for saved in [&retrieved, &listed[0]] {
+            assert_eq!(saved.audio_path.as_deref(), Some("/tmp/call.wav"));
+            assert_eq!(saved.metadata.language.as_deref(), Some("de"));
+            assert_eq!(
+                saved.metadata.provider_sessions,
+                recording.metadata.provider_sessions
+            );
+            assert_eq!(saved.metadata.silence_ratio, Some(0.25));
+            assert_eq!(
+                saved.metadata.paused_intervals,
+                recording.metadata.paused_intervals
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_streaming_config_round_trip() {
+        let db = test_db();
+        let project = create_test_project(&db, "Standups").await;
+        assert!(project.streaming_config.is_none());
+
+        let config = StreamingConfig {
+            max_turn_silence: 800,
+            ..Default::default()
+        };
+        let updated = db
+            .update_project_streaming_config(&project.id, Some(config.clone()))
+            .await
+            .unwrap();
+        assert_eq!(updated.streaming_config, Some(config.clone()));
+
+        let listed = db.list_projects().await.unwrap();
+        assert_eq!(listed[0].streaming_config, Some(config));
+
+        let cleared = db
+            .update_project_streaming_config(&project.id, None)
+            .await
+            .unwrap();
+        assert!(cleared.streaming_config.is_none());
+
+        assert!(db
+            .update_project_streaming_config("missing", None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_project_vocabulary_round_trip() {
+        let db = test_db();
+        let project = create_test_project(&db, "Earnings").await;
+        assert!(project.vocabulary.is_empty());
+
+        let terms = vec!["Megapack".to_string(), "TSLA".to_string()];
+        let updated = db
+            .update_project_vocabulary(&project.id, terms.clone())
+            .await
+            .unwrap();
+        assert_eq!(updated.vocabulary, terms);
+
+        let listed = db.list_projects().await.unwrap();
+        assert_eq!(listed[0].vocabulary, terms);
+
+        assert!(db
+            .update_project_vocabulary("missing", Vec::new())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_speaker() {
+        let db = test_db();
+        let project = create_test_project(&db, "Interviews").await;
+
+        let mut recording = Recording::new(
+            project.id.clone(),
+            "Interview".to_string(),
+            "Speaker A: Welcome. Speaker B: Thanks for having me.".to_string(),
+            String::new(),
+        );
+        recording.metadata.speakers = vec![
+            SpeakerStats::new("Speaker A".to_string()),
+            SpeakerStats::new("Speaker B".to_string()),
+        ];
+        let id = recording.id.clone();
+        db.create_recording(recording).await.unwrap();
+
+        let renamed = db
+            .update_speaker_name(&id, "Speaker B", Some(" Dana ".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(renamed.metadata.speakers[1].display_name(), "Dana");
+
+        let retrieved = db.get_recording(&id).await.unwrap();
+        assert_eq!(retrieved.metadata.speakers, renamed.metadata.speakers);
+        assert_eq!(
+            retrieved.summary_transcript(),
+            "Speaker A: Welcome. Dana: Thanks for having me."
+        );
+
+        let reset = db
+            .update_speaker_name(&id, "Speaker B", Some("".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(reset.metadata.speakers[1].display_name(), "Speaker B");
+
+        assert!(db
+            .update_speaker_name(&id, "Speaker C", Some("Sam".to_string()))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recording_words_round_trip() {
+        let db = test_db();
+        let project = create_test_project(&db, "Words").await;
+        let recording = db
+            .create_recording(Recording::new(
+                project.id.clone(),
+                "Call".to_string(),
+                "Hello there. Hi.".to_string(),
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        let word = |turn_order, text: &str, start_ms, end_ms| RecordingWord {
+            turn_order,
+            speaker: Some("Speaker A".to_string()),
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: 0.9,
+        };
+        let words = vec![
+            word(0, "Hello", 0, 400),
+            word(0, "there.", 450, 900),
+            word(1, "Hi.", 2000, 2300),
+        ];
+        db.save_recording_words(&recording.id, &words)
+            .await
+            .unwrap();
+
+        let all = db
+            .get_recording_words(&recording.id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all, words);
+
+        // Words overlapping the range, including one that starts before it
+        let range = db
+            .get_recording_words(&recording.id, Some(500), Some(2000))
+            .await
+            .unwrap();
+        let texts: Vec<_> = range.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, ["there.", "Hi."]);
+
+        // Words go with their recording
+        db.delete_recording(&recording.id).await.unwrap();
+        assert!(db
+            .get_recording_words(&recording.id, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
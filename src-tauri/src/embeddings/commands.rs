@@ -1,4 +1,4 @@
-/// Tauri commands for embeddings and semantic search functionality
+//! Tauri commands for embeddings and semantic search functionality
 
 use super::service::EmbeddingService;
 use super::storage::{DateRange, SimilarAnalysis};
@@ -57,6 +57,7 @@ pub struct AnalysisTrend {
 
 /// Store an analysis result with embedding
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn store_analysis_with_embedding(
     embeddings_state: State<'_, Mutex<EmbeddingsState>>,
     database: State<'_, Database>,
@@ -1,7 +1,7 @@
-/// ONNX-based embedding model for local inference
-///
-/// Uses all-MiniLM-L6-v2 sentence-transformers model to generate
-/// 384-dimensional embeddings for semantic search and similarity matching.
+//! ONNX-based embedding model for local inference
+//!
+//! Uses all-MiniLM-L6-v2 sentence-transformers model to generate
+//! 384-dimensional embeddings for semantic search and similarity matching.
 
 use ndarray::CowArray;
 use ort::{Environment, ExecutionProvider, GraphOptimizationLevel, Session, SessionBuilder, Value, execution_providers::CPUExecutionProviderOptions, tensor::OrtOwnedTensor};
@@ -142,6 +142,7 @@ impl EmbeddingModel {
     ///
     /// # Returns
     /// Vector of embedding vectors
+    #[allow(dead_code)] // Available for bulk re-indexing
     pub fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
         texts.iter()
             .map(|text| self.encode(text))
@@ -197,11 +198,13 @@ impl EmbeddingModel {
     }
 
     /// Get the model name
+    #[allow(dead_code)] // Available for diagnostics
     pub fn model_name(&self) -> &str {
         &self.model_name
     }
 
     /// Get the embedding dimension
+    #[allow(dead_code)] // Available for diagnostics
     pub fn dimension(&self) -> usize {
         EMBEDDING_DIM
     }
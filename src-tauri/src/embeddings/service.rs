@@ -1,7 +1,7 @@
-/// High-level embedding service for the application
-///
-/// Provides a unified interface for embedding generation, similarity search,
-/// and integration with the database layer.
+//! High-level embedding service for the application
+//!
+//! Provides a unified interface for embedding generation, similarity search,
+//! and integration with the database layer.
 
 use super::model::EmbeddingModel;
 use super::similarity::find_similar;
@@ -85,6 +85,7 @@ impl EmbeddingService {
     ///
     /// # Returns
     /// Vector of embedding vectors
+    #[allow(dead_code)] // Available for bulk re-indexing
     pub fn generate_embeddings(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
         // Ensure model is initialized
         if self.model.lock().map_err(|e| format!("Lock error: {}", e))?.is_none() {
@@ -101,6 +102,7 @@ impl EmbeddingService {
     }
 
     /// Store an analysis result with its embedding
+    #[allow(clippy::too_many_arguments)]
     pub fn store_analysis_with_embedding(
         &self,
         conn: &Connection,
@@ -142,6 +144,7 @@ impl EmbeddingService {
     ///
     /// # Returns
     /// Vector of similar analyses with similarity scores
+    #[allow(clippy::too_many_arguments)]
     pub fn find_similar_analyses(
         &self,
         conn: &Connection,
@@ -255,11 +258,13 @@ impl EmbeddingService {
     }
 
     /// Get the embedding dimension
+    #[allow(dead_code)] // Available for diagnostics
     pub fn dimension(&self) -> usize {
         384 // all-MiniLM-L6-v2 dimension
     }
 
     /// Get the model name
+    #[allow(dead_code)] // Available for diagnostics
     pub fn model_name(&self) -> &str {
         "all-MiniLM-L6-v2"
     }
@@ -1,7 +1,7 @@
-/// Vector similarity computation for semantic search
-///
-/// Provides efficient cosine similarity calculations and top-k
-/// similarity search functionality for embeddings.
+//! Vector similarity computation for semantic search
+//!
+//! Provides efficient cosine similarity calculations and top-k
+//! similarity search functionality for embeddings.
 
 use rayon::prelude::*;
 
@@ -30,7 +30,7 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         0.0
     } else {
         // Clamp to [-1.0, 1.0] to handle floating point errors
-        (dot_product / (norm_a * norm_b)).max(-1.0).min(1.0)
+        (dot_product / (norm_a * norm_b)).clamp(-1.0, 1.0)
     }
 }
 
@@ -76,6 +76,7 @@ pub fn find_similar<T: Clone + Send + Sync>(
 ///
 /// # Returns
 /// 2D vector of similarity scores
+#[allow(dead_code)] // Available for clustering related analyses
 pub fn pairwise_similarities(embeddings: &[Vec<f32>]) -> Vec<Vec<f32>> {
     embeddings
         .par_iter()
@@ -97,6 +98,7 @@ pub fn pairwise_similarities(embeddings: &[Vec<f32>]) -> Vec<Vec<f32>> {
 ///
 /// # Returns
 /// All candidates above the threshold, sorted by similarity
+#[allow(dead_code)] // Available for threshold-only searches
 pub fn find_above_threshold<T: Clone + Send + Sync>(
     query: &[f32],
     candidates: &[(T, Vec<f32>)],
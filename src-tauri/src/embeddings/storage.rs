@@ -1,7 +1,7 @@
-/// Database storage for embeddings and analysis results
-///
-/// Provides SQLite integration for storing analysis results with their
-/// vector embeddings and performing similarity-based queries.
+//! Database storage for embeddings and analysis results
+//!
+//! Provides SQLite integration for storing analysis results with their
+//! vector embeddings and performing similarity-based queries.
 
 use rusqlite::{params, Connection, Result as SqlResult};
 use serde::{Deserialize, Serialize};
@@ -146,6 +146,7 @@ pub fn init_embeddings_schema(conn: &Connection) -> Result<(), String> {
 }
 
 /// Store an analysis result with its embedding
+#[allow(clippy::too_many_arguments)]
 pub fn store_analysis(
     conn: &Connection,
     recording_id: &str,
@@ -252,6 +253,7 @@ pub fn get_all_analyses_with_embeddings(
 }
 
 /// Get a specific analysis by ID
+#[allow(dead_code)] // Available for analysis detail views
 pub fn get_analysis_by_id(
     conn: &Connection,
     analysis_id: i64,
@@ -294,6 +296,7 @@ pub fn get_analysis_by_id(
 }
 
 /// Get analysis count by project and type
+#[allow(dead_code)] // Available for storage statistics
 pub fn get_analysis_count(
     conn: &Connection,
     project_id: Option<&str>,
@@ -94,10 +94,10 @@ impl SettingsEncryption {
         let mut identifier = String::new();
 
         // Add OS information
-        identifier.push_str(&std::env::consts::OS);
-        identifier.push_str("-");
-        identifier.push_str(&std::env::consts::ARCH);
-        identifier.push_str("-");
+        identifier.push_str(std::env::consts::OS);
+        identifier.push('-');
+        identifier.push_str(std::env::consts::ARCH);
+        identifier.push('-');
 
         // Add current user information (if available)
         if let Ok(username) = std::env::var("USER").or_else(|_| std::env::var("USERNAME")) {
@@ -105,7 +105,7 @@ impl SettingsEncryption {
         } else {
             identifier.push_str("unknown-user");
         }
-        identifier.push_str("-");
+        identifier.push('-');
 
         // Add a constant application identifier
         identifier.push_str("causal-app-v2");
@@ -1,5 +1,6 @@
 use crate::intelligence::types::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use anthropic_sdk::Client;
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,6 +10,8 @@ use tokio::sync::mpsc;
 pub struct CompetitiveAgent {
     api_key: String,
     model: String,
+    /// Output language for the free-text fields
+    prompt: PromptSettings,
 }
 
 impl CompetitiveAgent {
@@ -17,11 +20,18 @@ impl CompetitiveAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(),
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the competitive analysis prompt with financial analyst expertise
-    fn build_competitive_prompt(text: &str) -> String {
+    fn build_competitive_prompt(text: &str, output_language: Option<&str>) -> String {
         format!(
             r#"You are a senior financial industry analyst with 15+ years of experience analyzing competitive dynamics in earnings calls, investor presentations, and business communications. Your expertise spans:
 
@@ -39,6 +49,7 @@ CRITICAL INSTRUCTIONS:
 3. Think like a buy-side or sell-side equity analyst
 4. Generate insightful follow-up questions that a senior analyst would ask
 5. Assess broader industry implications, not just company-specific details
+6. {language}
 
 ANALYSIS FRAMEWORK:
 
@@ -106,7 +117,8 @@ QUALITY STANDARDS:
 - Competitive moats should be sustainable advantages, not temporary ones
 
 JSON response:"#,
-            text = text
+            text = text,
+            language = language::json_response_instruction(output_language)
         )
     }
 
@@ -277,7 +289,7 @@ impl IntelligenceAgent for CompetitiveAgent {
         );
 
         // Build the prompt
-        let prompt = Self::build_competitive_prompt(&raw_text);
+        let prompt = Self::build_competitive_prompt(&raw_text, self.prompt.output_language.as_deref());
 
         // Create the API request
         let request = Client::new()
@@ -359,7 +371,7 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = CompetitiveAgent::build_competitive_prompt("We compete with Apple and Google");
+        let prompt = CompetitiveAgent::build_competitive_prompt("We compete with Apple and Google", None);
         assert!(prompt.contains("We compete with Apple and Google"));
         assert!(prompt.contains("competitive intelligence"));
         assert!(prompt.contains("competitors_mentioned"));
@@ -1,5 +1,6 @@
 use crate::intelligence::types::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use anthropic_sdk::Client;
 use async_trait::async_trait;
 use serde_json::json;
@@ -10,6 +11,8 @@ use tokio::sync::mpsc;
 pub struct FinancialAgent {
     api_key: String,
     model: String,
+    /// Output language for the free-text fields
+    prompt: PromptSettings,
 }
 
 impl FinancialAgent {
@@ -18,11 +21,18 @@ impl FinancialAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(),
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the financial analysis prompt
-    fn build_financial_prompt(text: &str) -> String {
+    fn build_financial_prompt(text: &str, output_language: Option<&str>) -> String {
         format!(
             r#"You are a financial analysis expert specializing in earnings calls and business communications. Extract and analyze financial metrics, numbers, and business performance indicators from the following transcript segment.
 
@@ -32,6 +42,7 @@ CRITICAL INSTRUCTIONS:
 3. Extract numerical values accurately (convert to numbers, not strings)
 4. Identify currency symbols and percentage values
 5. Focus on business-relevant financial metrics
+6. {language}
 
 Extract and analyze:
 - Financial metrics (revenue, profit, EBITDA, margins, growth rates, etc.)
@@ -64,7 +75,8 @@ Rules:
 - outlook: Overall financial sentiment (bullish/bearish/neutral) or null if unclear
 
 JSON response:"#,
-            text = text
+            text = text,
+            language = language::json_response_instruction(output_language)
         )
     }
 
@@ -178,7 +190,7 @@ impl IntelligenceAgent for FinancialAgent {
         );
 
         // Build the prompt
-        let prompt = Self::build_financial_prompt(&raw_text);
+        let prompt = Self::build_financial_prompt(&raw_text, self.prompt.output_language.as_deref());
 
         // Create the API request
         let request = Client::new()
@@ -258,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = FinancialAgent::build_financial_prompt("Revenue grew 15%");
+        let prompt = FinancialAgent::build_financial_prompt("Revenue grew 15%", None);
         assert!(prompt.contains("Revenue grew 15%"));
         assert!(prompt.contains("financial analysis"));
         assert!(prompt.contains("metrics"));
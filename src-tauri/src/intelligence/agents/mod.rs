@@ -1,11 +1,11 @@
-/// Individual intelligence agents for specialized analysis
-///
-/// Each agent focuses on a specific type of business intelligence analysis:
-/// - SentimentAgent: Emotional tone and sentiment analysis
-/// - FinancialAgent: Financial metrics and business performance
-/// - CompetitiveAgent: Competitive intelligence and market positioning
-/// - SummaryAgent: Key insights and business impact analysis
-/// - RiskAgent: Risk assessment and mitigation strategies
+//! Individual intelligence agents for specialized analysis
+//!
+//! Each agent focuses on a specific type of business intelligence analysis:
+//! - SentimentAgent: Emotional tone and sentiment analysis
+//! - FinancialAgent: Financial metrics and business performance
+//! - CompetitiveAgent: Competitive intelligence and market positioning
+//! - SummaryAgent: Key insights and business impact analysis
+//! - RiskAgent: Risk assessment and mitigation strategies
 
 pub mod sentiment;
 pub mod financial;
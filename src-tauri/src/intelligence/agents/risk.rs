@@ -1,5 +1,6 @@
 use crate::intelligence::types::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use anthropic_sdk::Client;
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,6 +10,8 @@ use tokio::sync::mpsc;
 pub struct RiskAgent {
     api_key: String,
     model: String,
+    /// Output language for the free-text fields
+    prompt: PromptSettings,
 }
 
 impl RiskAgent {
@@ -17,11 +20,18 @@ impl RiskAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(),
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the risk analysis prompt with promise detection and delivery risk focus
-    fn build_risk_prompt(text: &str) -> String {
+    fn build_risk_prompt(text: &str, output_language: Option<&str>) -> String {
         format!(
             r#"You are an expert risk analyst and business auditor specializing in promise detection and delivery risk assessment. Your role is to act as a critical evaluator who identifies commitments, analyzes delivery risks, and provides constructive risk insights.
 
@@ -35,6 +45,7 @@ CRITICAL INSTRUCTIONS - MUST FOLLOW EXACTLY:
 5. Be thorough but constructive in identifying risks
 6. Focus on actionable risk insights
 7. Keep descriptions concise - prioritize key information over lengthy explanations
+8. {language}
 
 PROMISE DETECTION:
 - Identify explicit promises (direct commitments, guarantees)
@@ -42,6 +53,7 @@ PROMISE DETECTION:
 - Categorize by type: delivery, timeline, financial, operational, quality
 - Assess specificity: specific (clear metrics/dates), vague (unclear), conditional (depends on factors)
 - Extract timelines and stakeholders when mentioned
+- Lines may start with the speaker (e.g. "Dana: ..."); use them as the stakeholder for their own promises
 
 DELIVERY RISK ASSESSMENT:
 - Evaluate risks that could prevent promise fulfillment
@@ -124,7 +136,8 @@ REMEMBER: Return ONLY the JSON object below with no additional text, explanation
   "risk_summary": "...",
   // ... rest of structure
 }}"#,
-            text = text
+            text = text,
+            language = language::json_response_instruction(output_language)
         )
     }
 
@@ -151,8 +164,7 @@ REMEMBER: Return ONLY the JSON object below with no additional text, explanation
         // Remove markdown code blocks
         if let Some(start) = cleaned.find("```json") {
             cleaned = &cleaned[start + 7..];
-        }
-        if let Some(start) = cleaned.find("```") {
+        } else if let Some(start) = cleaned.find("```") {
             cleaned = &cleaned[start + 3..];
         }
         if let Some(end) = cleaned.rfind("```") {
@@ -346,7 +358,7 @@ impl IntelligenceAgent for RiskAgent {
         );
 
         // Build the prompt
-        let prompt = Self::build_risk_prompt(&raw_text);
+        let prompt = Self::build_risk_prompt(&raw_text, self.prompt.output_language.as_deref());
 
         // Create the API request
         let request = Client::new()
@@ -473,7 +485,7 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = RiskAgent::build_risk_prompt("We plan to launch our new product by Q2 2024");
+        let prompt = RiskAgent::build_risk_prompt("We plan to launch our new product by Q2 2024", None);
         assert!(prompt.contains("We plan to launch our new product by Q2 2024"));
         assert!(prompt.contains("promise detection"));
         assert!(prompt.contains("delivery risk"));
@@ -1,5 +1,6 @@
 use crate::intelligence::types::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use anthropic_sdk::Client;
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,6 +10,8 @@ use tokio::sync::mpsc;
 pub struct SentimentAgent {
     api_key: String,
     model: String,
+    /// Output language for the free-text fields
+    prompt: PromptSettings,
 }
 
 impl SentimentAgent {
@@ -17,11 +20,18 @@ impl SentimentAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(),
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the sentiment analysis prompt
-    fn build_sentiment_prompt(text: &str) -> String {
+    fn build_sentiment_prompt(text: &str, output_language: Option<&str>) -> String {
         format!(
             r#"You are a business sentiment analysis expert specializing in earnings calls, meetings, and business communications. Analyze the emotional tone and sentiment of the following transcript segment.
 
@@ -30,6 +40,7 @@ CRITICAL INSTRUCTIONS:
 2. Use the EXACT format specified below
 3. Confidence scores must be between 0.0 and 1.0
 4. Overall sentiment must be one of: "positive", "negative", "neutral"
+5. {language}
 
 Analyze for:
 - Overall sentiment (positive/negative/neutral)
@@ -49,7 +60,8 @@ Required JSON format:
 }}
 
 JSON response:"#,
-            text = text
+            text = text,
+            language = language::json_response_instruction(output_language)
         )
     }
 
@@ -135,7 +147,7 @@ impl IntelligenceAgent for SentimentAgent {
         );
 
         // Build the prompt
-        let prompt = Self::build_sentiment_prompt(&raw_text);
+        let prompt = Self::build_sentiment_prompt(&raw_text, self.prompt.output_language.as_deref());
 
         // Create the API request
         let request = Client::new()
@@ -215,10 +227,13 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = SentimentAgent::build_sentiment_prompt("Great quarter!");
+        let prompt = SentimentAgent::build_sentiment_prompt("Great quarter!", None);
         assert!(prompt.contains("Great quarter!"));
         assert!(prompt.contains("sentiment analysis"));
         assert!(prompt.contains("JSON"));
+
+        let prompt = SentimentAgent::build_sentiment_prompt("Gutes Quartal!", Some("de"));
+        assert!(prompt.contains("Write your response in German"));
     }
 
     #[test]
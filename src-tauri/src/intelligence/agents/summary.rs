@@ -1,5 +1,6 @@
 use crate::intelligence::types::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use anthropic_sdk::Client;
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,6 +10,8 @@ use tokio::sync::mpsc;
 pub struct SummaryAgent {
     api_key: String,
     model: String,
+    /// Output language for the free-text fields
+    prompt: PromptSettings,
 }
 
 impl SummaryAgent {
@@ -17,11 +20,18 @@ impl SummaryAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(),
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the summary analysis prompt
-    fn build_summary_prompt(text: &str) -> String {
+    fn build_summary_prompt(text: &str, output_language: Option<&str>) -> String {
         format!(
             r#"You are a business intelligence expert specializing in extracting key insights and actionable information from earnings calls and business communications. Analyze the following transcript segment for main takeaways, decisions, and business impact.
 
@@ -30,6 +40,7 @@ CRITICAL INSTRUCTIONS:
 2. Use the EXACT format specified below
 3. Focus on actionable insights and business impact
 4. Identify decisions made and follow-up items
+5. {language}
 
 Analyze for:
 - Key points and main takeaways
@@ -58,7 +69,8 @@ Rules:
 - follow_up_required: Items that need additional attention or approval
 
 JSON response:"#,
-            text = text
+            text = text,
+            language = language::json_response_instruction(output_language)
         )
     }
 
@@ -164,7 +176,7 @@ impl IntelligenceAgent for SummaryAgent {
         );
 
         // Build the prompt
-        let prompt = Self::build_summary_prompt(&raw_text);
+        let prompt = Self::build_summary_prompt(&raw_text, self.prompt.output_language.as_deref());
 
         // Create the API request
         let request = Client::new()
@@ -244,9 +256,9 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = SummaryAgent::build_summary_prompt("We decided to expand operations");
+        let prompt = SummaryAgent::build_summary_prompt("We decided to expand operations", None);
         assert!(prompt.contains("We decided to expand operations"));
-        assert!(prompt.contains("key points"));
+        assert!(prompt.contains("Key points"));
         assert!(prompt.contains("action_items"));
     }
 
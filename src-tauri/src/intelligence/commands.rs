@@ -1,13 +1,14 @@
-/// Tauri commands for Business Intelligence functionality
-///
-/// This module provides the command interface between the React frontend and the
-/// Rust intelligence system. Commands handle configuration, status monitoring,
-/// and on-demand analysis requests.
+//! Tauri commands for Business Intelligence functionality
+//!
+//! This module provides the command interface between the React frontend and the
+//! Rust intelligence system. Commands handle configuration, status monitoring,
+//! and on-demand analysis requests.
 
 use super::types::*;
 use super::coordinator::IntelligenceCoordinator;
 use super::agents::*;
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -58,6 +59,10 @@ pub fn set_intelligence_config(
         return Err("At least one analysis type must be enabled".to_string());
     }
 
+    if let Some(output_language) = &config.output_language {
+        language::validate_output(output_language)?;
+    }
+
     state.config = config;
 
     // Invalidate coordinator to force recreation with new config
@@ -112,14 +117,27 @@ pub fn initialize_intelligence_system(
     // Create new coordinator with current config
     let mut coordinator = IntelligenceCoordinator::new(state.config.clone());
 
+    let prompt = PromptSettings::default().with_output_language(state.config.output_language.clone());
+
     // Register agents based on enabled analyses
     for analysis_type in &state.config.enabled_analyses {
+        let api_key = state.config.api_key.clone();
         let agent: Arc<dyn IntelligenceAgent> = match analysis_type {
-            AnalysisType::Sentiment => Arc::new(SentimentAgent::new(state.config.api_key.clone())),
-            AnalysisType::Financial => Arc::new(FinancialAgent::new(state.config.api_key.clone())),
-            AnalysisType::Competitive => Arc::new(CompetitiveAgent::new(state.config.api_key.clone())),
-            AnalysisType::Summary => Arc::new(SummaryAgent::new(state.config.api_key.clone())),
-            AnalysisType::Risk => Arc::new(RiskAgent::new(state.config.api_key.clone())),
+            AnalysisType::Sentiment => Arc::new(
+                SentimentAgent::new(api_key).with_prompt_settings(prompt.clone()),
+            ),
+            AnalysisType::Financial => Arc::new(
+                FinancialAgent::new(api_key).with_prompt_settings(prompt.clone()),
+            ),
+            AnalysisType::Competitive => Arc::new(
+                CompetitiveAgent::new(api_key).with_prompt_settings(prompt.clone()),
+            ),
+            AnalysisType::Summary => Arc::new(
+                SummaryAgent::new(api_key).with_prompt_settings(prompt.clone()),
+            ),
+            AnalysisType::Risk => Arc::new(
+                RiskAgent::new(api_key).with_prompt_settings(prompt.clone()),
+            ),
         };
 
         coordinator.register_agent(agent);
@@ -169,29 +187,36 @@ pub async fn analyze_text_buffer(
         is_complete: true,
     };
 
+    let prompt = PromptSettings::default().with_output_language(config.output_language.clone());
+
     // Run analysis with individual agents (avoid coordinator mutex issues)
     let mut results = HashMap::new();
 
     for analysis_type in &enabled_analyses {
         let result = match analysis_type {
             AnalysisType::Sentiment => {
-                let agent = SentimentAgent::new(config.api_key.clone());
+                let agent = SentimentAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Financial => {
-                let agent = FinancialAgent::new(config.api_key.clone());
+                let agent = FinancialAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Competitive => {
-                let agent = CompetitiveAgent::new(config.api_key.clone());
+                let agent = CompetitiveAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Summary => {
-                let agent = SummaryAgent::new(config.api_key.clone());
+                let agent = SummaryAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Risk => {
-                let agent = RiskAgent::new(config.api_key.clone());
+                let agent = RiskAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
         };
@@ -296,6 +321,7 @@ pub fn clear_intelligence_system(
 
 /// Analyze text buffer and store results with embeddings
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn analyze_and_store_text_buffer(
     intelligence_state: State<'_, Mutex<IntelligenceState>>,
     embeddings_state: State<'_, Mutex<crate::embeddings::commands::EmbeddingsState>>,
@@ -339,6 +365,8 @@ pub async fn analyze_and_store_text_buffer(
         is_complete: true,
     };
 
+    let prompt = PromptSettings::default().with_output_language(config.output_language.clone());
+
     // Run analysis with individual agents
     let mut results = HashMap::new();
 
@@ -377,23 +405,28 @@ pub async fn analyze_and_store_text_buffer(
         // Run analysis
         let result = match analysis_type {
             AnalysisType::Sentiment => {
-                let agent = SentimentAgent::new(config.api_key.clone());
+                let agent = SentimentAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Financial => {
-                let agent = FinancialAgent::new(config.api_key.clone());
+                let agent = FinancialAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Competitive => {
-                let agent = CompetitiveAgent::new(config.api_key.clone());
+                let agent = CompetitiveAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Summary => {
-                let agent = SummaryAgent::new(config.api_key.clone());
+                let agent = SummaryAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
             AnalysisType::Risk => {
-                let agent = RiskAgent::new(config.api_key.clone());
+                let agent = RiskAgent::new(config.api_key.clone())
+                    .with_prompt_settings(prompt.clone());
                 agent.analyze(&buffer).await
             },
         };
@@ -420,10 +453,7 @@ pub async fn analyze_and_store_text_buffer(
                             .unwrap_or_default();
 
                         // Calculate confidence score based on analysis type
-                        let confidence_score = match &analysis_result.sentiment {
-                            Some(s) => Some(s.confidence),
-                            None => None,
-                        };
+                        let confidence_score = analysis_result.sentiment.as_ref().map(|s| s.confidence);
 
                         // Store with embedding
                         if let Err(e) = service.store_analysis_with_embedding(
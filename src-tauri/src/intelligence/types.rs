@@ -154,6 +154,9 @@ pub struct IntelligenceConfig {
     pub max_tokens: u32,
     pub temperature: f32,
     pub concurrent_agents: usize,
+    /// Language for the analyses (e.g. "en"), or `None` to match the transcript
+    #[serde(default)]
+    pub output_language: Option<String>,
 }
 
 impl Default for IntelligenceConfig {
@@ -170,6 +173,7 @@ impl Default for IntelligenceConfig {
             max_tokens: 4096,
             temperature: 0.3,
             concurrent_agents: 4,
+            output_language: None,
         }
     }
 }
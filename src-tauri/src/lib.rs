@@ -33,7 +33,7 @@ mod encryption;
 mod error;
 mod intelligence;
 mod logging;
-mod transcription;
+pub mod transcription;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -45,7 +45,7 @@ use embeddings::commands::EmbeddingsState;
 use intelligence::commands::IntelligenceState;
 use logging::{MetricsCollector, MetricsSnapshot, LogEntry, LogFileInfo, LoggingStats};
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, State};
 use transcription::commands::AppState;
 
@@ -236,6 +236,11 @@ pub fn run() {
         }
     };
     let metrics = MetricsCollector::new();
+    // Transcription records into the same collector get_metrics reports
+    let app_state = AppState {
+        metrics: Arc::new(metrics.clone()),
+        ..AppState::default()
+    };
     let logging_state = Mutex::new(LoggingState {
         log_dir: fallback_log_dir,
         metrics,
@@ -317,7 +322,7 @@ pub fn run() {
             tracing::info!("✅ Application ready");
             Ok(())
         })
-        .manage(AppState::default())
+        .manage(app_state)
         .manage(database)
         .manage(logging_state)
         .manage(intelligence_state)
@@ -326,7 +331,11 @@ pub fn run() {
             greet,
             transcription::list_audio_devices,
             transcription::start_transcription,
+            transcription::start_transcription_from_source,
+            transcription::transcribe_file,
             transcription::stop_transcription,
+            transcription::pause_transcription,
+            transcription::resume_transcription,
             transcription::get_transcription_status,
             transcription::summarize_transcription,
             transcription::refine_transcript,
@@ -335,12 +344,16 @@ pub fn run() {
             database::list_projects,
             database::get_project,
             database::update_project,
+            database::update_project_streaming_config,
+            database::update_project_vocabulary,
             database::delete_project,
             // Database commands - Recordings
             database::create_recording,
             database::list_recordings,
             database::get_recording,
+            database::get_recording_words,
             database::update_recording_name,
+            database::rename_speaker,
             database::delete_recording,
             database::export_recording,
             database::get_database_stats,
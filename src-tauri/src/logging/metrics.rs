@@ -22,6 +22,10 @@ struct MetricsInner {
     audio_buffer_overruns: AtomicUsize,
     audio_buffer_underruns: AtomicUsize,
     total_audio_frames_processed: AtomicU64,
+    audio_buffer_pool_misses: AtomicU64,
+    audio_callbacks: AtomicU64,
+    total_audio_callback_us: AtomicU64,
+    max_audio_callback_us: AtomicU64,
 
     // API metrics
     api_calls_total: AtomicUsize,
@@ -54,6 +58,9 @@ pub struct MetricsSnapshot {
     pub audio_buffer_overruns: usize,
     pub audio_buffer_underruns: usize,
     pub total_audio_frames_processed: u64,
+    pub audio_buffer_pool_misses: u64,
+    pub avg_audio_callback_us: f64,
+    pub max_audio_callback_us: u64,
 
     // API stats
     pub api_calls_total: usize,
@@ -85,6 +92,10 @@ impl MetricsCollector {
                 audio_buffer_overruns: AtomicUsize::new(0),
                 audio_buffer_underruns: AtomicUsize::new(0),
                 total_audio_frames_processed: AtomicU64::new(0),
+                audio_buffer_pool_misses: AtomicU64::new(0),
+                audio_callbacks: AtomicU64::new(0),
+                total_audio_callback_us: AtomicU64::new(0),
+                max_audio_callback_us: AtomicU64::new(0),
                 api_calls_total: AtomicUsize::new(0),
                 api_calls_successful: AtomicUsize::new(0),
                 api_calls_failed: AtomicUsize::new(0),
@@ -122,9 +133,13 @@ impl MetricsCollector {
     }
 
     // Audio metrics
-    pub fn audio_buffer_overrun(&self) {
-        self.inner.audio_buffer_overruns.fetch_add(1, Ordering::Relaxed);
-        tracing::warn!("Audio buffer overrun detected");
+    /// Audio chunks discarded because the consumer fell behind
+    pub fn audio_buffer_overrun(&self, dropped_chunks: usize) {
+        if dropped_chunks == 0 {
+            return;
+        }
+        self.inner.audio_buffer_overruns.fetch_add(dropped_chunks, Ordering::Relaxed);
+        tracing::warn!(dropped_chunks = dropped_chunks, "Audio buffer overrun detected");
     }
 
     pub fn audio_buffer_underrun(&self) {
@@ -136,6 +151,18 @@ impl MetricsCollector {
         self.inner.total_audio_frames_processed.fetch_add(count, Ordering::Relaxed);
     }
 
+    /// Buffers allocated because the audio buffer pool was empty
+    pub fn audio_buffer_pool_misses(&self, count: u64) {
+        self.inner.audio_buffer_pool_misses.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Time spent in audio device callbacks
+    pub fn audio_callbacks_timed(&self, count: u64, total_us: u64, max_us: u64) {
+        self.inner.audio_callbacks.fetch_add(count, Ordering::Relaxed);
+        self.inner.total_audio_callback_us.fetch_add(total_us, Ordering::Relaxed);
+        self.inner.max_audio_callback_us.fetch_max(max_us, Ordering::Relaxed);
+    }
+
     // API metrics
     pub fn api_call_started(&self) {
         self.inner.api_calls_total.fetch_add(1, Ordering::Relaxed);
@@ -200,6 +227,13 @@ impl MetricsCollector {
             0.0
         };
 
+        let audio_callbacks = self.inner.audio_callbacks.load(Ordering::Relaxed);
+        let avg_audio_callback = if audio_callbacks > 0 {
+            self.inner.total_audio_callback_us.load(Ordering::Relaxed) as f64 / audio_callbacks as f64
+        } else {
+            0.0
+        };
+
         let total_api_latency = self.inner.total_api_latency_ms.load(Ordering::Relaxed);
         let avg_api_latency = if api_calls > 0 {
             total_api_latency as f64 / api_calls as f64
@@ -216,6 +250,9 @@ impl MetricsCollector {
             audio_buffer_overruns: self.inner.audio_buffer_overruns.load(Ordering::Relaxed),
             audio_buffer_underruns: self.inner.audio_buffer_underruns.load(Ordering::Relaxed),
             total_audio_frames_processed: self.inner.total_audio_frames_processed.load(Ordering::Relaxed),
+            audio_buffer_pool_misses: self.inner.audio_buffer_pool_misses.load(Ordering::Relaxed),
+            avg_audio_callback_us: avg_audio_callback,
+            max_audio_callback_us: self.inner.max_audio_callback_us.load(Ordering::Relaxed),
             api_calls_total: api_calls,
             api_calls_successful: api_successful,
             api_calls_failed: api_failed,
@@ -240,6 +277,10 @@ impl MetricsCollector {
         self.inner.audio_buffer_overruns.store(0, Ordering::Relaxed);
         self.inner.audio_buffer_underruns.store(0, Ordering::Relaxed);
         self.inner.total_audio_frames_processed.store(0, Ordering::Relaxed);
+        self.inner.audio_buffer_pool_misses.store(0, Ordering::Relaxed);
+        self.inner.audio_callbacks.store(0, Ordering::Relaxed);
+        self.inner.total_audio_callback_us.store(0, Ordering::Relaxed);
+        self.inner.max_audio_callback_us.store(0, Ordering::Relaxed);
         self.inner.api_calls_total.store(0, Ordering::Relaxed);
         self.inner.api_calls_successful.store(0, Ordering::Relaxed);
         self.inner.api_calls_failed.store(0, Ordering::Relaxed);
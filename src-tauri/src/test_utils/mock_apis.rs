@@ -2,9 +2,20 @@
 
 use serde_json::json;
 
-/// Mock AssemblyAI WebSocket responses for testing
+/// Mock AssemblyAI WebSocket responses and server for testing
 pub mod assemblyai {
     use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+    use tokio_tungstenite::tungstenite::http::StatusCode;
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::{accept_hdr_async, WebSocketStream};
 
     /// Create a mock "Begin" message from AssemblyAI
     pub fn create_begin_message(session_id: &str) -> String {
@@ -72,6 +83,293 @@ pub mod assemblyai {
             })
             .collect()
     }
+
+    /// One step of a scripted mock connection
+    #[derive(Debug, Clone)]
+    pub enum MockStep {
+        /// Send a text message, e.g. from [`create_turn_message`]
+        Send(String),
+        /// Wait until this connection has received at least this many bytes of PCM
+        AwaitAudio(usize),
+        /// Drop the connection without a close frame, like a network failure
+        Drop,
+        /// Close the connection with a close frame
+        Close,
+    }
+
+    /// What the mock server saw on one streaming connection
+    #[derive(Debug, Clone, Default)]
+    pub struct MockConnection {
+        pub query: HashMap<String, String>,
+        pub audio_bytes: usize,
+        /// The client sent a terminate message
+        pub terminated: bool,
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        scripts: VecDeque<Vec<MockStep>>,
+        connections: Vec<MockConnection>,
+        unused_tokens: HashSet<String>,
+        token_requests: usize,
+    }
+
+    /// In-process stand-in for the AssemblyAI streaming API.
+    ///
+    /// Serves the token endpoint (checking the API key header) and the
+    /// streaming WebSocket (checking the query parameters and that the token
+    /// was issued and is used once). Each WebSocket connection plays the next
+    /// script, then keeps receiving audio until the client terminates.
+    pub struct MockServer {
+        base_url: String,
+        state: Arc<Mutex<MockState>>,
+        handle: JoinHandle<()>,
+    }
+
+    impl MockServer {
+        /// Start serving on a free local port, one script per connection
+        pub async fn start(api_key: &str, scripts: Vec<Vec<MockStep>>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("Failed to bind mock server");
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let state = Arc::new(Mutex::new(MockState {
+                scripts: scripts.into(),
+                ..Default::default()
+            }));
+
+            let api_key = api_key.to_string();
+            let server_state = state.clone();
+            let handle = tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle_connection(
+                        stream,
+                        api_key.clone(),
+                        server_state.clone(),
+                    ));
+                }
+            });
+
+            Self {
+                base_url,
+                state,
+                handle,
+            }
+        }
+
+        /// Base URL to pass to `AssemblyAIClient::with_base_url`
+        pub fn base_url(&self) -> &str {
+            &self.base_url
+        }
+
+        pub fn connections(&self) -> Vec<MockConnection> {
+            self.state.lock().unwrap().connections.clone()
+        }
+
+        pub fn token_requests(&self) -> usize {
+            self.state.lock().unwrap().token_requests
+        }
+
+        /// Poll until `condition` holds for the connections, or 5s pass
+        pub async fn wait_for(&self, condition: impl Fn(&[MockConnection]) -> bool) -> bool {
+            for _ in 0..500 {
+                if condition(&self.state.lock().unwrap().connections) {
+                    return true;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            false
+        }
+    }
+
+    impl Drop for MockServer {
+        fn drop(&mut self) {
+            self.handle.abort();
+        }
+    }
+
+    async fn handle_connection(stream: TcpStream, api_key: String, state: Arc<Mutex<MockState>>) {
+        // Token requests are plain HTTP; anything else is a WebSocket upgrade
+        let mut head = [0u8; 32];
+        let is_token_request = match stream.peek(&mut head).await {
+            Ok(n) => head[..n].starts_with(b"GET /v3/token"),
+            Err(_) => return,
+        };
+
+        if is_token_request {
+            serve_token(stream, &api_key, &state).await;
+        } else {
+            serve_stream(stream, &api_key, &state).await;
+        }
+    }
+
+    async fn serve_token(mut stream: TcpStream, api_key: &str, state: &Mutex<MockState>) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+
+        let request = String::from_utf8_lossy(&request);
+        let authorized = request.lines().any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.eq_ignore_ascii_case("authorization") && value.trim() == api_key
+            })
+        });
+
+        let (status, body) = if authorized {
+            let mut state = state.lock().unwrap();
+            state.token_requests += 1;
+            let token = format!("mock-token-{}", state.token_requests);
+            state.unused_tokens.insert(token.clone());
+            (
+                "200 OK",
+                json!({ "token": token, "expires_in_seconds": 60 }),
+            )
+        } else {
+            ("401 Unauthorized", json!({ "error": "Invalid API key" }))
+        };
+
+        let body = body.to_string();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+    }
+
+    /// Reject the handshake unless the query carries everything the real API
+    /// requires, plus a token this server issued and nothing else secret
+    fn check_handshake(
+        request: &Request,
+        api_key: &str,
+        state: &Mutex<MockState>,
+    ) -> Result<HashMap<String, String>, (StatusCode, String)> {
+        if request.uri().path() != "/v3/ws" {
+            return Err((StatusCode::NOT_FOUND, "Unknown path".to_string()));
+        }
+
+        let query: HashMap<String, String> = request
+            .uri()
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        if query.values().any(|value| value.contains(api_key)) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "API key sent in the URL".to_string(),
+            ));
+        }
+        if query.get("encoding").map(String::as_str) != Some("pcm_s16le") {
+            return Err((StatusCode::BAD_REQUEST, "Unsupported encoding".to_string()));
+        }
+        if query
+            .get("sample_rate")
+            .and_then(|rate| rate.parse::<u32>().ok())
+            .is_none()
+        {
+            return Err((StatusCode::BAD_REQUEST, "Missing sample_rate".to_string()));
+        }
+
+        let token_valid = query
+            .get("token")
+            .is_some_and(|token| state.lock().unwrap().unused_tokens.remove(token));
+        if !token_valid {
+            return Err((StatusCode::UNAUTHORIZED, "Invalid token".to_string()));
+        }
+
+        Ok(query)
+    }
+
+    // The handshake callback's error type is fixed by tungstenite
+    #[allow(clippy::result_large_err)]
+    async fn serve_stream(stream: TcpStream, api_key: &str, state: &Mutex<MockState>) {
+        let mut query = None;
+        let callback = |request: &Request, response: Response| {
+            let checked = check_handshake(request, api_key, state);
+            match checked {
+                Ok(params) => {
+                    query = Some(params);
+                    Ok(response)
+                }
+                Err((status, reason)) => {
+                    let mut error = ErrorResponse::new(Some(reason));
+                    *error.status_mut() = status;
+                    Err(error)
+                }
+            }
+        };
+        let Ok(mut ws) = accept_hdr_async(stream, callback).await else {
+            return;
+        };
+
+        let (index, script) = {
+            let mut state = state.lock().unwrap();
+            state.connections.push(MockConnection {
+                query: query.unwrap_or_default(),
+                ..Default::default()
+            });
+            (
+                state.connections.len() - 1,
+                state.scripts.pop_front().unwrap_or_default(),
+            )
+        };
+
+        for step in script {
+            match step {
+                MockStep::Send(text) => {
+                    if ws.send(Message::Text(text)).await.is_err() {
+                        return;
+                    }
+                }
+                MockStep::AwaitAudio(bytes) => {
+                    while state.lock().unwrap().connections[index].audio_bytes < bytes {
+                        if !receive(&mut ws, index, state).await {
+                            return;
+                        }
+                    }
+                }
+                MockStep::Drop => return,
+                MockStep::Close => {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+            }
+        }
+
+        while receive(&mut ws, index, state).await {}
+    }
+
+    /// Handle one client message; false once the connection is done
+    async fn receive(
+        ws: &mut WebSocketStream<TcpStream>,
+        index: usize,
+        state: &Mutex<MockState>,
+    ) -> bool {
+        match ws.next().await {
+            Some(Ok(Message::Binary(audio))) => {
+                state.lock().unwrap().connections[index].audio_bytes += audio.len();
+                true
+            }
+            Some(Ok(Message::Text(text))) if text.contains("terminate") => {
+                state.lock().unwrap().connections[index].terminated = true;
+                let _ = ws.send(Message::Text(create_termination_message())).await;
+                let _ = ws.close(None).await;
+                false
+            }
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => false,
+            Some(Ok(_)) => true,
+        }
+    }
 }
 
 /// Mock Claude API responses for testing
@@ -3,9 +3,10 @@
 //! This module provides common test helpers, mocks, and fixtures
 //! used across the test suite.
 
+pub mod mock_apis;
+
 use crate::database::models::{Project, Recording};
 use crate::transcription::buffer::TranscriptionBuffer;
-use chrono::Utc;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -23,27 +24,17 @@ pub fn create_test_db_path() -> (TempDir, PathBuf) {
 
 /// Create a mock project for testing
 pub fn create_mock_project(name: &str, description: &str) -> Project {
-    Project {
-        id: 1,
-        name: name.to_string(),
-        description: description.to_string(),
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-    }
+    Project::new(name.to_string(), description.to_string())
 }
 
 /// Create a mock recording for testing
-pub fn create_mock_recording(project_id: i64, name: &str) -> Recording {
-    Recording {
-        id: 1,
+pub fn create_mock_recording(project_id: String, name: &str) -> Recording {
+    Recording::new(
         project_id,
-        name: name.to_string(),
-        transcript: Some("Test transcript".to_string()),
-        enhanced_transcript: Some("Enhanced test transcript".to_string()),
-        duration_seconds: Some(120),
-        created_at: Utc::now(),
-        metadata: None,
-    }
+        name.to_string(),
+        "Test transcript".to_string(),
+        "Enhanced test transcript".to_string(),
+    )
 }
 
 /// Create a mock transcription buffer for testing
@@ -1,9 +1,49 @@
+use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 
-const ASSEMBLYAI_WS_URL: &str = "wss://streaming.assemblyai.com/v3/ws";
+use super::language;
+use super::provider::{ProviderEvent, TranscriptionProvider};
+use super::reconnect::{Backoff, ReplayBuffer};
+
+/// Universal Streaming host; the WebSocket and token endpoints live under it
+const ASSEMBLYAI_BASE_URL: &str = "https://streaming.assemblyai.com";
+
+/// Streaming WebSocket, relative to the base URL
+const STREAMING_PATH: &str = "/v3/ws";
+
+/// Exchanges the API key for a temporary streaming token, relative to the base URL
+const TOKEN_PATH: &str = "/v3/token";
+
+/// Lifetime of a streaming token. It only has to last until the WebSocket
+/// connects; the session itself can run longer.
+const STREAMING_TOKEN_EXPIRES_SECS: u32 = 60;
+
+/// Delay before the first reconnection attempt, doubled after each failure
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest delay between reconnection attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Consecutive failed attempts before giving up (about 3.5 minutes)
+const RECONNECT_MAX_ATTEMPTS: u32 = 12;
+
+/// A connection that lasted this long resets the backoff
+const STABLE_CONNECTION: Duration = Duration::from_secs(30);
+
+/// Audio held for replay while reconnecting
+const REPLAY_BUFFER_SECS: usize = 60;
+
+/// Model used for every language other than English
+const MULTILINGUAL_MODEL: &str = "universal-streaming-multilingual";
+
+/// How long to wait for the session statistics after terminating
+const TERMINATION_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Result sent to frontend for each turn
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +55,20 @@ pub struct TranscriptResult {
     pub end_of_turn: bool,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub words: Vec<WordResult>,
+    /// Who spoke: the track label when a multi-track source is transcribed
+    /// (e.g. "me" / "them"), or the provider's label (e.g. "Speaker A")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Spoken language code (e.g. "de"), detected or as configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl TranscriptResult {
+    /// Start of the first word and end of the last, in ms from the start of the stream
+    pub fn span_ms(&self) -> Option<(u32, u32)> {
+        Some((self.words.first()?.start, self.words.last()?.end))
+    }
 }
 
 /// Individual word in a turn
@@ -32,10 +86,10 @@ pub struct WordResult {
 #[serde(tag = "type")]
 enum ServerMessage {
     Begin {
-        #[allow(dead_code)]
-        id: String, // Session ID from AssemblyAI - received but not currently used
-        #[allow(dead_code)]
-        expires_at: u64, // Unix timestamp - received but not currently used
+        /// Session ID, as shown in AssemblyAI's usage records
+        id: String,
+        /// Unix timestamp at which AssemblyAI ends the session
+        expires_at: u64,
     },
     Turn {
         turn_order: u32,
@@ -43,6 +97,12 @@ enum ServerMessage {
         end_of_turn_confidence: f32,
         transcript: String,
         words: Vec<Word>,
+        /// "A", "B", ... when speaker labels are enabled
+        #[serde(default)]
+        speaker_label: Option<String>,
+        /// Detected language, from the multilingual model
+        #[serde(default)]
+        language_code: Option<String>,
     },
     Termination {
         audio_duration_seconds: f32,
@@ -63,13 +123,81 @@ struct Word {
     word_is_final: bool,
 }
 
-/// Configuration for Universal Streaming
-#[derive(Debug, Clone)]
+/// Configuration for Universal Streaming turn detection.
+///
+/// Conversations with long pauses (interviews) want a longer
+/// `max_turn_silence`; rapid back-and-forth wants turns to end sooner.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct StreamingConfig {
+    /// Return punctuated, formatted text for finished turns
     pub format_turns: bool,
+    /// Confidence (0-1) needed to end a turn after a short silence
     pub end_of_turn_confidence_threshold: f32,
+    /// Silence in ms that ends a turn once the confidence threshold is met
     pub min_end_of_turn_silence_when_confident: u32,
+    /// Silence in ms that ends a turn regardless of confidence
     pub max_turn_silence: u32,
+    /// Label turns by speaker ("Speaker A", "Speaker B", ...)
+    pub speaker_labels: bool,
+    /// Spoken language code (e.g. "de"), or "auto" to detect it. English
+    /// when unset.
+    pub language: Option<String>,
+}
+
+/// Accepted range for both silence settings, in ms
+const TURN_SILENCE_RANGE_MS: std::ops::RangeInclusive<u32> = 100..=10_000;
+
+impl StreamingConfig {
+    /// Check the settings are within the ranges AssemblyAI accepts
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.end_of_turn_confidence_threshold) {
+            return Err(format!(
+                "End-of-turn confidence threshold must be between 0 and 1 (got {})",
+                self.end_of_turn_confidence_threshold
+            ));
+        }
+
+        for (name, value) in [
+            (
+                "Minimum end-of-turn silence",
+                self.min_end_of_turn_silence_when_confident,
+            ),
+            ("Maximum turn silence", self.max_turn_silence),
+        ] {
+            if !TURN_SILENCE_RANGE_MS.contains(&value) {
+                return Err(format!(
+                    "{} must be between {} and {}ms (got {}ms)",
+                    name,
+                    TURN_SILENCE_RANGE_MS.start(),
+                    TURN_SILENCE_RANGE_MS.end(),
+                    value
+                ));
+            }
+        }
+
+        if self.min_end_of_turn_silence_when_confident > self.max_turn_silence {
+            return Err(format!(
+                "Minimum end-of-turn silence ({}ms) can't exceed the maximum turn silence ({}ms)",
+                self.min_end_of_turn_silence_when_confident, self.max_turn_silence
+            ));
+        }
+
+        if let Some(language) = &self.language {
+            language::validate(language)?;
+        }
+
+        Ok(())
+    }
+
+    /// The spoken language, unless it is detected: English when unset.
+    /// Turns the server doesn't tag with a language are tagged with this.
+    pub fn fixed_language(&self) -> Option<String> {
+        match self.language.as_deref() {
+            None => Some("en".to_string()),
+            language => language::fixed(language).map(str::to_string),
+        }
+    }
 }
 
 impl Default for StreamingConfig {
@@ -79,224 +207,1043 @@ impl Default for StreamingConfig {
             end_of_turn_confidence_threshold: 0.4,
             min_end_of_turn_silence_when_confident: 400,
             max_turn_silence: 1280,
+            speaker_labels: false,
+            language: None,
         }
     }
 }
 
+/// Response from the token endpoint
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    token: String,
+}
+
+/// The `keyterms_prompt` query value: a JSON array of the terms,
+/// percent-encoded
+fn encode_keyterms(keyterms: &[String]) -> String {
+    let json = serde_json::Value::from(keyterms.to_vec()).to_string();
+    json.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Replace every occurrence of `secret` in `text`, for error messages that
+/// may quote a request
+fn redact(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        text.to_string()
+    } else {
+        text.replace(secret, "[redacted]")
+    }
+}
+
 pub struct AssemblyAIClient {
     api_key: String,
+    http: reqwest::Client,
+    /// Token endpoint (https) and streaming endpoint (wss)
+    token_url: String,
+    ws_url: String,
+    /// Used when streaming as a [`TranscriptionProvider`]
+    config: StreamingConfig,
+    /// Terms to boost recognition of
+    keyterms: Vec<String>,
+}
+
+/// Maps turns from successive connections onto one continuous session. Each
+/// connection restarts turn numbering and word timestamps at zero.
+#[derive(Default)]
+struct TurnSequencer {
+    turn_offset: u32,
+    next_turn: u32,
+    time_offset_ms: u32,
+    /// Latest result of the turn in progress
+    partial: Option<TranscriptResult>,
+    /// Language of turns the server doesn't tag with one
+    language: Option<String>,
+}
+
+impl TurnSequencer {
+    /// Continue numbering on a new connection whose audio starts at
+    /// `time_offset_ms` into the stream
+    fn begin_connection(&mut self, time_offset_ms: u32) {
+        self.turn_offset = self.next_turn;
+        self.time_offset_ms = time_offset_ms;
+        self.partial = None;
+    }
+
+    fn sequence(&mut self, mut result: TranscriptResult) -> TranscriptResult {
+        result.turn_order += self.turn_offset;
+        for word in &mut result.words {
+            word.start += self.time_offset_ms;
+            word.end += self.time_offset_ms;
+        }
+
+        self.next_turn = self.next_turn.max(result.turn_order + 1);
+        self.partial = (!result.end_of_turn).then(|| result.clone());
+        result
+    }
+
+    /// The turn that was in progress when the connection dropped, finalized
+    /// with the text received so far
+    fn interrupt(&mut self) -> Option<TranscriptResult> {
+        self.partial.take().map(|mut result| {
+            result.is_final = true;
+            result.end_of_turn = true;
+            for word in &mut result.words {
+                word.is_final = true;
+            }
+            result
+        })
+    }
+}
+
+/// How a connection ended
+enum ConnectionEnd {
+    /// The audio channel closed and the session was terminated
+    Finished,
+    /// The connection was lost and is worth re-establishing
+    Dropped(String),
+    /// AssemblyAI reported an error that reconnecting won't fix
+    Rejected(String),
+}
+
+/// Why a connection couldn't be opened
+enum ConnectError {
+    /// Credentials or parameters were refused - retrying won't help
+    Rejected(String),
+    /// Network or server trouble, worth retrying
+    Failed(String),
+}
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Convert i16 samples to bytes (PCM S16LE - little endian)
+fn to_pcm_bytes(samples: &[i16]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+/// Hold incoming audio for `delay`. Returns false if the audio channel closed.
+async fn buffer_audio(
+    audio_receiver: &mut mpsc::Receiver<Vec<i16>>,
+    replay: &mut ReplayBuffer,
+    delay: Duration,
+) -> bool {
+    let sleep = tokio::time::sleep(delay);
+    tokio::pin!(sleep);
+
+    loop {
+        tokio::select! {
+            _ = &mut sleep => return true,
+            chunk = audio_receiver.recv() => match chunk {
+                Some(chunk) => replay.push(chunk),
+                None => return false,
+            },
+        }
+    }
+}
+
+/// Handle a text message from AssemblyAI, returning how the connection ended
+/// if it should stop. `session_id` tracks the session the connection belongs
+/// to, which the termination statistics are reported against.
+fn handle_message(
+    text: &str,
+    turns: &mut TurnSequencer,
+    transcript_sender: &mpsc::UnboundedSender<TranscriptResult>,
+    events: &mpsc::UnboundedSender<ProviderEvent>,
+    session_id: &mut Option<String>,
+) -> Option<ConnectionEnd> {
+    match serde_json::from_str::<ServerMessage>(text) {
+        Ok(ServerMessage::Begin { id, expires_at }) => {
+            tracing::info!("Session {} started (expires at {})", id, expires_at);
+            let _ = events.send(ProviderEvent::SessionStarted {
+                session_id: id.clone(),
+                expires_at,
+            });
+            *session_id = Some(id);
+        }
+        Ok(ServerMessage::Turn {
+            turn_order,
+            end_of_turn,
+            transcript,
+            words,
+            end_of_turn_confidence,
+            speaker_label,
+            language_code,
+        }) => {
+            if !transcript.is_empty() {
+                let result = turns.sequence(TranscriptResult {
+                    text: transcript.clone(),
+                    confidence: end_of_turn_confidence,
+                    is_final: end_of_turn,
+                    turn_order,
+                    end_of_turn,
+                    words: words
+                        .into_iter()
+                        .map(|w| WordResult {
+                            text: w.text,
+                            start: w.start,
+                            end: w.end,
+                            confidence: w.confidence,
+                            is_final: w.word_is_final,
+                        })
+                        .collect(),
+                    // Turns too short to attribute come back as "UNKNOWN"
+                    speaker: speaker_label
+                        .filter(|label| label != "UNKNOWN")
+                        .map(|label| format!("Speaker {}", label)),
+                    language: language_code.or_else(|| turns.language.clone()),
+                });
+
+                // Only log completed turns, skip partial turn noise
+                if end_of_turn {
+                    // Cut by characters: transcripts are rarely plain ASCII
+                    let preview = match transcript.char_indices().nth(40) {
+                        Some((end, _)) => format!("{}...", &transcript[..end]),
+                        None => transcript.clone(),
+                    };
+                    tracing::info!("✓ Turn {}: \"{}\"", result.turn_order, preview);
+                }
+                // No logging for partial turns - reduces noise
+
+                if let Err(e) = transcript_sender.send(result) {
+                    // This is expected when stopping - the receiver is dropped
+                    tracing::debug!("Transcript receiver closed (normal during stop): {}", e);
+                    return Some(ConnectionEnd::Finished);
+                }
+            }
+        }
+        Ok(ServerMessage::Termination {
+            audio_duration_seconds,
+            session_duration_seconds,
+        }) => {
+            // We only terminate once the audio has ended, so this comes from
+            // the server side (e.g. the session reached its maximum length)
+            tracing::info!(
+                "Session terminated. Audio: {:.2}s, Session: {:.2}s",
+                audio_duration_seconds,
+                session_duration_seconds
+            );
+            if let Some(session_id) = session_id.take() {
+                let _ = events.send(ProviderEvent::SessionEnded {
+                    session_id,
+                    audio_duration_seconds: audio_duration_seconds as f64,
+                    session_duration_seconds: session_duration_seconds as f64,
+                });
+            }
+            return Some(ConnectionEnd::Dropped("session terminated".to_string()));
+        }
+        Ok(ServerMessage::Error { error }) => {
+            tracing::error!("AssemblyAI error: {}", error);
+            return Some(ConnectionEnd::Rejected(error));
+        }
+        Err(e) => {
+            tracing::error!("Failed to parse message: {}", e);
+        }
+    }
+    None
 }
 
 impl AssemblyAIClient {
     pub fn new(api_key: String) -> Self {
-        Self { api_key }
+        Self {
+            api_key,
+            http: reqwest::Client::new(),
+            token_url: String::new(),
+            ws_url: String::new(),
+            config: StreamingConfig::default(),
+            keyterms: Vec::new(),
+        }
+        .with_base_url(ASSEMBLYAI_BASE_URL)
+    }
+
+    /// Talk to another host than the public API, e.g. a local mock server in
+    /// tests. `http://` bases stream over `ws://`, `https://` over `wss://`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let ws_base = if let Some(host) = base_url.strip_prefix("https://") {
+            format!("wss://{}", host)
+        } else if let Some(host) = base_url.strip_prefix("http://") {
+            format!("ws://{}", host)
+        } else {
+            base_url.to_string()
+        };
+
+        self.token_url = format!("{}{}", base_url, TOKEN_PATH);
+        self.ws_url = format!("{}{}", ws_base, STREAMING_PATH);
+        self
+    }
+
+    /// Exchange the API key for a short-lived, single-use streaming token.
+    ///
+    /// The key is sent in a header, so it never appears in a URL that a proxy
+    /// or connection log could capture.
+    async fn create_streaming_token(&self) -> Result<String, ConnectError> {
+        let response = self
+            .http
+            .get(&self.token_url)
+            .query(&[("expires_in_seconds", STREAMING_TOKEN_EXPIRES_SECS)])
+            .header("Authorization", &self.api_key)
+            .send()
+            .await
+            .map_err(|e| {
+                ConnectError::Failed(format!(
+                    "Failed to request a streaming token: {}",
+                    e.without_url()
+                ))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            let error_msg = format!(
+                "AssemblyAI token request failed with status {}: {}",
+                status,
+                redact(&error_text, &self.api_key)
+            );
+            return Err(if status.is_client_error() {
+                ConnectError::Rejected(error_msg)
+            } else {
+                ConnectError::Failed(error_msg)
+            });
+        }
+
+        let TokenResponse { token } = response.json().await.map_err(|e| {
+            ConnectError::Failed(format!(
+                "Failed to parse streaming token: {}",
+                e.without_url()
+            ))
+        })?;
+        Ok(token)
+    }
+
+    /// Open a streaming connection with a fresh token. Tokens are single-use,
+    /// so every reconnection needs its own.
+    async fn connect(&self, query: &str) -> Result<WsStream, ConnectError> {
+        let token = self.create_streaming_token().await?;
+        let ws_url = format!("{}?{}&token={}", self.ws_url, query, token);
+
+        match connect_async(ws_url.as_str()).await {
+            Ok((ws_stream, _response)) => Ok(ws_stream),
+            Err(WsError::Http(response)) if response.status().is_client_error() => {
+                Err(ConnectError::Rejected(format!(
+                    "AssemblyAI rejected the connection: HTTP {}",
+                    response.status()
+                )))
+            }
+            // Never echo the URL, even though the token is short-lived
+            Err(e) => Err(ConnectError::Failed(redact(
+                &format!("WebSocket connection failed: {}", e),
+                &token,
+            ))),
+        }
+    }
+
+    /// Turn detection settings used when streaming as a [`TranscriptionProvider`]
+    pub fn with_config(mut self, config: StreamingConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Words and phrases (e.g. product names) the model should be more
+    /// likely to recognize
+    pub fn with_keyterms(mut self, keyterms: Vec<String>) -> Self {
+        self.keyterms = keyterms;
+        self
     }
 
-    /// Stream audio to AssemblyAI Universal Streaming API
+    /// Stream audio to AssemblyAI Universal Streaming API.
+    ///
+    /// If the connection drops, it is re-established with exponential backoff.
+    /// Audio captured in the meantime is buffered (up to a minute) and sent
+    /// once reconnected, and turn numbers and word timestamps continue from
+    /// where the previous connection left off.
     pub async fn stream_audio(
         &self,
         mut audio_receiver: mpsc::Receiver<Vec<i16>>,
         transcript_sender: mpsc::UnboundedSender<TranscriptResult>,
+        events: mpsc::UnboundedSender<ProviderEvent>,
         sample_rate: u32,
         config: StreamingConfig,
     ) -> Result<(), String> {
-        tracing::info!("Connecting to AssemblyAI ({}Hz)...", sample_rate);
-
-        // Build WebSocket URL with all configuration as query parameters
-        let ws_url = format!(
-            "{}?token={}&sample_rate={}&encoding=pcm_s16le&format_turns={}&end_of_turn_confidence_threshold={}&min_end_of_turn_silence_when_confident={}&max_turn_silence={}",
-            ASSEMBLYAI_WS_URL,
-            self.api_key,
+        // All configuration goes in the query; the token is added per connection
+        let mut query = format!(
+            "sample_rate={}&encoding=pcm_s16le&format_turns={}&end_of_turn_confidence_threshold={}&min_end_of_turn_silence_when_confident={}&max_turn_silence={}",
             sample_rate,
             config.format_turns,
             config.end_of_turn_confidence_threshold,
             config.min_end_of_turn_silence_when_confident,
             config.max_turn_silence
         );
+        if config.speaker_labels {
+            query.push_str("&speaker_labels=true");
+        }
+        if !matches!(config.language.as_deref(), None | Some("en")) {
+            query.push_str("&speech_model=");
+            query.push_str(MULTILINGUAL_MODEL);
+            query.push_str("&language_detection=true");
+        }
+        if !self.keyterms.is_empty() {
+            query.push_str("&keyterms_prompt=");
+            query.push_str(&encode_keyterms(&self.keyterms));
+        }
 
-        // Connect to WebSocket
-        let (ws_stream, _response) = match connect_async(ws_url).await {
-            Ok(result) => result,
-            Err(e) => {
-                tracing::error!("WebSocket connection error details: {:?}", e);
-                return Err(format!("WebSocket connection failed: {}. Note: Ensure your API key is valid and you have network connectivity.", e));
-            }
+        let mut backoff = Backoff::new(
+            RECONNECT_INITIAL_DELAY,
+            RECONNECT_MAX_DELAY,
+            RECONNECT_MAX_ATTEMPTS,
+        );
+        let mut replay = ReplayBuffer::new(sample_rate as usize * REPLAY_BUFFER_SECS);
+        let mut turns = TurnSequencer {
+            language: config.fixed_language(),
+            ..Default::default()
         };
+        let mut reconnecting = false;
 
-        tracing::info!("Connected to AssemblyAI ✓");
+        loop {
+            tracing::info!("Connecting to AssemblyAI ({}Hz)...", sample_rate);
 
-        let (mut write, mut read) = ws_stream.split();
+            let reason = match self.connect(&query).await {
+                Ok(ws_stream) => {
+                    tracing::info!("Connected to AssemblyAI ✓");
+                    let connected_at = Instant::now();
 
-        // Spawn task to handle incoming messages
-        let transcript_tx = transcript_sender.clone();
-        let read_handle = tokio::spawn(async move {
-            while let Some(msg_result) = read.next().await {
-                match msg_result {
-                    Ok(Message::Text(text)) => {
-                        match serde_json::from_str::<ServerMessage>(&text) {
-                            Ok(ServerMessage::Begin { id: _, expires_at: _ }) => {
-                                // Session started - no logging needed
-                            }
-                            Ok(ServerMessage::Turn {
-                                turn_order,
-                                end_of_turn,
-                                transcript,
-                                words,
-                                end_of_turn_confidence,
-                            }) => {
-                                if !transcript.is_empty() {
-                                    let result = TranscriptResult {
-                                        text: transcript.clone(),
-                                        confidence: end_of_turn_confidence,
-                                        is_final: end_of_turn,
-                                        turn_order,
-                                        end_of_turn,
-                                        words: words
-                                            .into_iter()
-                                            .map(|w| WordResult {
-                                                text: w.text,
-                                                start: w.start,
-                                                end: w.end,
-                                                confidence: w.confidence,
-                                                is_final: w.word_is_final,
-                                            })
-                                            .collect(),
-                                    };
-
-                                    // Only log completed turns, skip partial turn noise
-                                    if end_of_turn {
-                                        let preview = if transcript.len() > 40 {
-                                            format!("{}...", &transcript[..40])
-                                        } else {
-                                            transcript.clone()
-                                        };
-                                        tracing::info!("✓ Turn {}: \"{}\"", turn_order, preview);
-                                    }
-                                    // No logging for partial turns - reduces noise
-
-                                    if let Err(e) = transcript_tx.send(result) {
-                                        // This is expected when stopping - the receiver is dropped
-                                        tracing::debug!(
-                                            "Transcript receiver closed (normal during stop): {}",
-                                            e
-                                        );
-                                        break;
-                                    }
-                                }
-                            }
-                            Ok(ServerMessage::Termination {
-                                audio_duration_seconds,
-                                session_duration_seconds,
-                            }) => {
-                                tracing::info!(
-                                    "Session terminated. Audio: {:.2}s, Session: {:.2}s",
-                                    audio_duration_seconds,
-                                    session_duration_seconds
-                                );
-                                break;
-                            }
-                            Ok(ServerMessage::Error { error }) => {
-                                tracing::error!("AssemblyAI error: {}", error);
-                                break;
+                    let time_offset_ms = (replay.position() * 1000 / sample_rate as u64) as u32;
+                    turns.begin_connection(time_offset_ms);
+
+                    let end = Self::run_connection(
+                        ws_stream,
+                        &mut audio_receiver,
+                        &transcript_sender,
+                        &events,
+                        &mut replay,
+                        &mut turns,
+                        sample_rate,
+                    )
+                    .await;
+
+                    match end {
+                        ConnectionEnd::Finished => return Ok(()),
+                        ConnectionEnd::Rejected(error) => {
+                            return Err(format!("AssemblyAI error: {}", error));
+                        }
+                        ConnectionEnd::Dropped(reason) => {
+                            if let Some(turn) = turns.interrupt() {
+                                let _ = transcript_sender.send(turn);
                             }
-                            Err(e) => {
-                                tracing::error!("Failed to parse message: {}", e);
+                            if connected_at.elapsed() >= STABLE_CONNECTION {
+                                backoff.reset();
                             }
+                            reason
                         }
                     }
-                    Ok(Message::Binary(_)) => {
-                        tracing::warn!("Received unexpected binary message");
-                    }
-                    Ok(Message::Close(frame)) => {
-                        tracing::info!("WebSocket closed by server: {:?}", frame);
-                        break;
-                    }
-                    Err(e) => {
-                        tracing::error!("WebSocket error: {}", e);
-                        break;
-                    }
-                    _ => {}
                 }
+                Err(ConnectError::Rejected(error)) => {
+                    tracing::error!("{}", error);
+                    return Err(format!("{}. Note: Ensure your API key is valid.", error));
+                }
+                // Nothing to resume yet - most likely no network
+                Err(ConnectError::Failed(error)) if !reconnecting => {
+                    tracing::error!("{}", error);
+                    return Err(format!(
+                        "{}. Note: Ensure you have network connectivity.",
+                        error
+                    ));
+                }
+                Err(ConnectError::Failed(error)) => error,
+            };
+            reconnecting = true;
+
+            let Some(delay) = backoff.next_delay() else {
+                return Err(format!(
+                    "Lost connection to AssemblyAI and could not reconnect: {}",
+                    reason
+                ));
+            };
+            tracing::warn!(
+                "AssemblyAI connection lost ({}), reconnecting in {:.1}s (attempt {}/{})",
+                reason,
+                delay.as_secs_f32(),
+                backoff.attempt(),
+                RECONNECT_MAX_ATTEMPTS
+            );
+
+            if !buffer_audio(&mut audio_receiver, &mut replay, delay).await {
+                tracing::info!("Audio channel closed while reconnecting - stopping");
+                return Ok(());
             }
-            tracing::info!("Message handler exiting");
-        });
+            if replay.dropped() > 0 {
+                tracing::warn!(
+                    "Replay buffer full - {:.1}s of audio dropped so far",
+                    replay.dropped() as f32 / sample_rate as f32
+                );
+            }
+        }
+    }
 
-        // Spawn task to send audio data
-        let write_handle = tokio::spawn(async move {
-            let mut sample_count = 0usize;
-            let mut first_chunk = true;
+    /// Stream over one connection until the audio ends or the connection is lost
+    async fn run_connection(
+        ws_stream: WsStream,
+        audio_receiver: &mut mpsc::Receiver<Vec<i16>>,
+        transcript_sender: &mpsc::UnboundedSender<TranscriptResult>,
+        events: &mpsc::UnboundedSender<ProviderEvent>,
+        replay: &mut ReplayBuffer,
+        turns: &mut TurnSequencer,
+        sample_rate: u32,
+    ) -> ConnectionEnd {
+        let (mut write, mut read) = ws_stream.split();
+        let mut session_id = None;
+
+        // Audio captured while disconnected goes out before any live audio
+        if !replay.is_empty() {
+            tracing::info!(
+                "Replaying {:.1}s of buffered audio",
+                replay.buffered() as f32 / sample_rate as f32
+            );
+        }
+        while let Some(chunk) = replay.front() {
+            if let Err(e) = write.send(Message::Binary(to_pcm_bytes(chunk))).await {
+                return ConnectionEnd::Dropped(format!("failed to send audio: {}", e));
+            }
+            replay.pop_front();
+        }
 
-            loop {
-                // Check if channel is closed (no more samples coming)
-                match audio_receiver.recv().await {
+        loop {
+            tokio::select! {
+                message = read.next() => {
+                    let end = match message {
+                        Some(Ok(Message::Text(text))) => {
+                            handle_message(&text, turns, transcript_sender, events, &mut session_id)
+                        }
+                        Some(Ok(Message::Binary(_))) => {
+                            tracing::warn!("Received unexpected binary message");
+                            None
+                        }
+                        Some(Ok(Message::Close(frame))) => {
+                            tracing::info!("WebSocket closed by server: {:?}", frame);
+                            Some(ConnectionEnd::Dropped("closed by server".to_string()))
+                        }
+                        Some(Ok(_)) => None,
+                        Some(Err(e)) => {
+                            tracing::error!("WebSocket error: {}", e);
+                            Some(ConnectionEnd::Dropped(e.to_string()))
+                        }
+                        None => Some(ConnectionEnd::Dropped("connection closed".to_string())),
+                    };
+                    if let Some(end) = end {
+                        return end;
+                    }
+                }
+                chunk = audio_receiver.recv() => match chunk {
                     Some(samples) => {
-                        if first_chunk {
+                        if replay.position() == 0 {
                             tracing::info!("🎤 Streaming started");
-                            first_chunk = false;
-                        }
-
-                        sample_count += samples.len();
-
-                        // Convert i16 samples to bytes (PCM S16LE - little endian)
-                        let mut bytes = Vec::with_capacity(samples.len() * 2);
-                        for sample in samples {
-                            bytes.extend_from_slice(&sample.to_le_bytes());
                         }
 
-                        if let Err(e) = write.send(Message::Binary(bytes)).await {
-                            tracing::error!("Failed to send audio: {}", e);
-                            break;
+                        if let Err(e) = write.send(Message::Binary(to_pcm_bytes(&samples))).await {
+                            // Keep the chunk for the next connection
+                            replay.push(samples);
+                            return ConnectionEnd::Dropped(format!("failed to send audio: {}", e));
                         }
-
-                        // Remove verbose periodic logging - just stream audio without noise
+                        replay.advance(samples.len());
                     }
                     None => {
                         // Channel closed by sender (stop command received)
-                        // Exit immediately WITHOUT draining buffered messages
+                        // Exit immediately WITHOUT draining buffered transcripts
                         tracing::info!("Audio channel closed - stopping immediately");
                         break;
                     }
-                }
+                },
             }
+        }
 
-            tracing::info!(
-                "🎤 Audio streaming completed - {} samples (~{:.2}s)",
-                sample_count,
-                sample_count as f32 / 48000.0
+        tracing::info!(
+            "🎤 Audio streaming completed - {} samples (~{:.2}s)",
+            replay.position(),
+            replay.position() as f32 / sample_rate as f32
+        );
+
+        // Send terminate message to close the session cleanly
+        let terminate_msg = r#"{"type":"terminate"}"#;
+        if let Err(e) = write.send(Message::Text(terminate_msg.to_string())).await {
+            tracing::debug!(
+                "Failed to send terminate message (expected if stopped): {}",
+                e
             );
+        } else {
+            tracing::info!("Terminate message sent");
 
-            // Send terminate message to close the session cleanly
-            let terminate_msg = r#"{"type":"terminate"}"#;
-            if let Err(e) = write.send(Message::Text(terminate_msg.to_string())).await {
-                tracing::debug!(
-                    "Failed to send terminate message (expected if stopped): {}",
-                    e
-                );
-            } else {
-                tracing::info!("Terminate message sent");
+            // AssemblyAI answers with the session's statistics. Turns still in
+            // flight are skipped, as when the audio channel closed.
+            let termination = async {
+                while let Some(Ok(message)) = read.next().await {
+                    if let Message::Text(text) = message {
+                        if let Ok(ServerMessage::Termination { .. }) =
+                            serde_json::from_str::<ServerMessage>(&text)
+                        {
+                            handle_message(
+                                &text,
+                                turns,
+                                transcript_sender,
+                                events,
+                                &mut session_id,
+                            );
+                            return;
+                        }
+                    }
+                }
+            };
+            if tokio::time::timeout(TERMINATION_TIMEOUT, termination)
+                .await
+                .is_err()
+            {
+                tracing::debug!("No termination message before timeout");
             }
+        }
 
-            // Close the WebSocket writer
-            if let Err(e) = write.close().await {
-                tracing::debug!(
-                    "Failed to close WebSocket writer (expected if stopped): {}",
-                    e
-                );
-            } else {
-                tracing::info!("WebSocket writer closed cleanly");
-            }
+        // Close the WebSocket writer
+        if let Err(e) = write.close().await {
+            tracing::debug!(
+                "Failed to close WebSocket writer (expected if stopped): {}",
+                e
+            );
+        } else {
+            tracing::info!("WebSocket writer closed cleanly");
+        }
+
+        ConnectionEnd::Finished
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for AssemblyAIClient {
+    fn name(&self) -> &'static str {
+        "AssemblyAI"
+    }
+
+    async fn transcribe(
+        &self,
+        audio_receiver: mpsc::Receiver<Vec<i16>>,
+        transcript_sender: mpsc::UnboundedSender<TranscriptResult>,
+        events: mpsc::UnboundedSender<ProviderEvent>,
+        sample_rate: u32,
+    ) -> Result<(), String> {
+        self.stream_audio(
+            audio_receiver,
+            transcript_sender,
+            events,
+            sample_rate,
+            self.config.clone(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_apis::assemblyai::*;
+    use serde_json::json;
+
+    fn turn(turn_order: u32, end_of_turn: bool, start: u32, end: u32) -> TranscriptResult {
+        TranscriptResult {
+            text: format!("turn {}", turn_order),
+            confidence: 0.9,
+            is_final: end_of_turn,
+            turn_order,
+            end_of_turn,
+            words: vec![WordResult {
+                text: "turn".to_string(),
+                start,
+                end,
+                confidence: 0.9,
+                is_final: end_of_turn,
+            }],
+            speaker: None,
+            language: None,
+        }
+    }
+
+    #[test]
+    fn test_redact_hides_secrets() {
+        assert_eq!(
+            redact("wss://host/v3/ws?token=abc123&x=1 failed", "abc123"),
+            "wss://host/v3/ws?token=[redacted]&x=1 failed"
+        );
+        assert_eq!(redact("no secret here", ""), "no secret here");
+    }
+
+    #[test]
+    fn test_encode_keyterms() {
+        let keyterms = vec!["Megapack".to_string(), "Full Self-Driving".to_string()];
+        assert_eq!(
+            encode_keyterms(&keyterms),
+            "%5B%22Megapack%22%2C%22Full%20Self-Driving%22%5D"
+        );
+    }
+
+    #[test]
+    fn test_streaming_config_validation() {
+        assert!(StreamingConfig::default().validate().is_ok());
+
+        let config: StreamingConfig =
+            serde_json::from_str(r#"{"format_turns": true, "max_turn_silence": 3000}"#).unwrap();
+        assert!(config.format_turns);
+        assert_eq!(config.max_turn_silence, 3000);
+        assert_eq!(config.min_end_of_turn_silence_when_confident, 400);
+        assert!(config.validate().is_ok());
+
+        let invalid = [
+            StreamingConfig {
+                end_of_turn_confidence_threshold: 1.5,
+                ..Default::default()
+            },
+            StreamingConfig {
+                end_of_turn_confidence_threshold: f32::NAN,
+                ..Default::default()
+            },
+            StreamingConfig {
+                max_turn_silence: 60_000,
+                ..Default::default()
+            },
+            StreamingConfig {
+                min_end_of_turn_silence_when_confident: 2000,
+                max_turn_silence: 1000,
+                ..Default::default()
+            },
+            StreamingConfig {
+                language: Some("klingon".to_string()),
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(
+                config.validate().is_err(),
+                "{:?} should be rejected",
+                config
+            );
+        }
+    }
+
+    /// `handle_message` for a message outside of any provider session
+    fn handle_turn(
+        text: &str,
+        turns: &mut TurnSequencer,
+        sender: &mpsc::UnboundedSender<TranscriptResult>,
+    ) -> Option<ConnectionEnd> {
+        let (events, _) = mpsc::unbounded_channel();
+        handle_message(text, turns, sender, &events, &mut None)
+    }
+
+    #[test]
+    fn test_turns_carry_speaker_labels() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut turns = TurnSequencer::default();
+        let turn = |label: &str| {
+            json!({
+                "type": "Turn",
+                "turn_order": 0,
+                "end_of_turn": true,
+                "end_of_turn_confidence": 0.9,
+                "transcript": "Hello",
+                "words": [],
+                "speaker_label": label
+            })
+            .to_string()
+        };
+
+        assert!(handle_turn(&turn("B"), &mut turns, &sender).is_none());
+        assert_eq!(
+            receiver.try_recv().unwrap().speaker.as_deref(),
+            Some("Speaker B")
+        );
+
+        handle_turn(&turn("UNKNOWN"), &mut turns, &sender);
+        assert_eq!(receiver.try_recv().unwrap().speaker, None);
+
+        // Without speaker labels enabled the field is absent
+        handle_turn(&create_turn_message(1, "Hi", true), &mut turns, &sender);
+        assert_eq!(receiver.try_recv().unwrap().speaker, None);
+    }
+
+    #[test]
+    fn test_long_accented_turn_is_logged() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut turns = TurnSequencer::default();
+        // Byte 40 falls inside "é"
+        let text = "Très bien, nous verrons la sécurité énergétique à l'été";
+        assert!(!text.is_char_boundary(40));
+
+        let message = create_turn_message(0, text, true);
+        assert!(handle_turn(&message, &mut turns, &sender).is_none());
+        assert_eq!(receiver.try_recv().unwrap().text, text);
+    }
+
+    #[test]
+    fn test_turns_carry_detected_language() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let config = StreamingConfig {
+            language: Some(language::AUTO.to_string()),
+            ..Default::default()
+        };
+        let mut turns = TurnSequencer {
+            language: config.fixed_language(),
+            ..Default::default()
+        };
+        let turn = json!({
+            "type": "Turn",
+            "turn_order": 0,
+            "end_of_turn": true,
+            "end_of_turn_confidence": 0.9,
+            "transcript": "Guten Morgen",
+            "words": [],
+            "language_code": "de",
+            "language_confidence": 0.97
+        })
+        .to_string();
+
+        handle_turn(&turn, &mut turns, &sender);
+        assert_eq!(receiver.try_recv().unwrap().language.as_deref(), Some("de"));
+
+        // Detection on, but nothing reported yet
+        handle_turn(&create_turn_message(1, "Hallo", true), &mut turns, &sender);
+        assert_eq!(receiver.try_recv().unwrap().language, None);
+
+        // The English model never reports a language
+        turns.language = StreamingConfig::default().fixed_language();
+        handle_turn(&create_turn_message(2, "Hello", true), &mut turns, &sender);
+        assert_eq!(receiver.try_recv().unwrap().language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_turns_continue_across_connections() {
+        let mut turns = TurnSequencer::default();
+        turns.begin_connection(0);
+        assert_eq!(turns.sequence(turn(0, true, 100, 500)).turn_order, 0);
+        assert_eq!(turns.sequence(turn(1, true, 900, 1500)).turn_order, 1);
+        assert!(turns.interrupt().is_none());
+
+        // The next connection restarts at turn 0 and 0ms, 12s into the stream
+        turns.begin_connection(12_000);
+        let result = turns.sequence(turn(0, true, 200, 800));
+        assert_eq!(result.turn_order, 2);
+        assert_eq!(result.span_ms(), Some((12_200, 12_800)));
+    }
+
+    #[test]
+    fn test_interrupted_turn_is_finalized() {
+        let mut turns = TurnSequencer::default();
+        turns.begin_connection(0);
+        turns.sequence(turn(0, true, 0, 400));
+        turns.sequence(turn(1, false, 600, 900));
+
+        let interrupted = turns.interrupt().unwrap();
+        assert_eq!(interrupted.turn_order, 1);
+        assert!(interrupted.is_final && interrupted.end_of_turn);
+        assert!(interrupted.words.iter().all(|w| w.is_final));
+        assert!(turns.interrupt().is_none());
+
+        // The interrupted turn keeps its number
+        turns.begin_connection(5_000);
+        assert_eq!(turns.sequence(turn(0, false, 0, 100)).turn_order, 2);
+    }
+
+    const API_KEY: &str = "test-api-key";
+
+    /// 100ms of silence at 16kHz
+    fn chunk() -> Vec<i16> {
+        vec![0; 1600]
+    }
+
+    /// A `stream_audio` call running against a mock server
+    struct Streaming {
+        audio: mpsc::Sender<Vec<i16>>,
+        transcripts: mpsc::UnboundedReceiver<TranscriptResult>,
+        events: mpsc::UnboundedReceiver<ProviderEvent>,
+        handle: tokio::task::JoinHandle<Result<(), String>>,
+    }
+
+    fn start_streaming(server: &MockServer, api_key: &str) -> Streaming {
+        let client = AssemblyAIClient::new(api_key.to_string()).with_base_url(server.base_url());
+        let (audio, audio_rx) = mpsc::channel(32);
+        let (transcript_tx, transcripts) = mpsc::unbounded_channel();
+        let (event_tx, events) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(async move {
+            client
+                .stream_audio(
+                    audio_rx,
+                    transcript_tx,
+                    event_tx,
+                    16000,
+                    StreamingConfig::default(),
+                )
+                .await
         });
+        Streaming {
+            audio,
+            transcripts,
+            events,
+            handle,
+        }
+    }
 
-        // Wait for both tasks
-        tokio::select! {
-            result = read_handle => {
-                tracing::info!("Read handle completed: {:?}", result);
+    async fn next_transcript(
+        transcripts: &mut mpsc::UnboundedReceiver<TranscriptResult>,
+    ) -> TranscriptResult {
+        tokio::time::timeout(Duration::from_secs(5), transcripts.recv())
+            .await
+            .expect("timed out waiting for a transcript")
+            .expect("transcript channel closed")
+    }
+
+    #[tokio::test]
+    async fn test_stream_audio_against_mock_server() {
+        let server = MockServer::start(
+            API_KEY,
+            vec![vec![
+                MockStep::Send(create_begin_message("session-1")),
+                MockStep::AwaitAudio(6400),
+                MockStep::Send(create_turn_message(0, "hello", false)),
+                MockStep::Send(create_turn_message(0, "hello world", true)),
+            ]],
+        )
+        .await;
+        let Streaming {
+            audio,
+            mut transcripts,
+            mut events,
+            handle,
+        } = start_streaming(&server, API_KEY);
+
+        audio.send(chunk()).await.unwrap();
+        audio.send(chunk()).await.unwrap();
+
+        let partial = next_transcript(&mut transcripts).await;
+        assert_eq!(partial.text, "hello");
+        assert!(!partial.is_final);
+        let turn = next_transcript(&mut transcripts).await;
+        assert_eq!(turn.text, "hello world");
+        assert!(turn.is_final && turn.end_of_turn);
+
+        drop(audio);
+        assert_eq!(handle.await.unwrap(), Ok(()));
+
+        let connections = server.connections();
+        assert_eq!(connections.len(), 1);
+        let connection = &connections[0];
+        assert_eq!(connection.audio_bytes, 6400);
+        assert!(server.wait_for(|c| c[0].terminated).await);
+
+        // The session id and the statistics from the termination reply
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ProviderEvent::SessionStarted {
+                session_id: "session-1".to_string(),
+                expires_at: 1700000000,
             }
-            result = write_handle => {
-                tracing::info!("Write handle completed: {:?}", result);
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ProviderEvent::SessionEnded {
+                session_id: "session-1".to_string(),
+                audio_duration_seconds: 10.5,
+                session_duration_seconds: 11.0,
             }
-        }
+        );
 
-        Ok(())
+        // Authenticated with a streaming token, never the API key
+        assert_eq!(connection.query["sample_rate"], "16000");
+        assert_eq!(connection.query["token"], "mock-token-1");
+        assert_eq!(connection.query["max_turn_silence"], "1280");
+        assert!(!connection.query.values().any(|v| v.contains(API_KEY)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_audio_reconnects_after_drop() {
+        let server = MockServer::start(
+            API_KEY,
+            vec![
+                vec![
+                    MockStep::AwaitAudio(6400),
+                    MockStep::Send(create_turn_message(0, "first", true)),
+                    MockStep::Send(create_turn_message(1, "interrupted", false)),
+                    MockStep::Drop,
+                ],
+                vec![
+                    MockStep::AwaitAudio(3200),
+                    MockStep::Send(create_turn_message(0, "resumed", true)),
+                ],
+            ],
+        )
+        .await;
+        let Streaming {
+            audio,
+            mut transcripts,
+            handle,
+            ..
+        } = start_streaming(&server, API_KEY);
+
+        audio.send(chunk()).await.unwrap();
+        audio.send(chunk()).await.unwrap();
+
+        assert_eq!(next_transcript(&mut transcripts).await.text, "first");
+        assert!(!next_transcript(&mut transcripts).await.is_final);
+
+        // The partial turn is finalized once the connection is lost
+        let interrupted = next_transcript(&mut transcripts).await;
+        assert_eq!(interrupted.turn_order, 1);
+        assert!(interrupted.is_final && interrupted.end_of_turn);
+
+        assert!(server.wait_for(|c| c.len() == 2).await);
+        audio.send(chunk()).await.unwrap();
+
+        // Numbering and timestamps continue after the 200ms sent before the drop
+        let resumed = next_transcript(&mut transcripts).await;
+        assert_eq!(resumed.text, "resumed");
+        assert_eq!(resumed.turn_order, 2);
+        assert_eq!(resumed.span_ms(), Some((200, 1200)));
+
+        drop(audio);
+        assert_eq!(handle.await.unwrap(), Ok(()));
+
+        // Each connection used its own token
+        assert_eq!(server.token_requests(), 2);
+        assert_eq!(server.connections()[1].query["token"], "mock-token-2");
+    }
+
+    #[tokio::test]
+    async fn test_stream_audio_reports_server_errors() {
+        let server = MockServer::start(
+            API_KEY,
+            vec![vec![MockStep::Send(create_error_message(
+                "Invalid sample rate",
+            ))]],
+        )
+        .await;
+        let streaming = start_streaming(&server, API_KEY);
+
+        let error = streaming.handle.await.unwrap().unwrap_err();
+        assert!(error.contains("Invalid sample rate"), "{}", error);
+        assert_eq!(server.connections().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_audio_rejects_invalid_api_key() {
+        let server = MockServer::start(API_KEY, vec![]).await;
+        let streaming = start_streaming(&server, "wrong-key");
+
+        let error = streaming.handle.await.unwrap().unwrap_err();
+        assert!(error.contains("401"), "{}", error);
+        assert!(!error.contains("wrong-key"));
+        assert!(server.connections().is_empty());
     }
 }
@@ -7,6 +7,7 @@
 //! ## Key Components
 //!
 //! - [`AudioCapture`] - Main audio capture interface with buffer pooling
+//!   (the live-microphone [`AudioSource`](super::source::AudioSource))
 //! - [`AudioDevice`] - Audio device representation
 //! - [`list_audio_devices()`] - Device enumeration for UI selection
 //!
@@ -15,11 +16,15 @@
 //! - **Buffer Pooling**: Reuses audio buffers to minimize GC pressure
 //! - **Real-time Processing**: 50ms chunk processing for low latency
 //! - **Format Conversion**: Automatic stereo-to-mono and f32-to-i16 conversion
-//! - **Level Monitoring**: Audio level detection for debugging silent inputs
+//! - **Resampling**: Device audio is resampled to [`STREAMING_SAMPLE_RATE`] (configurable)
+//! - **Level Monitoring**: Levels and input health are metered downstream in
+//!   [`processing`](super::processing), for every source
+//! - **Device Recovery**: A disconnected device is re-opened (or replaced by the
+//!   default input) without interrupting the session; see [`CaptureEvent`]
 //!
 //! ## Example Usage
 //!
-//! ```rust,no_run
+//! ```rust,ignore
 //! use tokio::sync::mpsc;
 //! use cpal::traits::HostTrait;
 //!
@@ -35,8 +40,8 @@
 //! let (tx, rx) = mpsc::channel(32);
 //!
 //! // Start audio capture
-//! let capture = AudioCapture::new(device, tx)?;
-//! let handle = capture.start()?;
+//! let capture = AudioCapture::new(device)?;
+//! let handle = capture.start(tx)?;
 //!
 //! // Process audio chunks
 //! while let Some(chunk) = rx.recv().await {
@@ -48,14 +53,22 @@
 //! ```
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, StreamConfig};
+use cpal::{
+    Device, FromSample, Sample, SampleFormat, SampleRate, SizedSample, StreamConfig,
+    SupportedStreamConfig, SupportedStreamConfigRange,
+};
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{mpsc as std_mpsc, Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
 use super::buffer_pool::AudioBufferPool;
+use super::dsp::{DspChain, DspConfig};
+use super::resampler::StreamResampler;
+use super::source::{chunk_size_for, AudioSource};
 
 /// Represents an audio device available for capture or playback.
 ///
@@ -71,6 +84,108 @@ pub struct AudioDevice {
     pub is_default: bool,
     /// Type of device (input/output)
     pub device_type: AudioDeviceType,
+    /// Input configurations the device supports (empty for output devices)
+    #[serde(default)]
+    pub supported_configs: Vec<SupportedInputConfig>,
+    /// Configuration used when none is chosen
+    #[serde(default)]
+    pub default_config: Option<InputConfig>,
+}
+
+/// A range of input configurations supported by a device
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SupportedInputConfig {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    /// Sample format name, e.g. "f32" or "i16"
+    pub sample_format: String,
+}
+
+impl From<&SupportedStreamConfigRange> for SupportedInputConfig {
+    fn from(range: &SupportedStreamConfigRange) -> Self {
+        Self {
+            channels: range.channels(),
+            min_sample_rate: range.min_sample_rate().0,
+            max_sample_rate: range.max_sample_rate().0,
+            sample_format: range.sample_format().to_string(),
+        }
+    }
+}
+
+/// A specific input configuration to capture with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InputConfig {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Sample format name; the best supported format is used when omitted
+    #[serde(default)]
+    pub sample_format: Option<String>,
+}
+
+impl From<&SupportedStreamConfig> for InputConfig {
+    fn from(config: &SupportedStreamConfig) -> Self {
+        Self {
+            sample_rate: config.sample_rate().0,
+            channels: config.channels(),
+            sample_format: Some(config.sample_format().to_string()),
+        }
+    }
+}
+
+/// Formats in order of preference when the user does not pick one
+const PREFERRED_SAMPLE_FORMATS: [SampleFormat; 8] = [
+    SampleFormat::F32,
+    SampleFormat::I16,
+    SampleFormat::I32,
+    SampleFormat::F64,
+    SampleFormat::U16,
+    SampleFormat::I8,
+    SampleFormat::U8,
+    SampleFormat::U32,
+];
+
+/// Pick the supported configuration matching `wanted`
+fn choose_input_config(
+    ranges: impl IntoIterator<Item = SupportedStreamConfigRange>,
+    wanted: &InputConfig,
+) -> Result<SupportedStreamConfig, String> {
+    let rate = SampleRate(wanted.sample_rate);
+    let candidates: Vec<_> = ranges
+        .into_iter()
+        .filter(|r| r.channels() == wanted.channels)
+        .filter(|r| r.min_sample_rate() <= rate && rate <= r.max_sample_rate())
+        .filter(|r| {
+            wanted
+                .sample_format
+                .as_ref()
+                .is_none_or(|f| r.sample_format().to_string() == *f)
+        })
+        .collect();
+
+    let format_rank = |format: SampleFormat| {
+        PREFERRED_SAMPLE_FORMATS
+            .iter()
+            .position(|&f| f == format)
+            .unwrap_or(PREFERRED_SAMPLE_FORMATS.len())
+    };
+
+    candidates
+        .into_iter()
+        .min_by_key(|r| format_rank(r.sample_format()))
+        .map(|r| r.with_sample_rate(rate))
+        .ok_or_else(|| {
+            format!(
+                "Device does not support {} channel(s) at {}Hz{}",
+                wanted.channels,
+                wanted.sample_rate,
+                wanted
+                    .sample_format
+                    .as_ref()
+                    .map(|f| format!(" ({})", f))
+                    .unwrap_or_default()
+            )
+        })
 }
 
 /// Audio device type classification.
@@ -121,11 +236,22 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
                     .map(|d| d == name)
                     .unwrap_or(false);
 
+                let supported_configs = device
+                    .supported_input_configs()
+                    .map(|ranges| ranges.map(|r| SupportedInputConfig::from(&r)).collect())
+                    .unwrap_or_default();
+                let default_config = device
+                    .default_input_config()
+                    .ok()
+                    .map(|c| InputConfig::from(&c));
+
                 devices.push(AudioDevice {
                     id: format!("input_{}", idx),
                     name,
                     is_default,
                     device_type: AudioDeviceType::Input,
+                    supported_configs,
+                    default_config,
                 });
             }
         }
@@ -147,6 +273,8 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
                     name,
                     is_default,
                     device_type: AudioDeviceType::Output,
+                    supported_configs: Vec::new(),
+                    default_config: None,
                 });
             }
         }
@@ -184,29 +312,287 @@ pub fn get_device_by_id(device_id: &str) -> Result<Device, String> {
     }
 }
 
+/// Default rate audio is resampled to before streaming.
+/// 16kHz is AssemblyAI's recommended rate and a third of the bandwidth of 48kHz capture.
+pub const STREAMING_SAMPLE_RATE: u32 = 16_000;
+
 /// Target chunk duration in milliseconds
 /// AssemblyAI requires between 50ms and 1000ms per chunk
-const CHUNK_DURATION_MS: f32 = 50.0;
+pub(crate) const CHUNK_DURATION_MS: f32 = 50.0;
+
+/// A device that delivers no callbacks for this long is treated as disconnected.
+/// Not every backend reports unplugged devices as an error.
+const DEVICE_STALL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Delay between attempts to re-open a lost device
+const DEVICE_REOPEN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often capture statistics are reported
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Warn once this many chunks are dropped within [`DROP_WARNING_WINDOW`]
+const DROP_WARNING_THRESHOLD: u64 = 10;
+const DROP_WARNING_WINDOW: Duration = Duration::from_secs(10);
+
+/// Minimum time between two drop warnings
+const DROP_WARNING_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Notifications from the capture thread during a session
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CaptureEvent {
+    /// The device stopped delivering audio (e.g. a USB headset was unplugged)
+    DeviceLost { device: String, reason: String },
+    /// Capture resumed; `fallback` is set when a different device is now used
+    DeviceReconnected { device: String, fallback: bool },
+    /// Chunks were discarded because the pipeline could not keep up, leaving
+    /// gaps in the transcript
+    ChunksDropped {
+        dropped_chunks: u64,
+        dropped_ms: u64,
+        window_secs: u64,
+    },
+    /// Periodic counters for metrics
+    Stats(CaptureStats),
+}
+
+/// Capture counters accumulated since the previous report
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct CaptureStats {
+    pub callbacks: u64,
+    pub frames: u64,
+    /// Chunks discarded because the chunk channel was full
+    pub dropped_chunks: u64,
+    /// Chunk buffers allocated because the buffer pool was empty
+    pub pool_misses: u64,
+    pub callback_us_total: u64,
+    pub callback_us_max: u64,
+}
 
 /// Commands for controlling the audio thread
 enum AudioThreadCommand {
     Stop,
 }
 
+/// Shared between a stream's callbacks and the audio thread so the thread can
+/// tell when the device has gone away and report how capture is doing
+#[derive(Default)]
+struct StreamMonitor {
+    callbacks: AtomicU64,
+    lost: Mutex<Option<String>>,
+    // Reset on every report
+    timed_callbacks: AtomicU64,
+    frames: AtomicU64,
+    dropped_chunks: AtomicU64,
+    callback_us_total: AtomicU64,
+    callback_us_max: AtomicU64,
+}
+
+impl StreamMonitor {
+    fn record_callback(&self, frames: usize, elapsed: Duration) {
+        let us = elapsed.as_micros() as u64;
+        self.timed_callbacks.fetch_add(1, Ordering::Relaxed);
+        self.frames.fetch_add(frames as u64, Ordering::Relaxed);
+        self.callback_us_total.fetch_add(us, Ordering::Relaxed);
+        self.callback_us_max.fetch_max(us, Ordering::Relaxed);
+    }
+
+    /// Counters since the previous call (`pool_misses` is filled in by the caller)
+    fn take_stats(&self) -> CaptureStats {
+        CaptureStats {
+            callbacks: self.timed_callbacks.swap(0, Ordering::Relaxed),
+            frames: self.frames.swap(0, Ordering::Relaxed),
+            dropped_chunks: self.dropped_chunks.swap(0, Ordering::Relaxed),
+            pool_misses: 0,
+            callback_us_total: self.callback_us_total.swap(0, Ordering::Relaxed),
+            callback_us_max: self.callback_us_max.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    fn report_lost(&self, reason: String) {
+        if let Ok(mut lost) = self.lost.lock() {
+            lost.get_or_insert(reason);
+        }
+    }
+
+    fn take_lost(&self) -> Option<String> {
+        self.lost.lock().ok().and_then(|mut lost| lost.take())
+    }
+}
+
+/// Notices when the callback count stops moving
+struct StallDetector {
+    timeout: Duration,
+    last_count: u64,
+    last_change: Instant,
+}
+
+impl StallDetector {
+    fn new(timeout: Duration, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_count: 0,
+            last_change: now,
+        }
+    }
+
+    /// Returns when audio last arrived if the stream has stalled
+    fn check(&mut self, count: u64, now: Instant) -> Option<Instant> {
+        if count != self.last_count {
+            self.last_count = count;
+            self.last_change = now;
+            None
+        } else if now.duration_since(self.last_change) >= self.timeout {
+            Some(self.last_change)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decides when dropped chunks are worth warning about
+struct DropAlarm {
+    recent: VecDeque<(Instant, u64)>,
+    quiet_until: Option<Instant>,
+}
+
+impl DropAlarm {
+    fn new() -> Self {
+        Self {
+            recent: VecDeque::new(),
+            quiet_until: None,
+        }
+    }
+
+    /// Record drops seen at `now`; returns the drops in the window when a warning is due
+    fn record(&mut self, dropped: u64, now: Instant) -> Option<u64> {
+        if dropped > 0 {
+            self.recent.push_back((now, dropped));
+        }
+        while self
+            .recent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > DROP_WARNING_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+
+        let in_window: u64 = self.recent.iter().map(|(_, n)| n).sum();
+        if in_window < DROP_WARNING_THRESHOLD || self.quiet_until.is_some_and(|t| now < t) {
+            return None;
+        }
+        self.quiet_until = Some(now + DROP_WARNING_COOLDOWN);
+        Some(in_window)
+    }
+}
+
+/// Turns a stream's counters into periodic [`CaptureEvent`]s
+struct StatsReporter {
+    last_report: Instant,
+    pool_misses: u64,
+    alarm: DropAlarm,
+    events: mpsc::UnboundedSender<CaptureEvent>,
+}
+
+impl StatsReporter {
+    fn new(now: Instant, events: mpsc::UnboundedSender<CaptureEvent>) -> Self {
+        Self {
+            last_report: now,
+            pool_misses: 0,
+            alarm: DropAlarm::new(),
+            events,
+        }
+    }
+
+    fn due(&self, now: Instant) -> bool {
+        now.duration_since(self.last_report) >= STATS_INTERVAL
+    }
+
+    fn report(&mut self, monitor: &StreamMonitor, pool: &AudioBufferPool, now: Instant) {
+        self.last_report = now;
+
+        let mut stats = monitor.take_stats();
+        let pool_misses = pool.stats().misses;
+        stats.pool_misses = pool_misses.saturating_sub(self.pool_misses);
+        self.pool_misses = pool_misses;
+
+        if let Some(dropped_chunks) = self.alarm.record(stats.dropped_chunks, now) {
+            let dropped_ms = dropped_chunks * CHUNK_DURATION_MS as u64;
+            tracing::warn!(
+                "⚠️  {} audio chunks ({}ms) dropped in the last {}s - the pipeline is not keeping up",
+                dropped_chunks,
+                dropped_ms,
+                DROP_WARNING_WINDOW.as_secs()
+            );
+            let _ = self.events.send(CaptureEvent::ChunksDropped {
+                dropped_chunks,
+                dropped_ms,
+                window_secs: DROP_WARNING_WINDOW.as_secs(),
+            });
+        }
+
+        if stats != CaptureStats::default() {
+            let _ = self.events.send(CaptureEvent::Stats(stats));
+        }
+    }
+}
+
 /// Handle to control the audio capture thread
 pub struct AudioCaptureHandle {
     command_tx: std_mpsc::Sender<AudioThreadCommand>,
     thread_handle: Option<thread::JoinHandle<()>>,
     stop_flag: Arc<AtomicBool>,
+    events: Option<mpsc::UnboundedReceiver<CaptureEvent>>,
+    /// Captures feeding this handle's thread, stopped before it
+    inputs: Vec<AudioCaptureHandle>,
 }
 
 impl AudioCaptureHandle {
+    /// Wrap a producer thread that only watches the stop flag (e.g. file playback)
+    pub(crate) fn from_thread(
+        thread_handle: thread::JoinHandle<()>,
+        stop_flag: Arc<AtomicBool>,
+    ) -> Self {
+        let (command_tx, _) = std_mpsc::channel();
+        Self {
+            command_tx,
+            thread_handle: Some(thread_handle),
+            stop_flag,
+            events: None,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Wrap a thread that combines the output of other captures (e.g. dual capture).
+    /// Stopping the handle stops `inputs` first so the thread can drain them.
+    pub(crate) fn combining(
+        thread_handle: thread::JoinHandle<()>,
+        stop_flag: Arc<AtomicBool>,
+        inputs: Vec<AudioCaptureHandle>,
+        events: mpsc::UnboundedReceiver<CaptureEvent>,
+    ) -> Self {
+        Self {
+            inputs,
+            events: Some(events),
+            ..Self::from_thread(thread_handle, stop_flag)
+        }
+    }
+
+    /// Take the receiver for capture notifications (live capture only)
+    pub fn take_events(&mut self) -> Option<mpsc::UnboundedReceiver<CaptureEvent>> {
+        self.events.take()
+    }
+
     /// Stop the audio capture and wait for the thread to finish
     pub fn stop(mut self) -> Result<(), String> {
         // Set stop flag immediately - this will make the audio callback exit fast
         self.stop_flag.store(true, Ordering::Release);
         tracing::info!("Stop flag set - audio callback will exit");
 
+        for input in self.inputs.drain(..) {
+            input.stop()?;
+        }
+
         if let Some(handle) = self.thread_handle.take() {
             // Send stop command
             let _ = self.command_tx.send(AudioThreadCommand::Stop);
@@ -231,6 +617,7 @@ impl AudioCaptureHandle {
 ///
 /// - **Buffer Pooling**: Reuses audio buffers to reduce GC pressure
 /// - **Format Conversion**: Automatic stereo-to-mono conversion
+/// - **Resampling**: Converts the device rate to the streaming rate (default 16kHz)
 /// - **Chunk Management**: Configurable chunk sizes (default 50ms)
 /// - **Real-time Processing**: Dedicated thread for audio processing
 ///
@@ -246,8 +633,8 @@ impl AudioCaptureHandle {
 /// let device = host.default_input_device().unwrap();
 /// let (tx, mut rx) = mpsc::channel(32);
 ///
-/// let capture = AudioCapture::new(device, tx)?;
-/// let handle = capture.start()?;
+/// let capture = AudioCapture::new(device)?;
+/// let handle = capture.start(tx)?;
 ///
 /// // Process audio chunks
 /// tokio::spawn(async move {
@@ -264,18 +651,58 @@ impl AudioCaptureHandle {
 pub struct AudioCapture {
     device: Device,
     config: StreamConfig,
-    chunk_sender: mpsc::Sender<Vec<i16>>,
+    sample_format: SampleFormat,
     buffer_pool: AudioBufferPool,
+    target_sample_rate: u32,
+    /// Configuration the user chose, reused when the device is re-opened
+    input_config: Option<InputConfig>,
+    /// Capturing an output device's playback rather than an input
+    loopback: bool,
+    /// Tracks streamed: 1 for a mono downmix, otherwise one per device channel
+    tracks: usize,
+    /// Conditioning applied to each track before PCM conversion
+    dsp: DspConfig,
 }
 
 impl AudioCapture {
     /// Create a new audio capture instance for real-time streaming
-    pub fn new(device: Device, chunk_sender: mpsc::Sender<Vec<i16>>) -> Result<Self, String> {
+    /// using the device's default input configuration
+    pub fn new(device: Device) -> Result<Self, String> {
         // Get the default input config
         let config = device
             .default_input_config()
             .map_err(|e| format!("Failed to get default input config: {}", e))?;
 
+        Ok(Self::from_config(device, config))
+    }
+
+    /// Create a capture instance using a specific input configuration,
+    /// e.g. fewer channels or a lower rate than a pro interface's default
+    pub fn new_with_config(device: Device, input_config: &InputConfig) -> Result<Self, String> {
+        let ranges = device
+            .supported_input_configs()
+            .map_err(|e| format!("Failed to get supported input configs: {}", e))?;
+        let config = choose_input_config(ranges, input_config)?;
+
+        let mut capture = Self::from_config(device, config);
+        capture.input_config = Some(input_config.clone());
+        Ok(capture)
+    }
+
+    /// Capture what an output device is playing (loopback).
+    /// Only supported by some hosts, e.g. WASAPI on Windows; on Linux use the
+    /// device's monitor input instead.
+    pub fn new_loopback(device: Device) -> Result<Self, String> {
+        let config = device
+            .default_output_config()
+            .map_err(|e| format!("Failed to get default output config: {}", e))?;
+
+        let mut capture = Self::from_config(device, config);
+        capture.loopback = true;
+        Ok(capture)
+    }
+
+    fn from_config(device: Device, config: SupportedStreamConfig) -> Self {
         tracing::info!(
             "Device config - Sample rate: {}, Channels: {}, Format: {:?}",
             config.sample_rate().0,
@@ -283,6 +710,7 @@ impl AudioCapture {
             config.sample_format()
         );
 
+        let sample_format = config.sample_format();
         let config = config.into();
 
         // Create buffer pool for audio processing optimization
@@ -290,19 +718,54 @@ impl AudioCapture {
         // Keep up to 10 buffers in the pool
         let buffer_pool = AudioBufferPool::new(4800, 10);
 
-        Ok(Self {
+        Self {
             device,
             config,
-            chunk_sender,
+            sample_format,
             buffer_pool,
-        })
+            target_sample_rate: STREAMING_SAMPLE_RATE,
+            input_config: None,
+            loopback: false,
+            tracks: 1,
+            dsp: DspConfig::bypass(),
+        }
+    }
+
+    /// Resample captured audio to `sample_rate` instead of [`STREAMING_SAMPLE_RATE`]
+    pub fn with_target_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.target_sample_rate = sample_rate;
+        self
+    }
+
+    /// Stream each device channel as its own track instead of downmixing,
+    /// for recorders that put every participant on a separate channel
+    pub fn with_separate_channels(mut self) -> Self {
+        self.tracks = self.config.channels.max(1) as usize;
+        self
+    }
+
+    /// Condition captured audio with `config`; by default it passes through unprocessed
+    pub fn with_dsp(mut self, config: DspConfig) -> Self {
+        self.dsp = config;
+        self
     }
 
-    /// Start capturing audio on a dedicated thread
+    /// Sample rate of the captured chunks (after resampling)
+    pub fn sample_rate(&self) -> u32 {
+        self.target_sample_rate
+    }
+
+    /// Start capturing audio on a dedicated thread, sending chunks to `chunk_sender`
     /// Returns a handle that can be used to stop the capture
-    pub fn start(self) -> Result<AudioCaptureHandle, String> {
+    ///
+    /// If the device disconnects, the thread feeds silence to keep the session's
+    /// timeline intact while it re-opens the device or falls back to the default
+    /// input. Device changes, dropped chunks and capture statistics are reported
+    /// through [`AudioCaptureHandle::take_events`].
+    pub fn start(self, chunk_sender: mpsc::Sender<Vec<i16>>) -> Result<AudioCaptureHandle, String> {
         // Create a channel for controlling the audio thread
         let (cmd_tx, cmd_rx) = std_mpsc::channel::<AudioThreadCommand>();
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         // Create a stop flag that the audio callback can check
         let stop_flag = Arc::new(AtomicBool::new(false));
@@ -311,36 +774,93 @@ impl AudioCapture {
         // Spawn a dedicated thread for audio capture
         // This is necessary because cpal::Stream is not Send on macOS
         let thread_handle = thread::spawn(move || {
-            let stream = match self.start_stream(stop_flag_clone) {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::error!("Failed to start audio stream: {}", e);
-                    return;
-                }
-            };
-
-            // Audio stream started - no debug logging needed
+            let mut capture = self;
+            let (mut stream, mut monitor) =
+                match capture.start_stream(chunk_sender.clone(), stop_flag_clone.clone()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        tracing::error!("Failed to start audio stream: {}", e);
+                        return;
+                    }
+                };
+            let mut stall = StallDetector::new(DEVICE_STALL_TIMEOUT, Instant::now());
+            let mut reporter = StatsReporter::new(Instant::now(), event_tx.clone());
 
             // Keep the stream alive until we receive a stop command
             loop {
-                match cmd_rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                match cmd_rx.recv_timeout(Duration::from_millis(100)) {
                     Ok(AudioThreadCommand::Stop) => {
                         tracing::info!("Received stop command, shutting down audio thread");
                         break;
                     }
-                    Err(std_mpsc::RecvTimeoutError::Timeout) => {
-                        // Continue running
-                        continue;
-                    }
+                    Err(std_mpsc::RecvTimeoutError::Timeout) => {}
                     Err(std_mpsc::RecvTimeoutError::Disconnected) => {
                         tracing::warn!("Command channel disconnected, shutting down audio thread");
                         break;
                     }
                 }
+
+                // Callbacks return early once stopping, which is not a stall
+                if stop_flag_clone.load(Ordering::Acquire) {
+                    continue;
+                }
+
+                let now = Instant::now();
+                if reporter.due(now) {
+                    reporter.report(&monitor, &capture.buffer_pool, now);
+                }
+
+                let (reason, lost_at) = match monitor.take_lost() {
+                    Some(reason) => (reason, now),
+                    None => match stall.check(monitor.callbacks.load(Ordering::Relaxed), now) {
+                        Some(last_audio) => (
+                            format!(
+                                "No audio received for {}s",
+                                now.duration_since(last_audio).as_secs()
+                            ),
+                            last_audio,
+                        ),
+                        None => continue,
+                    },
+                };
+
+                let lost_device = capture.describe();
+                tracing::warn!("🎤 Audio device lost ({}): {}", lost_device, reason);
+                drop(stream);
+                reporter.report(&monitor, &capture.buffer_pool, now);
+                let _ = event_tx.send(CaptureEvent::DeviceLost {
+                    device: lost_device.clone(),
+                    reason,
+                });
+
+                let Some((recovered, new_stream, new_monitor)) = capture.recover(
+                    lost_at,
+                    &chunk_sender,
+                    &stop_flag_clone,
+                    &cmd_rx,
+                    &monitor,
+                    &mut reporter,
+                ) else {
+                    tracing::info!("Capture stopped while the audio device was unavailable");
+                    break;
+                };
+
+                let device = recovered.describe();
+                let fallback = device != lost_device;
+                tracing::info!(
+                    "🎤 Audio capture resumed on {}{}",
+                    device,
+                    if fallback { " (fallback)" } else { "" }
+                );
+                let _ = event_tx.send(CaptureEvent::DeviceReconnected { device, fallback });
+
+                capture = recovered;
+                stream = new_stream;
+                monitor = new_monitor;
+                stall = StallDetector::new(DEVICE_STALL_TIMEOUT, Instant::now());
             }
 
-            // Stream will be dropped here, properly cleaning up resources
-            drop(stream);
+            // The stream is dropped with the thread, properly cleaning up resources
             tracing::info!("Audio thread shut down cleanly");
         });
 
@@ -348,126 +868,373 @@ impl AudioCapture {
             command_tx: cmd_tx,
             thread_handle: Some(thread_handle),
             stop_flag,
+            events: Some(event_rx),
+            inputs: Vec::new(),
         })
     }
 
+    /// Open a replacement for this capture's device: the same device if it is
+    /// back, otherwise the default input (or default output for loopback)
+    fn reopen(&self) -> Result<AudioCapture, String> {
+        let name = self.describe();
+        let host = cpal::default_host();
+
+        let devices = if self.loopback {
+            host.output_devices().ok()
+        } else {
+            host.input_devices().ok()
+        };
+        let same_device = devices.and_then(|mut devices| {
+            devices.find(|d| d.name().ok().as_deref() == Some(name.as_str()))
+        });
+        if let Some(device) = same_device {
+            let reopened = match &self.input_config {
+                _ if self.loopback => AudioCapture::new_loopback(device),
+                Some(input_config) => AudioCapture::new_with_config(device, input_config),
+                None => AudioCapture::new(device),
+            };
+            match reopened {
+                Ok(capture) => return Ok(capture.with_target_sample_rate(self.target_sample_rate)),
+                Err(e) => tracing::debug!("Could not re-open {}: {}", name, e),
+            }
+        }
+
+        let capture = if self.loopback {
+            let device = host
+                .default_output_device()
+                .ok_or_else(|| "No output device available".to_string())?;
+            AudioCapture::new_loopback(device)?
+        } else {
+            let device = host
+                .default_input_device()
+                .ok_or_else(|| "No input device available".to_string())?;
+            AudioCapture::new(device)?
+        };
+        Ok(capture.with_target_sample_rate(self.target_sample_rate))
+    }
+
+    /// Fill the gap with silence until a replacement stream is running.
+    /// Silence the channel can't take is counted on the lost stream's
+    /// `monitor` and reported like any other dropped chunks.
+    /// Returns `None` if capture is stopped first.
+    fn recover(
+        &self,
+        lost_at: Instant,
+        chunk_sender: &mpsc::Sender<Vec<i16>>,
+        stop_flag: &Arc<AtomicBool>,
+        cmd_rx: &std_mpsc::Receiver<AudioThreadCommand>,
+        monitor: &StreamMonitor,
+        reporter: &mut StatsReporter,
+    ) -> Option<(AudioCapture, cpal::Stream, Arc<StreamMonitor>)> {
+        let chunk_size = chunk_size_for(self.target_sample_rate) * self.tracks;
+        let mut silent_chunks = 0u64;
+        let mut next_attempt = Instant::now();
+
+        loop {
+            // Keep downstream timestamps and the session recording aligned with wall-clock time
+            let due = (lost_at.elapsed().as_secs_f32() * 1000.0 / CHUNK_DURATION_MS) as u64;
+            while silent_chunks < due {
+                if let Err(mpsc::error::TrySendError::Full(_)) =
+                    chunk_sender.try_send(vec![0; chunk_size])
+                {
+                    monitor.dropped_chunks.fetch_add(1, Ordering::Relaxed);
+                }
+                silent_chunks += 1;
+            }
+
+            let now = Instant::now();
+            if reporter.due(now) {
+                reporter.report(monitor, &self.buffer_pool, now);
+            }
+
+            if Instant::now() >= next_attempt {
+                let attempt = self.reopen().and_then(|mut capture| {
+                    // Keep pool statistics and the processing settings across the switch
+                    capture.buffer_pool = self.buffer_pool.clone();
+                    capture.tracks = self.tracks;
+                    capture.dsp = self.dsp.clone();
+                    let (stream, monitor) =
+                        capture.start_stream(chunk_sender.clone(), stop_flag.clone())?;
+                    Ok((capture, stream, monitor))
+                });
+                match attempt {
+                    Ok(recovered) => {
+                        // Don't leave the last of the gap's drops unreported
+                        reporter.report(monitor, &self.buffer_pool, Instant::now());
+                        return Some(recovered);
+                    }
+                    Err(e) => {
+                        tracing::debug!("Audio device not available yet: {}", e);
+                        next_attempt = Instant::now() + DEVICE_REOPEN_INTERVAL;
+                    }
+                }
+            }
+
+            match cmd_rx.recv_timeout(Duration::from_millis(CHUNK_DURATION_MS as u64)) {
+                Ok(AudioThreadCommand::Stop) | Err(std_mpsc::RecvTimeoutError::Disconnected) => {
+                    return None
+                }
+                Err(std_mpsc::RecvTimeoutError::Timeout) => {}
+            }
+        }
+    }
+
     /// Internal method to create and start the audio stream
     /// This runs on the dedicated audio thread
-    fn start_stream(&self, stop_flag: Arc<AtomicBool>) -> Result<cpal::Stream, String> {
+    fn start_stream(
+        &self,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+        stop_flag: Arc<AtomicBool>,
+    ) -> Result<(cpal::Stream, Arc<StreamMonitor>), String> {
         let channels = self.config.channels as usize;
-        let sample_rate = self.config.sample_rate.0;
-        let chunk_sender = self.chunk_sender.clone();
-        let buffer_pool = self.buffer_pool.clone();
-
-        // Calculate chunk size based on sample rate to ensure 50ms chunks
-        // AssemblyAI requires between 50ms and 1000ms
-        let chunk_size = ((sample_rate as f32 * CHUNK_DURATION_MS) / 1000.0) as usize;
+        let device_sample_rate = self.config.sample_rate.0;
 
         tracing::info!(
-            "Audio capture: {}Hz, {} channel(s), {}ms chunks",
-            sample_rate,
+            "Audio capture: {}Hz {:?} -> {}Hz, {} channel(s) -> {} track(s), {}ms chunks",
+            device_sample_rate,
+            self.sample_format,
+            self.target_sample_rate,
             channels,
+            self.tracks,
             CHUNK_DURATION_MS
         );
 
-        // Create a buffer for accumulating samples
-        let buffer = Arc::new(Mutex::new(Vec::with_capacity(chunk_size)));
+        let processor = CaptureProcessor::new(
+            channels,
+            self.tracks,
+            device_sample_rate,
+            self.target_sample_rate,
+            self.buffer_pool.clone(),
+            chunk_sender,
+            stop_flag,
+        )?
+        .with_dsp(&self.dsp, self.target_sample_rate);
+        let monitor = processor.monitor.clone();
+
+        // Build the input stream in the device's native sample format
+        let stream = match self.sample_format {
+            SampleFormat::I8 => self.build_stream::<i8>(processor),
+            SampleFormat::I16 => self.build_stream::<i16>(processor),
+            SampleFormat::I32 => self.build_stream::<i32>(processor),
+            SampleFormat::U8 => self.build_stream::<u8>(processor),
+            SampleFormat::U16 => self.build_stream::<u16>(processor),
+            SampleFormat::U32 => self.build_stream::<u32>(processor),
+            SampleFormat::F32 => self.build_stream::<f32>(processor),
+            SampleFormat::F64 => self.build_stream::<f64>(processor),
+            other => Err(format!("Unsupported sample format: {:?}", other)),
+        }?;
+
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start audio stream: {}", e))?;
 
-        // Audio level monitoring
-        let last_level_log = Arc::new(Mutex::new(std::time::Instant::now()));
-        let sample_count = Arc::new(Mutex::new(0usize));
+        Ok((stream, monitor))
+    }
 
-        // Build the input stream
-        let stream = self
-            .device
+    fn build_stream<T>(&self, mut processor: CaptureProcessor) -> Result<cpal::Stream, String>
+    where
+        T: SizedSample,
+        f32: FromSample<T>,
+    {
+        let monitor = processor.monitor.clone();
+        self.device
             .build_input_stream(
                 &self.config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    // Check stop flag first - exit immediately if stopping
-                    if stop_flag.load(Ordering::Acquire) {
-                        return; // Exit callback immediately
-                    }
-                    // Convert stereo to mono if needed
-                    let mono_data: Vec<f32> = if channels == 1 {
-                        data.to_vec()
-                    } else {
-                        // Average channels to create mono
-                        data.chunks(channels)
-                            .map(|chunk| chunk.iter().sum::<f32>() / channels as f32)
-                            .collect()
-                    };
-
-                    // Calculate audio level (RMS) for monitoring
-                    let rms = if !mono_data.is_empty() {
-                        let sum_squares: f32 = mono_data.iter().map(|&s| s * s).sum();
-                        (sum_squares / mono_data.len() as f32).sqrt()
-                    } else {
-                        0.0
-                    };
-
-                    // Log audio level every 15 seconds to help diagnose silent input
-                    {
-                        let mut count = sample_count.lock().expect("Audio sample count mutex poisoned");
-                        *count += mono_data.len();
-
-                        let mut last_log = last_level_log.lock().expect("Audio level log mutex poisoned");
-                        if last_log.elapsed().as_secs() >= 15 {
-                            let db = if rms > 0.0 {
-                                20.0 * rms.log10()
-                            } else {
-                                -100.0
-                            };
-
-                            if rms < 0.001 {
-                                tracing::warn!("🔇 Audio silent ({:.1} dB). Check microphone selection/mute/volume", db);
-                            } else {
-                                tracing::info!("🔊 Audio level: {:.1} dB", db);
-                            }
-
-                            *last_log = std::time::Instant::now();
-                        }
-                    }
-
-                    // Convert f32 to i16 PCM samples using pooled buffer
-                    let mut pcm_samples = buffer_pool.get_buffer();
-                    pcm_samples.extend(mono_data
-                        .iter()
-                        .map(|&sample| {
-                            (sample.clamp(-1.0, 1.0) * 32767.0) as i16
-                        }));
-
-                    // Add to buffer and send when we have enough samples
-                    let mut buf = buffer.lock().expect("Audio buffer mutex poisoned");
-                    buf.extend_from_slice(&pcm_samples);
-
-                    // Return the pcm_samples buffer to the pool for reuse
-                    buffer_pool.return_buffer(pcm_samples);
-
-                    // Send chunks when we have enough samples for the target duration
-                    while buf.len() >= chunk_size {
-                        let mut chunk = buffer_pool.get_buffer();
-                        // Drain into the pooled buffer
-                        chunk.extend(buf.drain(0..chunk_size));
-                        // Try to send chunk - if channel is full or closed, use try_send
-                        // This prevents blocking the audio thread if the receiver is slow
-                        if chunk_sender.try_send(chunk).is_err() {
-                            // Channel full or closed - expected during shutdown or if receiver is slow
-                            break;
-                        }
-                    }
-                },
-                move |err| {
-                    tracing::error!("Audio stream error: {}", err);
+                move |data: &[T], _: &cpal::InputCallbackInfo| processor.process(data),
+                move |err| match err {
+                    cpal::StreamError::DeviceNotAvailable => monitor.report_lost(err.to_string()),
+                    err => tracing::error!("Audio stream error: {}", err),
                 },
                 None,
             )
-            .map_err(|e| format!("Failed to build input stream: {}", e))?;
+            .map_err(|e| format!("Failed to build input stream: {}", e))
+    }
+}
 
-        stream
-            .play()
-            .map_err(|e| format!("Failed to start audio stream: {}", e))?;
+/// Turns raw device callbacks into resampled, conditioned i16 chunks of
+/// [`CHUNK_DURATION_MS`], either downmixed to mono or with each channel kept as
+/// an interleaved track.
+///
+/// Owned by the cpal data callback, so it never blocks: chunks are sent with
+/// `try_send` and dropped if the receiver falls behind.
+struct CaptureProcessor {
+    channels: usize,
+    /// One resampler per output track
+    resamplers: Vec<StreamResampler>,
+    /// One DSP chain per output track
+    dsp: Vec<DspChain>,
+    track_input: Vec<f32>,
+    resampled: Vec<Vec<f32>>,
+    pending: Vec<i16>,
+    chunk_size: usize,
+    buffer_pool: AudioBufferPool,
+    chunk_sender: mpsc::Sender<Vec<i16>>,
+    stop_flag: Arc<AtomicBool>,
+    monitor: Arc<StreamMonitor>,
+}
 
-        Ok(stream)
+impl CaptureProcessor {
+    fn new(
+        channels: usize,
+        tracks: usize,
+        device_sample_rate: u32,
+        target_sample_rate: u32,
+        buffer_pool: AudioBufferPool,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+        stop_flag: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        // Calculate chunk size based on the output rate to ensure 50ms chunks
+        // AssemblyAI requires between 50ms and 1000ms
+        let tracks = tracks.max(1);
+        let chunk_size = chunk_size_for(target_sample_rate) * tracks;
+
+        Ok(Self {
+            channels: channels.max(1),
+            resamplers: (0..tracks)
+                .map(|_| StreamResampler::new(device_sample_rate, target_sample_rate))
+                .collect::<Result<_, _>>()?,
+            dsp: (0..tracks)
+                .map(|_| DspChain::new(&DspConfig::bypass(), target_sample_rate))
+                .collect(),
+            track_input: Vec::with_capacity(4800),
+            resampled: vec![Vec::with_capacity(4800); tracks],
+            pending: Vec::with_capacity(chunk_size * 2),
+            chunk_size,
+            buffer_pool,
+            chunk_sender,
+            stop_flag,
+            monitor: Arc::default(),
+        })
+    }
+
+    /// Condition every track with `config` (processing is bypassed by default)
+    fn with_dsp(mut self, config: &DspConfig, sample_rate: u32) -> Self {
+        for chain in &mut self.dsp {
+            *chain = DspChain::new(config, sample_rate);
+        }
+        self
+    }
+
+    /// Handle one callback worth of interleaved samples
+    fn process<T>(&mut self, data: &[T])
+    where
+        T: Sample,
+        f32: FromSample<T>,
+    {
+        // Check stop flag first - exit immediately if stopping
+        if self.stop_flag.load(Ordering::Acquire) {
+            return;
+        }
+        self.monitor.callbacks.fetch_add(1, Ordering::Relaxed);
+
+        let started = Instant::now();
+        self.convert_and_send(data);
+        self.monitor
+            .record_callback(data.len() / self.channels, started.elapsed());
+    }
+
+    fn convert_and_send<T>(&mut self, data: &[T])
+    where
+        T: Sample,
+        f32: FromSample<T>,
+    {
+        let channels = self.channels;
+        let tracks = self.resamplers.len();
+
+        for (track, resampler) in self.resamplers.iter_mut().enumerate() {
+            // Convert to f32, downmixing to mono or picking out this track's channel.
+            // A replacement device with fewer channels leaves its extra tracks silent.
+            self.track_input.clear();
+            if tracks == 1 && channels == 1 {
+                self.track_input
+                    .extend(data.iter().map(|&s| f32::from_sample(s)));
+            } else if tracks == 1 {
+                self.track_input.extend(data.chunks(channels).map(|frame| {
+                    frame.iter().map(|&s| f32::from_sample(s)).sum::<f32>() / channels as f32
+                }));
+            } else {
+                self.track_input.extend(
+                    data.chunks(channels)
+                        .map(|frame| frame.get(track).map_or(0.0, |&s| f32::from_sample(s))),
+                );
+            }
+
+            // Resample to the streaming rate; output arrives in resampler-sized blocks
+            let resampled = &mut self.resampled[track];
+            resampled.clear();
+            if let Err(e) = resampler.process(&self.track_input, resampled) {
+                tracing::error!("{}", e);
+                return;
+            }
+            self.dsp[track].process(resampled);
+        }
+
+        // Convert f32 to i16 PCM samples, interleaving the tracks. Every resampler
+        // is fed the same number of frames, so they produce the same amount.
+        let frames = self.resampled.iter().map(Vec::len).min().unwrap_or(0);
+        for i in 0..frames {
+            self.pending.extend(
+                self.resampled
+                    .iter()
+                    .map(|track| (track[i].clamp(-1.0, 1.0) * 32767.0) as i16),
+            );
+        }
+
+        // Send chunks when we have enough samples for the target duration
+        while self.pending.len() >= self.chunk_size {
+            let mut chunk = self.buffer_pool.get_buffer();
+            // Drain into the pooled buffer
+            chunk.extend(self.pending.drain(0..self.chunk_size));
+            // Try to send chunk - if channel is full or closed, use try_send
+            // This prevents blocking the audio thread if the receiver is slow
+            match self.chunk_sender.try_send(chunk) {
+                Ok(()) => {}
+                // Receiver is slow: drop this chunk and keep going, so every
+                // chunk it can't take is counted and `pending` stays bounded
+                Err(mpsc::error::TrySendError::Full(chunk)) => {
+                    self.monitor.dropped_chunks.fetch_add(1, Ordering::Relaxed);
+                    self.buffer_pool.return_buffer(chunk);
+                }
+                // Closed - expected during shutdown, nothing will read the rest
+                Err(mpsc::error::TrySendError::Closed(chunk)) => {
+                    self.buffer_pool.return_buffer(chunk);
+                    self.pending.clear();
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Speaker label for a separately captured channel (1-based)
+fn channel_label(channel: usize) -> String {
+    format!("channel {}", channel)
+}
+
+impl AudioSource for AudioCapture {
+    fn sample_rate(&self) -> u32 {
+        AudioCapture::sample_rate(self)
+    }
+
+    fn describe(&self) -> String {
+        self.device
+            .name()
+            .unwrap_or_else(|_| "unknown input device".to_string())
+    }
+
+    fn track_labels(&self) -> Vec<String> {
+        if self.tracks > 1 {
+            (1..=self.tracks).map(channel_label).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String> {
+        AudioCapture::start(*self, chunk_sender)
     }
 }
 
@@ -482,6 +1249,8 @@ mod tests {
             name: "Test Microphone".to_string(),
             is_default: true,
             device_type: AudioDeviceType::Input,
+            supported_configs: Vec::new(),
+            default_config: None,
         };
 
         // Test serialization to JSON
@@ -541,6 +1310,299 @@ mod tests {
         assert_eq!(chunk_size_16k, 800); // 50ms at 16kHz = 800 samples
     }
 
+    fn test_processor(
+        channels: usize,
+        device_rate: u32,
+    ) -> (CaptureProcessor, mpsc::Receiver<Vec<i16>>) {
+        test_processor_with_tracks(channels, 1, device_rate)
+    }
+
+    fn test_processor_with_tracks(
+        channels: usize,
+        tracks: usize,
+        device_rate: u32,
+    ) -> (CaptureProcessor, mpsc::Receiver<Vec<i16>>) {
+        let (tx, rx) = mpsc::channel(64);
+        let processor = CaptureProcessor::new(
+            channels,
+            tracks,
+            device_rate,
+            STREAMING_SAMPLE_RATE,
+            AudioBufferPool::new(800, 4),
+            tx,
+            Arc::new(AtomicBool::new(false)),
+        )
+        .unwrap();
+        (processor, rx)
+    }
+
+    /// Fill the processor's channel as a stalled receiver would, leaving one slot
+    fn leave_one_free_slot(processor: &CaptureProcessor, rx: &mut mpsc::Receiver<Vec<i16>>) {
+        while processor.chunk_sender.try_send(Vec::new()).is_ok() {}
+        rx.try_recv().unwrap();
+    }
+
+    #[test]
+    fn test_capture_resampling_to_streaming_rate() {
+        // 10ms callbacks at 48kHz, resampled and cut into 50ms chunks
+        let (mut processor, mut rx) = test_processor(1, 48000);
+        for _ in 0..100 {
+            processor.process(&[0.25f32; 480]);
+        }
+
+        let mut chunks = 0;
+        while let Ok(chunk) = rx.try_recv() {
+            assert_eq!(chunk.len(), 800);
+            chunks += 1;
+        }
+        // One second of audio, minus what is still held in the resampler
+        assert!((18..=20).contains(&chunks), "got {} chunks", chunks);
+    }
+
+    #[test]
+    fn test_integer_sample_formats_convert_to_i16() {
+        let (mut processor, mut rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+
+        // Half scale in each format
+        processor.process(&[16384i16; 800]);
+        processor.process(&[49152u16; 800]);
+        processor.process(&[1i32 << 30; 800]);
+
+        for _ in 0..3 {
+            let chunk = rx.try_recv().unwrap();
+            assert!(chunk.iter().all(|&s| s == 16383), "got {:?}", &chunk[..4]);
+        }
+    }
+
+    #[test]
+    fn test_multichannel_integer_input_is_downmixed() {
+        let (mut processor, mut rx) = test_processor(2, STREAMING_SAMPLE_RATE);
+
+        let frames: Vec<i16> = (0..1600)
+            .map(|i| if i % 2 == 0 { 16384 } else { 0 })
+            .collect();
+        processor.process(&frames);
+
+        let chunk = rx.try_recv().unwrap();
+        assert_eq!(chunk.len(), 800);
+        assert!(chunk.iter().all(|&s| s == 8191));
+    }
+
+    #[test]
+    fn test_separate_channels_are_kept_as_tracks() {
+        let (mut processor, mut rx) = test_processor_with_tracks(2, 2, STREAMING_SAMPLE_RATE);
+
+        let frames: Vec<i16> = (0..1600)
+            .map(|i| if i % 2 == 0 { 16384 } else { -8192 })
+            .collect();
+        processor.process(&frames);
+
+        // 50ms per track, interleaved
+        let chunk = rx.try_recv().unwrap();
+        assert_eq!(chunk.len(), 1600);
+        assert!(chunk.iter().step_by(2).all(|&s| s == 16383));
+        assert!(chunk.iter().skip(1).step_by(2).all(|&s| s == -8191));
+    }
+
+    #[test]
+    fn test_dsp_chain_is_applied_before_conversion() {
+        let mut dsp = DspConfig::bypass();
+        dsp.high_pass.enabled = true;
+        let (processor, mut rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+        let mut processor = processor.with_dsp(&dsp, STREAMING_SAMPLE_RATE);
+
+        // A DC offset is filtered out once the high-pass settles
+        for _ in 0..20 {
+            processor.process(&[0.25f32; 800]);
+        }
+        let last = std::iter::from_fn(|| rx.try_recv().ok()).last().unwrap();
+        assert!(last.iter().all(|&s| s.abs() < 10), "got {:?}", &last[..4]);
+    }
+
+    #[test]
+    fn test_missing_channels_are_silent_tracks() {
+        // A mono replacement device feeding a two-track capture
+        let (mut processor, mut rx) = test_processor_with_tracks(1, 2, 48000);
+        for _ in 0..20 {
+            processor.process(&[0.5f32; 480]);
+        }
+
+        let chunk = rx.try_recv().unwrap();
+        assert_eq!(chunk.len(), 1600);
+        assert!(chunk.iter().skip(1).step_by(2).all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_processor_ignores_callbacks_after_stop() {
+        let (mut processor, mut rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+        processor.stop_flag.store(true, Ordering::Release);
+
+        processor.process(&[0.5f32; 1600]);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_processor_counts_callbacks() {
+        let (mut processor, _rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+        processor.process(&[0.0f32; 160]);
+        processor.process(&[0.0f32; 160]);
+        assert_eq!(processor.monitor.callbacks.load(Ordering::Relaxed), 2);
+
+        processor.monitor.report_lost("first".to_string());
+        processor.monitor.report_lost("second".to_string());
+        assert_eq!(processor.monitor.take_lost().as_deref(), Some("first"));
+        assert_eq!(processor.monitor.take_lost(), None);
+    }
+
+    #[test]
+    fn test_stall_detector() {
+        let start = Instant::now();
+        let mut stall = StallDetector::new(Duration::from_secs(3), start);
+
+        assert_eq!(stall.check(5, start + Duration::from_secs(1)), None);
+        assert_eq!(stall.check(5, start + Duration::from_secs(3)), None);
+        assert_eq!(
+            stall.check(5, start + Duration::from_secs(4)),
+            Some(start + Duration::from_secs(1))
+        );
+
+        // Audio flowing again resets the timer
+        assert_eq!(stall.check(6, start + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn test_device_event_serialization() {
+        let lost = CaptureEvent::DeviceLost {
+            device: "USB Headset".to_string(),
+            reason: "device unplugged".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&lost).unwrap(),
+            serde_json::json!({
+                "kind": "device_lost",
+                "device": "USB Headset",
+                "reason": "device unplugged"
+            })
+        );
+
+        let reconnected = CaptureEvent::DeviceReconnected {
+            device: "MacBook Pro Microphone".to_string(),
+            fallback: true,
+        };
+        assert_eq!(
+            serde_json::to_value(&reconnected).unwrap()["kind"],
+            "device_reconnected"
+        );
+    }
+
+    #[test]
+    fn test_full_channel_counts_dropped_chunks() {
+        let (mut processor, mut rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+        leave_one_free_slot(&processor, &mut rx);
+
+        for _ in 0..3 {
+            processor.process(&[0.1f32; 800]);
+        }
+
+        let stats = processor.monitor.take_stats();
+        assert_eq!(stats.callbacks, 3);
+        assert_eq!(stats.frames, 2400);
+        assert_eq!(stats.dropped_chunks, 2);
+        assert!(stats.callback_us_max <= stats.callback_us_total);
+        assert_eq!(processor.monitor.take_stats().callbacks, 0);
+    }
+
+    #[test]
+    fn test_full_channel_drops_every_ready_chunk() {
+        let (mut processor, mut rx) = test_processor(1, STREAMING_SAMPLE_RATE);
+        leave_one_free_slot(&processor, &mut rx);
+
+        // One callback worth five chunks: one fits, the other four are dropped
+        processor.process(&[0.1f32; 4000]);
+        assert!(processor.pending.is_empty());
+        assert_eq!(processor.monitor.take_stats().dropped_chunks, 4);
+
+        for _ in 0..10 {
+            processor.process(&[0.1f32; 4000]);
+        }
+        assert!(processor.pending.len() < processor.chunk_size);
+        assert_eq!(processor.monitor.take_stats().dropped_chunks, 50);
+    }
+
+    #[test]
+    fn test_drop_alarm_threshold_and_cooldown() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut alarm = DropAlarm::new();
+
+        // Drops spread over more than the window never add up
+        for i in 0..5 {
+            assert_eq!(alarm.record(3, at(i * 11)), None);
+        }
+
+        assert_eq!(alarm.record(6, at(60)), None);
+        assert_eq!(alarm.record(6, at(61)), Some(12));
+        // Quiet during the cooldown, even while drops continue
+        assert_eq!(alarm.record(20, at(62)), None);
+        assert_eq!(alarm.record(20, at(91)), Some(20));
+    }
+
+    fn range(
+        channels: u16,
+        min: u32,
+        max: u32,
+        format: SampleFormat,
+    ) -> SupportedStreamConfigRange {
+        SupportedStreamConfigRange::new(
+            channels,
+            SampleRate(min),
+            SampleRate(max),
+            cpal::SupportedBufferSize::Unknown,
+            format,
+        )
+    }
+
+    #[test]
+    fn test_choose_input_config_prefers_float() {
+        let ranges = vec![
+            range(8, 44100, 96000, SampleFormat::I32),
+            range(2, 44100, 96000, SampleFormat::I16),
+            range(2, 44100, 96000, SampleFormat::F32),
+        ];
+        let wanted = InputConfig {
+            sample_rate: 48000,
+            channels: 2,
+            sample_format: None,
+        };
+
+        let config = choose_input_config(ranges, &wanted).unwrap();
+        assert_eq!(config.channels(), 2);
+        assert_eq!(config.sample_rate().0, 48000);
+        assert_eq!(config.sample_format(), SampleFormat::F32);
+    }
+
+    #[test]
+    fn test_choose_input_config_honours_format_and_rejects_unsupported() {
+        let ranges = vec![
+            range(2, 44100, 48000, SampleFormat::I16),
+            range(2, 44100, 48000, SampleFormat::F32),
+        ];
+
+        let wanted = InputConfig {
+            sample_rate: 44100,
+            channels: 2,
+            sample_format: Some("i16".to_string()),
+        };
+        let config = choose_input_config(ranges.clone(), &wanted).unwrap();
+        assert_eq!(config.sample_format(), SampleFormat::I16);
+
+        let too_fast = InputConfig {
+            sample_rate: 96000,
+            ..wanted
+        };
+        assert!(choose_input_config(ranges, &too_fast).is_err());
+    }
+
     #[test]
     fn test_audio_device_type_serialization() {
         // Test AudioDeviceType serialization (maintains original case)
@@ -0,0 +1,475 @@
+//! # Audio File Playback
+//!
+//! Decodes previously recorded calls (WAV or raw PCM) and replays them as an
+//! [`AudioSource`], feeding the same chunk channel used by live capture, so offline recordings
+//! go through AssemblyAI streaming, buffering, enhancement and session tracking exactly
+//! like a live session.
+//!
+//! Audio is downmixed to mono, resampled to [`PLAYBACK_SAMPLE_RATE`] and sent in
+//! real time: the streaming API expects audio at roughly the rate it was spoken.
+//! Files are decoded a block at a time as they are played, so hour-long calls
+//! take no more memory than short ones.
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use tokio::sync::mpsc;
+
+use super::audio::{AudioCaptureHandle, STREAMING_SAMPLE_RATE};
+use super::resampler::StreamResampler;
+use super::source::{chunk_size_for, spawn_producer, validate_sample_rate, AudioSource};
+
+/// Sample rate files are converted to before streaming
+pub const PLAYBACK_SAMPLE_RATE: u32 = STREAMING_SAMPLE_RATE;
+
+/// Layout of a headerless PCM file (signed 16-bit little endian, interleaved)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RawPcmFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl RawPcmFormat {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_sample_rate("Raw PCM sample rate", self.sample_rate)?;
+        if self.channels == 0 {
+            return Err("Raw PCM format needs at least one channel".to_string());
+        }
+        Ok(())
+    }
+
+    fn frame_bytes(&self) -> usize {
+        self.channels as usize * 2
+    }
+}
+
+/// Frames read from the file at a time, before resampling
+const READ_FRAMES: usize = 4096;
+
+enum FileFormat {
+    Wav {
+        reader: hound::WavReader<BufReader<File>>,
+        sample_format: hound::SampleFormat,
+        /// Full scale of integer samples
+        scale: f32,
+    },
+    Raw {
+        reader: BufReader<File>,
+        format: RawPcmFormat,
+    },
+}
+
+/// Reads an audio file (WAV or raw PCM) a block at a time as mono f32 samples,
+/// so long recordings never have to fit in memory.
+///
+/// WAV files are read with `hound`; pass `raw_format` to read headerless
+/// s16le PCM instead.
+pub struct AudioFileReader {
+    format: FileFormat,
+    channels: usize,
+    sample_rate: u32,
+    total_frames: u64,
+}
+
+impl AudioFileReader {
+    pub fn open(path: &Path, raw_format: Option<RawPcmFormat>) -> Result<Self, String> {
+        match raw_format {
+            Some(format) => Self::open_raw_pcm(path, format),
+            None => Self::open_wav(path),
+        }
+    }
+
+    fn open_wav(path: &Path) -> Result<Self, String> {
+        let reader = hound::WavReader::open(path)
+            .map_err(|e| format!("Failed to open WAV file {}: {}", path.display(), e))?;
+        let spec = reader.spec();
+        if spec.channels == 0 {
+            return Err(format!("WAV file {} has no channels", path.display()));
+        }
+
+        tracing::info!(
+            "Opened WAV: {}Hz, {} channel(s), {} bits, {} frames",
+            spec.sample_rate,
+            spec.channels,
+            spec.bits_per_sample,
+            reader.duration()
+        );
+
+        Ok(Self {
+            total_frames: reader.duration() as u64,
+            channels: spec.channels as usize,
+            sample_rate: spec.sample_rate,
+            format: FileFormat::Wav {
+                reader,
+                sample_format: spec.sample_format,
+                scale: (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32,
+            },
+        })
+    }
+
+    fn open_raw_pcm(path: &Path, format: RawPcmFormat) -> Result<Self, String> {
+        format.validate()?;
+        let file = File::open(path)
+            .map_err(|e| format!("Failed to read PCM file {}: {}", path.display(), e))?;
+        let bytes = file
+            .metadata()
+            .map_err(|e| format!("Failed to read PCM file {}: {}", path.display(), e))?
+            .len();
+
+        Ok(Self {
+            total_frames: bytes / format.frame_bytes() as u64,
+            channels: format.channels as usize,
+            sample_rate: format.sample_rate,
+            format: FileFormat::Raw {
+                reader: BufReader::new(file),
+                format,
+            },
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Length of the audio in seconds
+    pub fn duration_secs(&self) -> f64 {
+        self.total_frames as f64 / self.sample_rate.max(1) as f64
+    }
+
+    /// Read up to `max_frames` frames, downmixed to mono, into `output`.
+    /// Returns the number of frames read, 0 once the file is exhausted.
+    ///
+    /// An incomplete frame at the end of a truncated file is skipped with a
+    /// warning rather than mixed from the channels that are there.
+    pub fn read(&mut self, max_frames: usize, output: &mut Vec<f32>) -> Result<usize, String> {
+        let wanted = max_frames * self.channels;
+        let interleaved: Vec<f32> = match &mut self.format {
+            FileFormat::Wav {
+                reader,
+                sample_format: hound::SampleFormat::Float,
+                ..
+            } => reader
+                .samples::<f32>()
+                .take(wanted)
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Failed to read WAV samples: {}", e))?,
+            FileFormat::Wav { reader, scale, .. } => {
+                let scale = *scale;
+                reader
+                    .samples::<i32>()
+                    .take(wanted)
+                    .map(|s| s.map(|v| v as f32 / scale))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| format!("Failed to read WAV samples: {}", e))?
+            }
+            FileFormat::Raw { reader, format } => {
+                let mut bytes = Vec::with_capacity(max_frames * format.frame_bytes());
+                reader
+                    .take((max_frames * format.frame_bytes()) as u64)
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| format!("Failed to read PCM file: {}", e))?;
+                let samples = bytes.chunks_exact(2);
+                if !samples.remainder().is_empty() {
+                    tracing::warn!("Ignoring a trailing odd byte at the end of the PCM file");
+                }
+                samples
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                    .collect()
+            }
+        };
+
+        let before = output.len();
+        let incomplete = downmix(&interleaved, self.channels, output);
+        if incomplete > 0 {
+            tracing::warn!(
+                "Ignoring an incomplete frame at the end of the file ({} of {} samples)",
+                incomplete,
+                self.channels
+            );
+        }
+        Ok(output.len() - before)
+    }
+}
+
+/// Average interleaved channels down to mono, appending to `output`.
+/// Returns how many trailing samples did not make up a whole frame.
+fn downmix(interleaved: &[f32], channels: usize, output: &mut Vec<f32>) -> usize {
+    let frames = interleaved.chunks_exact(channels.max(1));
+    let incomplete = frames.remainder().len();
+    output.extend(frames.map(|frame| frame.iter().sum::<f32>() / frame.len() as f32));
+    incomplete
+}
+
+/// Decodes and resamples a file into streaming chunks as they are needed
+struct PlaybackDecoder {
+    reader: AudioFileReader,
+    resampler: StreamResampler,
+    decoded: Vec<f32>,
+    resampled: Vec<f32>,
+    finished: bool,
+}
+
+impl PlaybackDecoder {
+    /// Read and resample until a chunk is ready or the file ends
+    fn fill(&mut self, chunk_size: usize) -> Result<(), String> {
+        while self.resampled.len() < chunk_size && !self.finished {
+            self.decoded.clear();
+            if self.reader.read(READ_FRAMES, &mut self.decoded)? == 0 {
+                self.finished = true;
+                self.resampler.flush(&mut self.resampled)?;
+            } else {
+                self.resampler.process(&self.decoded, &mut self.resampled)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn next_chunk(&mut self, chunk_size: usize) -> Option<Vec<i16>> {
+        if let Err(e) = self.fill(chunk_size) {
+            // Stream what was decoded; the session ends with the file
+            tracing::error!("File playback failed: {}", e);
+            self.finished = true;
+        }
+        if self.resampled.is_empty() {
+            return None;
+        }
+
+        let len = chunk_size.min(self.resampled.len());
+        Some(
+            self.resampled
+                .drain(..len)
+                .map(|sample| (sample.clamp(-1.0, 1.0) * 32767.0) as i16)
+                .collect(),
+        )
+    }
+}
+
+/// Replays a file into the transcription chunk channel at real-time pace,
+/// decoding it as it goes.
+pub struct FilePlayback {
+    reader: AudioFileReader,
+    description: String,
+    realtime: bool,
+}
+
+impl FilePlayback {
+    /// Open `path`, ready to be streamed
+    pub fn open(path: &Path, raw_format: Option<RawPcmFormat>) -> Result<Self, String> {
+        let reader = AudioFileReader::open(path, raw_format)?;
+        if reader.total_frames == 0 {
+            return Err(format!("Audio file {} contains no samples", path.display()));
+        }
+        // Fail now rather than once playback has started
+        StreamResampler::new(reader.sample_rate(), PLAYBACK_SAMPLE_RATE)?;
+
+        Ok(Self {
+            reader,
+            description: format!("file {}", path.display()),
+            realtime: true,
+        })
+    }
+
+    /// Length of the audio in seconds
+    pub fn duration_secs(&self) -> f64 {
+        self.reader.duration_secs()
+    }
+
+    /// Produce chunks as fast as the receiver accepts them instead of in real time
+    #[cfg(test)]
+    pub fn unpaced(mut self) -> Self {
+        self.realtime = false;
+        self
+    }
+}
+
+impl AudioSource for FilePlayback {
+    fn sample_rate(&self) -> u32 {
+        PLAYBACK_SAMPLE_RATE
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    /// The chunk sender is dropped once the file is exhausted, closing the channel.
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String> {
+        tracing::info!(
+            "📼 File playback started ({:.1}s of audio)",
+            self.duration_secs()
+        );
+
+        let chunk_size = chunk_size_for(PLAYBACK_SAMPLE_RATE);
+        let mut decoder = PlaybackDecoder {
+            resampler: StreamResampler::new(self.reader.sample_rate(), PLAYBACK_SAMPLE_RATE)?,
+            reader: self.reader,
+            decoded: Vec::with_capacity(READ_FRAMES),
+            resampled: Vec::new(),
+            finished: false,
+        };
+
+        Ok(spawn_producer(
+            PLAYBACK_SAMPLE_RATE,
+            self.realtime,
+            chunk_sender,
+            move |_| {
+                let chunk = decoder.next_chunk(chunk_size);
+                if chunk.is_none() {
+                    tracing::info!("📼 File playback finished");
+                }
+                chunk
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_test_wav(path: &Path, sample_rate: u32, channels: u16, frames: usize) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..frames {
+            for _ in 0..channels {
+                writer.write_sample(((i % 100) as i16) * 100).unwrap();
+            }
+        }
+        writer.finalize().unwrap();
+    }
+
+    /// Read a whole file as mono samples
+    fn read_all(reader: &mut AudioFileReader) -> Vec<f32> {
+        let mut samples = Vec::new();
+        while reader.read(1000, &mut samples).unwrap() > 0 {}
+        samples
+    }
+
+    fn write_pcm(path: &Path, samples: &[i16], extra: &[u8]) {
+        let mut bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        bytes.extend_from_slice(extra);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_decode_stereo_wav_to_mono() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.wav");
+        write_test_wav(&path, 44100, 2, 4410);
+
+        let mut reader = AudioFileReader::open(&path, None).unwrap();
+        assert_eq!(reader.sample_rate(), 44100);
+        assert!((reader.duration_secs() - 0.1).abs() < 1e-9);
+        assert_eq!(read_all(&mut reader).len(), 4410);
+    }
+
+    #[test]
+    fn test_decode_raw_pcm() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.pcm");
+        write_pcm(&path, &[1000, -1000, 2000, -2000], &[]);
+
+        let mut reader = AudioFileReader::open(
+            &path,
+            Some(RawPcmFormat {
+                sample_rate: 8000,
+                channels: 2,
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(reader.sample_rate(), 8000);
+        assert_eq!(read_all(&mut reader), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_truncated_raw_pcm_skips_incomplete_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.pcm");
+        // One whole stereo frame, then a lone left sample and an odd byte
+        write_pcm(&path, &[1000, 3000, 16384], &[0x7f]);
+
+        let format = RawPcmFormat {
+            sample_rate: 8000,
+            channels: 2,
+        };
+        let mut reader = AudioFileReader::open(&path, Some(format)).unwrap();
+        let expected = 2000.0 / 32768.0;
+        assert_eq!(read_all(&mut reader), vec![expected]);
+    }
+
+    #[test]
+    fn test_downmix_reports_incomplete_frame() {
+        let mut mono = Vec::new();
+        assert_eq!(downmix(&[0.5, 0.25, 1.0], 2, &mut mono), 1);
+        assert_eq!(mono, vec![0.375]);
+        assert_eq!(downmix(&[0.5], 1, &mut mono), 0);
+    }
+
+    #[test]
+    fn test_raw_format_validation() {
+        let format = |sample_rate, channels| RawPcmFormat {
+            sample_rate,
+            channels,
+        };
+        assert!(format(8000, 1).validate().is_ok());
+        assert!(format(0, 1).validate().is_err());
+        assert!(format(8000, 0).validate().is_err());
+    }
+
+    #[test]
+    fn test_playback_resamples_to_target_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.wav");
+        write_test_wav(&path, 48000, 1, 48000);
+
+        let playback = FilePlayback::open(&path, None).unwrap();
+
+        assert_eq!(playback.sample_rate(), PLAYBACK_SAMPLE_RATE);
+        assert!((playback.duration_secs() - 1.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_playback_streams_decoded_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("call.wav");
+        // Longer than one read, and not a whole number of chunks
+        write_test_wav(&path, 44100, 2, 44100 * 3 / 2 + 123);
+
+        let playback = FilePlayback::open(&path, None).unwrap().unpaced();
+        let (tx, mut rx) = mpsc::channel(4);
+        let handle = Box::new(playback).start(tx).unwrap();
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            chunks.push(chunk);
+        }
+        handle.stop().unwrap();
+
+        let chunk_size = chunk_size_for(PLAYBACK_SAMPLE_RATE);
+        let total: usize = chunks.iter().map(Vec::len).sum();
+        assert_eq!(total, (44100 * 3 / 2 + 123) * 16000 / 44100);
+        assert!(chunks[..chunks.len() - 1]
+            .iter()
+            .all(|chunk| chunk.len() == chunk_size));
+        assert!(chunks.iter().flatten().any(|&s| s != 0));
+    }
+
+    #[test]
+    fn test_missing_file_is_an_error() {
+        let result = AudioFileReader::open(Path::new("/nonexistent/call.wav"), None);
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 /// Audio buffer pool for reducing allocations in real-time audio processing
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Pool of reusable audio buffers to reduce allocations in real-time processing
@@ -6,6 +7,7 @@ pub struct AudioBufferPool {
     buffers: Arc<Mutex<Vec<Vec<i16>>>>,
     buffer_size: usize,
     max_pool_size: usize,
+    misses: Arc<AtomicU64>,
 }
 
 impl AudioBufferPool {
@@ -15,13 +17,17 @@ impl AudioBufferPool {
             buffers: Arc::new(Mutex::new(Vec::with_capacity(max_pool_size))),
             buffer_size,
             max_pool_size,
+            misses: Arc::new(AtomicU64::new(0)),
         }
     }
 
     /// Get a buffer from the pool, or create a new one if pool is empty
     pub fn get_buffer(&self) -> Vec<i16> {
         if let Ok(mut buffers) = self.buffers.lock() {
-            buffers.pop().unwrap_or_else(|| Vec::with_capacity(self.buffer_size))
+            buffers.pop().unwrap_or_else(|| {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                Vec::with_capacity(self.buffer_size)
+            })
         } else {
             // Mutex is poisoned, create new buffer
             Vec::with_capacity(self.buffer_size)
@@ -45,19 +51,21 @@ impl AudioBufferPool {
     }
 
     /// Get statistics about the buffer pool
-    #[allow(dead_code)] // Available for monitoring and debugging
     pub fn stats(&self) -> BufferPoolStats {
+        let misses = self.misses.load(Ordering::Relaxed);
         if let Ok(buffers) = self.buffers.lock() {
             BufferPoolStats {
                 available_buffers: buffers.len(),
                 max_pool_size: self.max_pool_size,
                 buffer_size: self.buffer_size,
+                misses,
             }
         } else {
             BufferPoolStats {
                 available_buffers: 0,
                 max_pool_size: self.max_pool_size,
                 buffer_size: self.buffer_size,
+                misses,
             }
         }
     }
@@ -70,6 +78,8 @@ pub struct BufferPoolStats {
     pub available_buffers: usize,
     pub max_pool_size: usize,
     pub buffer_size: usize,
+    /// Buffers allocated because the pool was empty
+    pub misses: u64,
 }
 
 impl Clone for AudioBufferPool {
@@ -78,6 +88,7 @@ impl Clone for AudioBufferPool {
             buffers: Arc::clone(&self.buffers),
             buffer_size: self.buffer_size,
             max_pool_size: self.max_pool_size,
+            misses: Arc::clone(&self.misses),
         }
     }
 }
@@ -104,6 +115,7 @@ mod tests {
         // Stats should show 1 available buffer
         let stats = pool.stats();
         assert_eq!(stats.available_buffers, 1);
+        assert_eq!(stats.misses, 1);
 
         // Get it back - should be the same buffer (cleared)
         let buffer2 = pool.get_buffer();
@@ -126,5 +138,10 @@ mod tests {
 
         let stats = pool.stats();
         assert_eq!(stats.available_buffers, 2); // Only 2 buffers kept
+        assert_eq!(stats.misses, 3);
+
+        // Pooled buffers are reused without allocating
+        let _reused = pool.get_buffer();
+        assert_eq!(pool.stats().misses, 3);
     }
-}
\ No newline at end of file
+}
@@ -1,23 +1,35 @@
+use crate::database::{Database, ProviderSession};
 use crate::logging::MetricsCollector;
 use crate::transcription::{
-    assemblyai::{self, AssemblyAIClient},
-    audio,
+    assemblyai::{StreamingConfig, TranscriptResult},
+    audio, audio_file,
     buffer::{BufferManager, TranscriptionBuffer},
+    dsp::DspConfig,
     enhancement::EnhancementAgent,
+    language::{self, PromptSettings},
+    levels::{LevelMeter, LevelMeterConfig},
+    processing::{ChunkStage, PauseGate, StageEvent, StageSummary, TrackSplitter},
+    provider::{ProviderConfig, ProviderEvent, TranscriptionProvider},
+    recorder::SessionAudioRecorder,
     refinement::RefinementAgent,
-    session::SessionManager,
-    summary, RefinementConfig, RefinementMode,
+    session::{SessionData, SessionManager, TurnData, TurnWord},
+    source::{AudioSource, AudioSourceConfig},
+    summary,
+    vad::{StreamTimeline, VadConfig, VoiceActivityDetector},
+    RefinementConfig, RefinementMode,
 };
-use cpal::traits::DeviceTrait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
-use tauri::{AppHandle, Emitter, State};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Runtime, State};
 use tokio::sync::{mpsc, Mutex};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptionState {
     pub is_active: bool,
+    pub is_paused: bool,
     pub device_id: Option<String>,
 }
 
@@ -29,6 +41,8 @@ pub struct AppState {
     pub session_manager: SessionManager,
     pub current_project_id: Arc<Mutex<Option<String>>>,
     pub session_start_time: Arc<Mutex<Option<Instant>>>,
+    /// Set while the session is paused; audio is replaced with silence
+    pub capture_paused: Arc<AtomicBool>,
     pub metrics: Arc<MetricsCollector>,
 }
 
@@ -42,6 +56,7 @@ impl Default for AppState {
             session_manager: SessionManager::new(),
             current_project_id: Arc::new(Mutex::new(None)),
             session_start_time: Arc::new(Mutex::new(None)),
+            capture_paused: Arc::new(AtomicBool::new(false)),
             metrics: Arc::new(MetricsCollector::new()),
         }
     }
@@ -54,16 +69,24 @@ pub async fn list_audio_devices() -> Result<Vec<audio::AudioDevice>, String> {
     audio::list_audio_devices()
 }
 
-#[tracing::instrument(skip(app, state, api_key, claude_api_key))]
+#[tracing::instrument(skip(app, state, db, api_key, claude_api_key))]
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn start_transcription(
     app: AppHandle,
     state: State<'_, AppState>,
+    db: State<'_, Database>,
     device_id: String,
-    api_key: String,
+    api_key: Option<String>,
     claude_api_key: Option<String>,
     project_id: Option<String>,
     refinement_config: Option<RefinementConfig>,
+    input_config: Option<audio::InputConfig>,
+    separate_channels: Option<bool>,
+    dsp_config: Option<DspConfig>,
+    vad_config: Option<VadConfig>,
+    provider: Option<ProviderConfig>,
+    streaming_config: Option<StreamingConfig>,
 ) -> Result<(), String> {
     let refinement_cfg = refinement_config.unwrap_or_default();
     tracing::info!(
@@ -73,6 +96,262 @@ pub async fn start_transcription(
         refinement_cfg.mode
     );
 
+    let vad_config = resolve_vad_config(vad_config)?;
+    let source = AudioSourceConfig::Device {
+        device_id: device_id.clone(),
+        target_sample_rate: None,
+        input_config,
+        separate_channels: separate_channels.unwrap_or(false),
+        dsp: dsp_config.unwrap_or_else(DspConfig::bypass),
+    }
+    .open()?;
+    let streaming_config =
+        resolve_streaming_config(&db, project_id.as_deref(), streaming_config).await?;
+    let vocabulary = project_vocabulary(&db, project_id.as_deref()).await;
+    let language = streaming_config.fixed_language();
+    let provider = create_provider(provider, api_key, streaming_config, vocabulary.clone()).await?;
+    let prompt = PromptSettings::default()
+        .with_vocabulary(vocabulary)
+        .with_language(language);
+    let enhancement =
+        claude_api_key.map(|key| EnhancementAgent::new(key).with_prompt_settings(prompt));
+
+    start_pipeline(
+        &app,
+        &state,
+        source,
+        provider,
+        enhancement,
+        project_id.clone(),
+        refinement_cfg,
+        vad_config,
+    )
+    .await?;
+
+    // Emit transcription started event
+    if let Err(e) = app.emit("transcription_started", serde_json::json!({
+        "device_id": device_id,
+        "project_id": project_id
+    })) {
+        tracing::error!("Failed to emit transcription_started event: {}", e);
+    }
+
+    tracing::info!("Real-time transcription started successfully");
+    Ok(())
+}
+
+/// Start transcription from any configured audio source.
+///
+/// Lets the pipeline run from synthetic tones or network PCM streams as well as
+/// input devices, e.g. on machines without audio hardware.
+#[tracing::instrument(skip(app, state, db, api_key, claude_api_key))]
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn start_transcription_from_source(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    db: State<'_, Database>,
+    source: AudioSourceConfig,
+    api_key: Option<String>,
+    claude_api_key: Option<String>,
+    project_id: Option<String>,
+    refinement_config: Option<RefinementConfig>,
+    vad_config: Option<VadConfig>,
+    provider: Option<ProviderConfig>,
+    streaming_config: Option<StreamingConfig>,
+) -> Result<(), String> {
+    let refinement_cfg = refinement_config.unwrap_or_default();
+    let vad_config = resolve_vad_config(vad_config)?;
+    let audio_source = source.open()?;
+    let description = audio_source.describe();
+    let streaming_config =
+        resolve_streaming_config(&db, project_id.as_deref(), streaming_config).await?;
+    let vocabulary = project_vocabulary(&db, project_id.as_deref()).await;
+    let language = streaming_config.fixed_language();
+    let provider = create_provider(provider, api_key, streaming_config, vocabulary.clone()).await?;
+    let prompt = PromptSettings::default()
+        .with_vocabulary(vocabulary)
+        .with_language(language);
+    let enhancement =
+        claude_api_key.map(|key| EnhancementAgent::new(key).with_prompt_settings(prompt));
+
+    start_pipeline(
+        &app,
+        &state,
+        audio_source,
+        provider,
+        enhancement,
+        project_id.clone(),
+        refinement_cfg,
+        vad_config,
+    )
+    .await?;
+
+    if let Err(e) = app.emit("transcription_started", serde_json::json!({
+        "source": description,
+        "project_id": project_id
+    })) {
+        tracing::error!("Failed to emit transcription_started event: {}", e);
+    }
+
+    tracing::info!("Transcription started from {}", description);
+    Ok(())
+}
+
+/// Transcribe a recorded WAV/PCM file through the live streaming pipeline.
+///
+/// The file is replayed in real time, so it produces the same `transcript`,
+/// `enhanced_transcript` and session data as a live recording and can be
+/// stopped early with `stop_transcription`.
+#[tracing::instrument(skip(app, state, db, api_key, claude_api_key))]
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn transcribe_file(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    db: State<'_, Database>,
+    file_path: String,
+    raw_format: Option<audio_file::RawPcmFormat>,
+    api_key: Option<String>,
+    claude_api_key: Option<String>,
+    project_id: Option<String>,
+    refinement_config: Option<RefinementConfig>,
+    vad_config: Option<VadConfig>,
+    provider: Option<ProviderConfig>,
+    streaming_config: Option<StreamingConfig>,
+) -> Result<(), String> {
+    let refinement_cfg = refinement_config.unwrap_or_default();
+    tracing::info!(
+        "Starting file transcription: {} (project: {:?}, refinement: {:?})",
+        file_path,
+        project_id,
+        refinement_cfg.mode
+    );
+
+    let vad_config = resolve_vad_config(vad_config)?;
+
+    // Opening reads the file header from disk - keep it off the async runtime
+    let path = std::path::PathBuf::from(&file_path);
+    let playback = tokio::task::spawn_blocking(move || {
+        audio_file::FilePlayback::open(&path, raw_format)
+    })
+    .await
+    .map_err(|e| format!("Failed to join file open task: {}", e))??;
+
+    let duration_secs = playback.duration_secs();
+    let streaming_config =
+        resolve_streaming_config(&db, project_id.as_deref(), streaming_config).await?;
+    let vocabulary = project_vocabulary(&db, project_id.as_deref()).await;
+    let language = streaming_config.fixed_language();
+    let provider = create_provider(provider, api_key, streaming_config, vocabulary.clone()).await?;
+    let prompt = PromptSettings::default()
+        .with_vocabulary(vocabulary)
+        .with_language(language);
+    let enhancement =
+        claude_api_key.map(|key| EnhancementAgent::new(key).with_prompt_settings(prompt));
+
+    start_pipeline(
+        &app,
+        &state,
+        Box::new(playback),
+        provider,
+        enhancement,
+        project_id.clone(),
+        refinement_cfg,
+        vad_config,
+    )
+    .await?;
+
+    if let Err(e) = app.emit("transcription_started", serde_json::json!({
+        "file_path": file_path,
+        "project_id": project_id,
+        "duration_seconds": duration_secs
+    })) {
+        tracing::error!("Failed to emit transcription_started event: {}", e);
+    }
+
+    tracing::info!("File transcription started ({:.1}s of audio)", duration_secs);
+    Ok(())
+}
+
+/// Turn detection settings for a session: the caller's if given, otherwise the
+/// project's saved default. Checked before connecting, so a bad value fails
+/// the start rather than the stream.
+async fn resolve_streaming_config(
+    db: &Database,
+    project_id: Option<&str>,
+    streaming_config: Option<StreamingConfig>,
+) -> Result<StreamingConfig, String> {
+    let config = match (streaming_config, project_id) {
+        (Some(config), _) => config,
+        (None, Some(project_id)) => db
+            .get_project(project_id)
+            .await
+            .ok()
+            .and_then(|project| project.streaming_config)
+            .unwrap_or_default(),
+        (None, None) => StreamingConfig::default(),
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+/// Voice activity detection settings for a session, the defaults unless the
+/// caller passes their own. Checked before the source is opened.
+fn resolve_vad_config(vad_config: Option<VadConfig>) -> Result<VadConfig, String> {
+    let config = vad_config.unwrap_or_default();
+    config.validate()?;
+    Ok(config)
+}
+
+/// Key terms saved for the project, or none without a project
+async fn project_vocabulary(db: &Database, project_id: Option<&str>) -> Vec<String> {
+    match project_id {
+        Some(project_id) => db
+            .get_project(project_id)
+            .await
+            .map(|project| project.vocabulary)
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Create the selected transcription provider, AssemblyAI by default.
+/// Local models can take a while to load, so this runs off the async runtime.
+async fn create_provider(
+    config: Option<ProviderConfig>,
+    api_key: Option<String>,
+    streaming_config: StreamingConfig,
+    vocabulary: Vec<String>,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    let config = config.unwrap_or_default();
+    let provider = tokio::task::spawn_blocking(move || {
+        config.create(api_key, streaming_config, vocabulary)
+    })
+    .await
+    .map_err(|e| format!("Failed to join provider setup task: {}", e))??;
+    tracing::info!("Transcription provider: {}", provider.name());
+    Ok(provider)
+}
+
+/// Start the streaming pipeline for any [`AudioSource`].
+///
+/// Starts the source, then marks the transcription active, starts a new
+/// session and spawns the transcription stream, transcript buffering and
+/// enhancement workers fed by the source's chunks. Without an `enhancement`
+/// agent, buffers are not enhanced. Each track gets its own voice activity
+/// detector using `vad_config`.
+#[allow(clippy::too_many_arguments)]
+async fn start_pipeline<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    source: Box<dyn AudioSource>,
+    provider: Arc<dyn TranscriptionProvider>,
+    enhancement: Option<EnhancementAgent>,
+    project_id: Option<String>,
+    refinement_cfg: RefinementConfig,
+    vad_config: VadConfig,
+) -> Result<(), String> {
     // Track metrics
     state.metrics.transcription_session_started();
 
@@ -82,20 +361,28 @@ pub async fn start_transcription(
         return Err("Transcription already active".to_string());
     }
 
-    // Get the audio device and validate it's an input device
-    if device_id.starts_with("output_") {
-        return Err(
-            "Cannot use output devices (speakers) for recording. Please select an input device (microphone) instead. \
-            Note: To capture system audio on macOS, you need special loopback software like BlackHole or Soundflower."
-                .to_string(),
-        );
-    }
+    let sample_rate = source.sample_rate();
+    tracing::info!("Audio source: {} ({}Hz)", source.describe(), sample_rate);
 
-    let device = audio::get_device_by_id(&device_id)?;
+    // Multi-track sources (e.g. mic + system audio) get a stream per track
+    let track_labels = source.track_labels();
 
-    // Create channels for audio chunks and transcription results
+    // Create channels for audio chunks: source -> pause gate -> processing stage -> stream
     // Use a bounded channel with small buffer to prevent excessive buffering during stop
-    let (chunk_tx, chunk_rx) = mpsc::channel(10); // ~500ms buffer at 50ms chunks
+    let (chunk_tx, capture_rx) = mpsc::channel(10); // ~500ms buffer at 50ms chunks
+    let (gate_tx, source_rx) = mpsc::channel(10);
+
+    // Live sources never end on their own, so keep a sender that
+    // stop_transcription can drop to close the channel. Finite sources own the
+    // only sender and close the channel (ending the stream) once exhausted.
+    let stop_chunk_tx = (!source.is_finite()).then(|| chunk_tx.clone());
+
+    // Start the source on its own thread (real-time streaming) before touching
+    // any state, so a source that fails to start leaves nothing behind. Its
+    // first chunks wait in the channel until the stages below are running.
+    let mut audio_handle = source.start(chunk_tx)?;
+
+    // Create channels for transcription results
     let (transcript_tx, mut transcript_rx) = mpsc::unbounded_channel();
     let (stop_tx, mut stop_rx) = mpsc::unbounded_channel();
 
@@ -106,16 +393,21 @@ pub async fn start_transcription(
     let chunk_sender_state = state.chunk_sender.clone();
     let session_manager_transcript = state.session_manager.clone();
     let session_manager_enhanced = state.session_manager.clone();
+    let session_manager_pipeline = state.session_manager.clone();
+    let session_manager_provider = state.session_manager.clone();
 
-    // Store stop sender and chunk sender
+    // Store stop sender
     *state.stop_sender.lock().await = Some(stop_tx.clone());
-    *state.chunk_sender.lock().await = Some(chunk_tx.clone());
     *is_active = true;
     drop(is_active); // Release the lock
 
     // Track session start time
     *state.session_start_time.lock().await = Some(Instant::now());
 
+    // Sessions start unpaused; pausing silences the audio ahead of every stage
+    state.capture_paused.store(false, Ordering::Release);
+    tokio::spawn(PauseGate::new(state.capture_paused.clone()).run(capture_rx, gate_tx));
+
     // Start a new session
     state
         .session_manager
@@ -123,16 +415,77 @@ pub async fn start_transcription(
         .await;
 
     // Update current project ID
-    *state.current_project_id.lock().await = project_id.clone();
-
-    // Get device config for sample rate
-    let device_config = device
-        .default_input_config()
-        .map_err(|e| format!("Failed to get default input config: {}", e))?;
-    let sample_rate = device_config.sample_rate().0;
+    *state.current_project_id.lock().await = project_id;
+
+    // Record the session audio so passages can be re-listened to later.
+    // Failing to create the file should not prevent transcription.
+    let channels = track_labels.len().max(1) as u16;
+    let mut recorder = match SessionAudioRecorder::for_session(
+        &uuid::Uuid::new_v4().to_string(),
+        sample_rate,
+        channels,
+    ) {
+        Ok(recorder) => {
+            let audio_path = recorder.path().to_string_lossy().into_owned();
+            state
+                .session_manager
+                .update_metadata(|session| session.audio_path = Some(audio_path))
+                .await
+                .ok();
+            Some(recorder)
+        }
+        Err(e) => {
+            tracing::warn!("Session audio will not be recorded: {}", e);
+            None
+        }
+    };
+
+    // Split multi-track chunks so each track is processed and streamed on its own
+    let track_inputs: Vec<(Option<String>, mpsc::Receiver<Vec<i16>>)> = if track_labels.is_empty() {
+        vec![(None, source_rx)]
+    } else {
+        let mut splitter = TrackSplitter::new();
+        if let Some(recorder) = recorder.take() {
+            splitter = splitter.with_recorder(recorder);
+        }
+        let (outputs, inputs): (Vec<_>, Vec<_>) = track_labels
+            .iter()
+            .map(|label| {
+                let (tx, rx) = mpsc::channel(10);
+                (tx, (Some(label.clone()), rx))
+            })
+            .unzip();
+        tokio::spawn(splitter.run(source_rx, outputs));
+        inputs
+    };
+
+    // Meter input levels and skip silence before it reaches the stream
+    let mut track_streams = Vec::with_capacity(track_inputs.len());
+    for (track, (speaker, stage_input)) in track_inputs.into_iter().enumerate() {
+        let (stage_event_tx, stage_event_rx) = mpsc::unbounded_channel();
+        let vad = VoiceActivityDetector::new(vad_config.clone(), sample_rate);
+        let timeline = vad.timeline();
+        let mut stage = ChunkStage::new()
+            .with_level_meter(LevelMeter::new(LevelMeterConfig::default(), sample_rate))
+            .with_vad(vad)
+            .with_events(stage_event_tx);
+        if let Some(recorder) = recorder.take() {
+            stage = stage.with_recorder(recorder);
+        }
 
-    // Create AssemblyAI client and start chunk processing
-    let client = AssemblyAIClient::new(api_key);
+        let (stage_tx, chunk_rx) = mpsc::channel(10);
+        let stage_handle = tokio::spawn(stage.run(stage_input, stage_tx));
+        // With several tracks the first (the microphone) decides the silence ratio
+        forward_stage_events(
+            app.clone(),
+            state.session_manager.clone(),
+            stage_event_rx,
+            stage_handle,
+            speaker.clone(),
+            track == 0,
+        );
+        track_streams.push((speaker, chunk_rx, timeline));
+    }
 
     // Create channels for buffering and enhancement
     let (buffer_tx, buffer_rx) = mpsc::unbounded_channel::<TranscriptionBuffer>();
@@ -140,22 +493,63 @@ pub async fn start_transcription(
     // Spawn task to handle transcription
     let app_clone = app.clone();
     let enhancement_enabled =
-        claude_api_key.is_some() && refinement_cfg.mode != RefinementMode::Disabled;
+        enhancement.is_some() && refinement_cfg.mode != RefinementMode::Disabled;
 
     // Spawn async task for streaming processing
     tokio::spawn(async move {
-        // Start the AssemblyAI Universal Streaming
+        // Start transcribing, one stream per track.
+        // Aborting this task drops the JoinSet, which aborts every stream.
         let app_for_error = app_clone.clone();
-        let config = assemblyai::StreamingConfig::default();
         let mut processing_handle = tokio::spawn(async move {
-            if let Err(e) = client
-                .stream_audio(chunk_rx, transcript_tx, sample_rate, config)
-                .await
-            {
-                tracing::error!("Streaming error: {}", e);
-                // Emit error to frontend
-                let _ = app_for_error.emit("transcription_error", e);
+            let mut streams = tokio::task::JoinSet::new();
+            let next_turn = Arc::new(AtomicU32::new(0));
+            for (speaker, chunk_rx, timeline) in track_streams {
+                let mut turns = TrackTurns::new(next_turn.clone());
+                let provider = provider.clone();
+                let transcript_tx = transcript_tx.clone();
+                let app_for_error = app_for_error.clone();
+                let session_manager = session_manager_provider.clone();
+                streams.spawn(async move {
+                    // Tag each result with its track before it joins the others.
+                    // A separate track identifies the speaker better than the
+                    // provider's own labels. Each track skips its own silence,
+                    // so times move to the shared session timeline here too,
+                    // and its turns are numbered across the whole session.
+                    let (track_tx, mut track_rx) = mpsc::unbounded_channel::<TranscriptResult>();
+                    let (event_tx, event_rx) = mpsc::unbounded_channel();
+                    let stream = async {
+                        if let Err(e) = provider
+                            .transcribe(chunk_rx, track_tx, event_tx, sample_rate)
+                            .await
+                        {
+                            tracing::error!("Streaming error: {}", e);
+                            // Emit error to frontend
+                            let _ = app_for_error.emit("transcription_error", e);
+                        }
+                    };
+                    let label = async {
+                        while let Some(mut result) = track_rx.recv().await {
+                            to_session_time(&mut result, &timeline);
+                            turns.renumber(&mut result);
+                            if speaker.is_some() {
+                                result.speaker = speaker.clone();
+                            }
+                            if transcript_tx.send(result).is_err() {
+                                break;
+                            }
+                        }
+                    };
+                    let events = forward_provider_events(
+                        app_for_error.clone(),
+                        session_manager,
+                        event_rx,
+                        speaker.clone(),
+                    );
+                    tokio::join!(stream, label, events);
+                });
             }
+            drop(transcript_tx);
+            while streams.join_next().await.is_some() {}
         });
 
         // Handle transcript results with buffering
@@ -187,14 +581,29 @@ pub async fn start_transcription(
 
                         // Track in session manager
                         if result.is_final {
-                            if let Err(e) = session_manager_transcript
-                                .add_turn(
-                                    result.turn_order as usize,
-                                    result.text.clone(),
-                                    result.confidence as f64,
-                                )
-                                .await
-                            {
+                            let mut turn = TurnData::new(
+                                result.turn_order as usize,
+                                result.text.clone(),
+                                result.confidence as f64,
+                            )
+                            .with_speaker(result.speaker.clone())
+                            .with_language(result.language.clone());
+                            if let Some((start_ms, end_ms)) = result.span_ms() {
+                                turn = turn.with_span(start_ms, end_ms);
+                            }
+                            turn = turn.with_words(
+                                result
+                                    .words
+                                    .iter()
+                                    .map(|word| TurnWord {
+                                        text: word.text.clone(),
+                                        start_ms: word.start,
+                                        end_ms: word.end,
+                                        confidence: word.confidence as f64,
+                                    })
+                                    .collect(),
+                            );
+                            if let Err(e) = session_manager_transcript.add_turn(turn).await {
                                 tracing::error!("Failed to track turn in session: {}", e);
                             }
                         }
@@ -211,7 +620,11 @@ pub async fn start_transcription(
                                 "Adding final turn {} to buffer manager",
                                 result.turn_order
                             );
-                            buffer_manager.add_result(result.text, result.end_of_turn);
+                            let text = match result.speaker {
+                                Some(speaker) => format!("{}: {}", speaker, result.text),
+                                None => result.text,
+                            };
+                            buffer_manager.add_result(text, result.end_of_turn);
                         } else {
                             tracing::debug!(
                                 "Received partial turn {} (skipping buffer)",
@@ -243,9 +656,9 @@ pub async fn start_transcription(
 
         // Handle enhancement with parallel worker pool for improved performance
         let enhancement_handle = if enhancement_enabled {
-            if let Some(claude_key) = claude_api_key {
+            if let Some(agent) = enhancement {
                 let app_for_enhanced = app_clone.clone();
-                let agent = Arc::new(EnhancementAgent::new(claude_key));
+                let agent = Arc::new(agent);
 
                 // Configuration: Number of concurrent enhancement workers
                 // Optimized for 4 parallel requests - balances performance vs API rate limits
@@ -343,13 +756,20 @@ pub async fn start_transcription(
         };
 
         // Wait for stop signal or completion
-        tokio::select! {
+        let completed_naturally = tokio::select! {
             _ = stop_rx.recv() => {
-                tracing::info!("Stop signal received - aborting all tasks immediately");
-
-                // CRITICAL FIX: Abort processing handle IMMEDIATELY to close WebSocket
-                processing_handle.abort();
-                tracing::info!("Processing handle aborted");
+                tracing::info!("Stop signal received - stopping all tasks");
+
+                // The audio channel is already closed, so the streams are
+                // terminating. Give the provider a moment to report the session
+                // statistics, then abort to make sure the WebSocket closes.
+                if tokio::time::timeout(PROVIDER_SHUTDOWN_GRACE, &mut processing_handle)
+                    .await
+                    .is_err()
+                {
+                    processing_handle.abort();
+                    tracing::info!("Processing handle aborted");
+                }
 
                 // Abort transcript handler immediately to stop processing events
                 transcript_handle.abort();
@@ -360,6 +780,8 @@ pub async fn start_transcription(
                     handle.abort();
                     tracing::info!("Enhancement worker pool aborted");
                 }
+
+                false
             }
             _ = &mut processing_handle => {
                 tracing::info!("Streaming completed naturally");
@@ -373,11 +795,14 @@ pub async fn start_transcription(
                     handle.abort();
                     tracing::info!("Enhancement worker pool completed");
                 }
+
+                true
             }
-        }
+        };
 
         *transcription_active.lock().await = false;
-        *stop_sender_state.lock().await = None;
+        // stop_transcription takes the stop sender, so an empty slot means a user stop
+        let stopped_by_user = stop_sender_state.lock().await.take().is_none();
         *chunk_sender_state.lock().await = None;
 
         // Stop audio capture if still running
@@ -388,27 +813,241 @@ pub async fn start_transcription(
                 }
             });
         }
-    });
 
-    // Create audio capture and start it on a dedicated thread (real-time streaming)
-    let capture = audio::AudioCapture::new(device, chunk_tx)?;
-    let audio_handle = capture.start()?;
+        // The stream ended on its own (e.g. a transcribed file ran out), so nobody
+        // called stop_transcription - finalize the session and tell the frontend
+        if completed_naturally && !stopped_by_user {
+            session_manager_pipeline
+                .update_metadata(end_session_timing)
+                .await
+                .ok();
 
-    // Store the audio handle so we can stop it later
-    *state.audio_handle.lock().await = Some(audio_handle);
+            if let Err(e) = app_clone.emit("transcription_stopped", serde_json::json!({})) {
+                tracing::error!("Failed to emit transcription_stopped event: {}", e);
+            }
+        }
+    });
 
-    // Emit transcription started event
-    if let Err(e) = app.emit("transcription_started", serde_json::json!({
-        "device_id": device_id,
-        "project_id": project_id
-    })) {
-        tracing::error!("Failed to emit transcription_started event: {}", e);
+    *state.chunk_sender.lock().await = stop_chunk_tx;
+
+    // The stream and session carry on across device changes; just let the frontend
+    // know. Capture statistics feed the metrics collector.
+    if let Some(mut capture_events) = audio_handle.take_events() {
+        let app_for_capture = app.clone();
+        let metrics = state.metrics.clone();
+        tokio::spawn(async move {
+            while let Some(event) = capture_events.recv().await {
+                let name = match &event {
+                    audio::CaptureEvent::DeviceLost { .. } => "device_lost",
+                    audio::CaptureEvent::DeviceReconnected { .. } => "device_reconnected",
+                    audio::CaptureEvent::ChunksDropped { .. } => "audio_dropped",
+                    audio::CaptureEvent::Stats(stats) => {
+                        record_capture_stats(&metrics, stats);
+                        continue;
+                    }
+                };
+                if let Err(e) = app_for_capture.emit(name, event) {
+                    tracing::error!("Failed to emit {} event: {}", name, e);
+                }
+            }
+        });
     }
+    *state.audio_handle.lock().await = Some(audio_handle);
 
-    tracing::info!("Real-time transcription started successfully");
     Ok(())
 }
 
+/// Emit a stage's events to the frontend, tagged with the track's speaker label
+fn forward_stage_events<R: Runtime>(
+    app: AppHandle<R>,
+    session_manager: SessionManager,
+    mut events: mpsc::UnboundedReceiver<StageEvent>,
+    stage_handle: tokio::task::JoinHandle<StageSummary>,
+    speaker: Option<String>,
+    tracks_silence: bool,
+) {
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            let (name, mut payload) = match event {
+                StageEvent::SpeechStart { offset_secs } => (
+                    "speech_start",
+                    serde_json::json!({ "offset_seconds": offset_secs }),
+                ),
+                StageEvent::SpeechEnd {
+                    offset_secs,
+                    silence_ratio,
+                } => {
+                    if tracks_silence {
+                        session_manager
+                            .update_metadata(|session| session.metadata.silence_ratio = silence_ratio)
+                            .await
+                            .ok();
+                    }
+                    (
+                        "speech_end",
+                        serde_json::json!({
+                            "offset_seconds": offset_secs,
+                            "silence_ratio": silence_ratio
+                        }),
+                    )
+                }
+                StageEvent::Level(level) => ("audio_level", serde_json::json!(level)),
+                StageEvent::Health(health) => ("audio_health", serde_json::json!(health)),
+            };
+            if let Some(speaker) = &speaker {
+                payload["speaker"] = serde_json::json!(speaker);
+            }
+            if let Err(e) = app.emit(name, payload) {
+                tracing::error!("Failed to emit {} event: {}", name, e);
+            }
+        }
+
+        // The event channel closes when the stage finishes
+        if let Ok(summary) = stage_handle.await {
+            if let (true, Some(silence_ratio)) = (tracks_silence, summary.silence_ratio) {
+                session_manager
+                    .update_metadata(|session| session.metadata.silence_ratio = silence_ratio)
+                    .await
+                    .ok();
+            }
+        }
+    });
+}
+
+/// Move a result's word times from the stream, which skips silence, to the
+/// session timeline the recording follows
+fn to_session_time(result: &mut TranscriptResult, timeline: &StreamTimeline) {
+    for word in &mut result.words {
+        word.start = timeline.session_ms(word.start);
+        word.end = timeline.session_end_ms(word.end);
+    }
+}
+
+/// Numbers one track's turns across the session. Every track's stream counts
+/// its turns from 0, so with several tracks their turns would share numbers
+/// and run together in the live transcript.
+struct TrackTurns {
+    next: Arc<AtomicU32>,
+    /// Session number of the track's current and previous turn
+    assigned: HashMap<u32, u32>,
+}
+
+impl TrackTurns {
+    fn new(next: Arc<AtomicU32>) -> Self {
+        Self {
+            next,
+            assigned: HashMap::new(),
+        }
+    }
+
+    fn renumber(&mut self, result: &mut TranscriptResult) {
+        let turn = result.turn_order;
+        let next = &self.next;
+        let numbered = *self
+            .assigned
+            .entry(turn)
+            .or_insert_with(|| next.fetch_add(1, Ordering::Relaxed));
+        // A stream's turns arrive in order, so older ones are done
+        self.assigned.retain(|&earlier, _| earlier + 1 >= turn);
+        result.turn_order = numbered;
+    }
+}
+
+/// Warn the frontend this long before the provider ends a session
+const SESSION_EXPIRY_WARNING: Duration = Duration::from_secs(5 * 60);
+
+/// How long a stopped pipeline waits for the provider to close its sessions
+const PROVIDER_SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+
+/// Record a track's provider sessions in the session metadata, and emit
+/// `provider_session_expiring` shortly before the provider will end one.
+/// Runs until the provider stops transcribing.
+async fn forward_provider_events<R: Runtime>(
+    app: AppHandle<R>,
+    session_manager: SessionManager,
+    mut events: mpsc::UnboundedReceiver<ProviderEvent>,
+    speaker: Option<String>,
+) {
+    // Open sessions not warned about yet, with their expiry
+    let mut unwarned: Vec<(String, u64)> = Vec::new();
+
+    loop {
+        let now = unix_now();
+        let next_warning = unwarned
+            .iter()
+            .min_by_key(|(_, expires_at)| *expires_at)
+            .cloned();
+        let warn_in = next_warning.as_ref().map(|(_, expires_at)| {
+            Duration::from_secs(
+                expires_at
+                    .saturating_sub(SESSION_EXPIRY_WARNING.as_secs())
+                    .saturating_sub(now),
+            )
+        });
+
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(ProviderEvent::SessionStarted { session_id, expires_at }) => {
+                    let session =
+                        ProviderSession::new(session_id.clone(), speaker.clone(), expires_at);
+                    session_manager
+                        .update_metadata(|s| s.metadata.provider_sessions.push(session))
+                        .await
+                        .ok();
+                    unwarned.push((session_id, expires_at));
+                }
+                Some(ProviderEvent::SessionEnded {
+                    session_id,
+                    audio_duration_seconds,
+                    session_duration_seconds,
+                }) => {
+                    unwarned.retain(|(id, _)| *id != session_id);
+                    session_manager
+                        .update_metadata(|s| {
+                            s.metadata.end_provider_session(
+                                &session_id,
+                                audio_duration_seconds,
+                                session_duration_seconds,
+                            )
+                        })
+                        .await
+                        .ok();
+                }
+                None => break,
+            },
+            _ = tokio::time::sleep(warn_in.unwrap_or_default()), if warn_in.is_some() => {
+                if let Some((session_id, expires_at)) = next_warning {
+                    unwarned.retain(|(id, _)| *id != session_id);
+                    tracing::warn!("Provider session {} expires at {}", session_id, expires_at);
+                    let payload = serde_json::json!({
+                        "session_id": session_id,
+                        "expires_at": expires_at,
+                        "seconds_remaining": expires_at.saturating_sub(unix_now()),
+                        "speaker": speaker,
+                    });
+                    if let Err(e) = app.emit("provider_session_expiring", payload) {
+                        tracing::error!("Failed to emit provider_session_expiring event: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn record_capture_stats(metrics: &MetricsCollector, stats: &audio::CaptureStats) {
+    metrics.audio_frames_processed(stats.frames);
+    metrics.audio_buffer_overrun(stats.dropped_chunks as usize);
+    metrics.audio_buffer_pool_misses(stats.pool_misses);
+    metrics.audio_callbacks_timed(stats.callbacks, stats.callback_us_total, stats.callback_us_max);
+}
+
 #[tracing::instrument(skip(app, state))]
 #[tauri::command]
 pub async fn stop_transcription(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
@@ -425,9 +1064,12 @@ pub async fn stop_transcription(app: AppHandle, state: State<'_, AppState>) -> R
     let start_time = state.session_start_time.lock().await.take();
 
     // Update session duration before accessing session data
-    state.session_manager.update_metadata(|session| {
-        session.update_duration();
-    }).await.ok();
+    state
+        .session_manager
+        .update_metadata(end_session_timing)
+        .await
+        .ok();
+    state.capture_paused.store(false, Ordering::Release);
 
     let session_data = state.session_manager.get_session().await;
     let word_count = session_data.map(|s| s.metadata.word_count).unwrap_or(0);
@@ -479,6 +1121,59 @@ pub async fn stop_transcription(app: AppHandle, state: State<'_, AppState>) -> R
     Ok(())
 }
 
+/// Close a pause left open when the session ends and record its duration
+fn end_session_timing(session: &mut SessionData) {
+    if session.is_paused() {
+        session.resume().ok();
+    }
+    session.update_duration();
+}
+
+/// Stop transcribing without ending the session.
+///
+/// Audio is replaced with silence until [`resume_transcription`], so the
+/// streaming connection, turn numbering and session carry on untouched.
+#[tracing::instrument(skip(app, state))]
+#[tauri::command]
+pub async fn pause_transcription(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if !*state.transcription_active.lock().await {
+        return Err("No active transcription".to_string());
+    }
+
+    let offset_secs = state.session_manager.pause().await?;
+    state.capture_paused.store(true, Ordering::Release);
+    tracing::info!("⏸️  Transcription paused at {:.1}s", offset_secs);
+
+    if let Err(e) = app.emit(
+        "transcription_paused",
+        serde_json::json!({ "offset_secs": offset_secs }),
+    ) {
+        tracing::error!("Failed to emit transcription_paused event: {}", e);
+    }
+    Ok(())
+}
+
+/// Resume a paused session
+#[tracing::instrument(skip(app, state))]
+#[tauri::command]
+pub async fn resume_transcription(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if !*state.transcription_active.lock().await {
+        return Err("No active transcription".to_string());
+    }
+
+    let offset_secs = state.session_manager.resume().await?;
+    state.capture_paused.store(false, Ordering::Release);
+    tracing::info!("▶️  Transcription resumed at {:.1}s", offset_secs);
+
+    if let Err(e) = app.emit(
+        "transcription_resumed",
+        serde_json::json!({ "offset_secs": offset_secs }),
+    ) {
+        tracing::error!("Failed to emit transcription_resumed event: {}", e);
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_transcription_status(
     state: State<'_, AppState>,
@@ -486,38 +1181,299 @@ pub async fn get_transcription_status(
     let is_active = *state.transcription_active.lock().await;
     Ok(TranscriptionState {
         is_active,
+        is_paused: is_active && state.capture_paused.load(Ordering::Acquire),
         device_id: None, // TODO: Store and return current device_id
     })
 }
 
+/// Summarize a transcript, in `output_language` if given and otherwise in
+/// the transcript's own language
 #[tauri::command]
 pub async fn summarize_transcription(
     transcript_text: String,
     chunk_count: u32,
     claude_api_key: String,
+    output_language: Option<String>,
 ) -> Result<summary::TranscriptSummary, String> {
+    if let Some(output_language) = &output_language {
+        language::validate_output(output_language)?;
+    }
     tracing::info!(
         "Summarizing transcription: {} words, {} chunks",
         transcript_text.split_whitespace().count(),
         chunk_count
     );
 
-    let summary_service = summary::SummaryService::new(claude_api_key);
+    let summary_service = summary::SummaryService::new(claude_api_key)
+        .with_prompt_settings(PromptSettings::default().with_output_language(output_language));
     summary_service
         .summarize(transcript_text, chunk_count)
         .await
 }
 
+/// Refine a full transcript, spelling the project's key terms as saved when
+/// `project_id` is given. `language` is the spoken language code, if known.
 #[tauri::command]
 pub async fn refine_transcript(
+    db: State<'_, Database>,
     transcript_text: String,
     claude_api_key: String,
+    project_id: Option<String>,
+    language: Option<String>,
 ) -> Result<crate::transcription::refinement::RefinedTranscript, String> {
     tracing::info!(
         "Refining full transcript: {} words",
         transcript_text.split_whitespace().count()
     );
 
-    let refinement_agent = RefinementAgent::new(claude_api_key);
+    let vocabulary = project_vocabulary(&db, project_id.as_deref()).await;
+    let refinement_agent = RefinementAgent::new(claude_api_key).with_prompt_settings(
+        PromptSettings::default()
+            .with_vocabulary(vocabulary)
+            .with_language(language),
+    );
     refinement_agent.refine(transcript_text).await
 }
+
+// Needs Tauri's mock runtime: run with `--features test-runtime`
+#[cfg(all(test, feature = "test-runtime"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_apis::assemblyai::*;
+    use crate::transcription::assemblyai::AssemblyAIClient;
+    use crate::transcription::source::ToneSource;
+    use std::time::Duration;
+
+    /// A source whose device can't be opened
+    struct FailingSource;
+
+    impl AudioSource for FailingSource {
+        fn sample_rate(&self) -> u32 {
+            16000
+        }
+
+        fn describe(&self) -> String {
+            "failing source".to_string()
+        }
+
+        fn start(
+            self: Box<Self>,
+            _chunk_sender: mpsc::Sender<Vec<i16>>,
+        ) -> Result<audio::AudioCaptureHandle, String> {
+            Err("device unavailable".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_source_start_leaves_transcription_inactive() {
+        let app = tauri::test::mock_app();
+        let state = AppState::default();
+        for _ in 0..2 {
+            let provider = Arc::new(AssemblyAIClient::new("test-api-key".to_string()));
+            let result = start_pipeline(
+                app.handle(),
+                &state,
+                Box::new(FailingSource),
+                provider,
+                None,
+                None,
+                RefinementConfig::default(),
+                VadConfig::default(),
+            )
+            .await;
+            // The second attempt fails for the same reason, not as "already active"
+            assert_eq!(result, Err("device unavailable".to_string()));
+        }
+        assert!(!*state.transcription_active.lock().await);
+        assert!(state.session_manager.get_session().await.is_none());
+        assert!(state.stop_sender.lock().await.is_none());
+    }
+
+    /// A tone delivered as two interleaved tracks
+    struct TwoTrackTone(ToneSource);
+
+    impl AudioSource for TwoTrackTone {
+        fn sample_rate(&self) -> u32 {
+            self.0.sample_rate()
+        }
+
+        fn describe(&self) -> String {
+            "two track tone".to_string()
+        }
+
+        fn track_labels(&self) -> Vec<String> {
+            vec!["me".to_string(), "them".to_string()]
+        }
+
+        fn start(
+            self: Box<Self>,
+            chunk_sender: mpsc::Sender<Vec<i16>>,
+        ) -> Result<audio::AudioCaptureHandle, String> {
+            Box::new(self.0).start(chunk_sender)
+        }
+    }
+
+    /// Sends the same two turns, numbered from 0, on every stream
+    struct TwoTurnProvider;
+
+    #[async_trait::async_trait]
+    impl TranscriptionProvider for TwoTurnProvider {
+        fn name(&self) -> &'static str {
+            "two turns"
+        }
+
+        async fn transcribe(
+            &self,
+            mut audio_receiver: mpsc::Receiver<Vec<i16>>,
+            transcript_sender: mpsc::UnboundedSender<TranscriptResult>,
+            _events: mpsc::UnboundedSender<ProviderEvent>,
+            _sample_rate: u32,
+        ) -> Result<(), String> {
+            for (turn_order, text, is_final) in [
+                (0, "hello", false),
+                (0, "hello there", true),
+                (1, "bye", true),
+            ] {
+                let _ = transcript_sender.send(TranscriptResult {
+                    text: text.to_string(),
+                    confidence: 0.9,
+                    is_final,
+                    turn_order,
+                    end_of_turn: is_final,
+                    words: Vec::new(),
+                    speaker: None,
+                    language: None,
+                });
+            }
+            while audio_receiver.recv().await.is_some() {}
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tracks_keep_their_turns_apart() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let live = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received = live.clone();
+        app.listen_any("transcript", move |event| {
+            let result: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            received.lock().unwrap().push((
+                result["turn_order"].as_u64().unwrap() as u32,
+                result["speaker"].as_str().unwrap().to_string(),
+                result["text"].as_str().unwrap().to_string(),
+            ));
+        });
+
+        let state = AppState::default();
+        let source = TwoTrackTone(ToneSource::new(16000).with_duration(Duration::from_millis(500)));
+        start_pipeline(
+            app.handle(),
+            &state,
+            Box::new(source),
+            Arc::new(TwoTurnProvider),
+            None,
+            None,
+            RefinementConfig::default(),
+            VadConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..100 {
+            if !*state.transcription_active.lock().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let session = state.session_manager.get_session().await.unwrap();
+        if let Some(path) = &session.audio_path {
+            std::fs::remove_file(path).ok();
+        }
+
+        // Every live turn number belongs to one speaker, and a partial and its
+        // final share theirs
+        let live = live.lock().unwrap().clone();
+        assert_eq!(live.len(), 6);
+        let mut turns: HashMap<u32, (String, Vec<String>)> = HashMap::new();
+        for (turn_order, speaker, text) in live {
+            let (owner, texts) = turns
+                .entry(turn_order)
+                .or_insert_with(|| (speaker.clone(), Vec::new()));
+            assert_eq!(*owner, speaker, "turn {} has two speakers", turn_order);
+            texts.push(text);
+        }
+        assert_eq!(turns.len(), 4);
+        assert_eq!(
+            turns.values().filter(|(_, texts)| texts.len() == 2).count(),
+            2
+        );
+
+        let mut orders: Vec<_> = session.turns.iter().map(|t| t.turn_order).collect();
+        orders.sort();
+        orders.dedup();
+        assert_eq!(orders.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_transcribes_against_mock_server() {
+        let server = MockServer::start(
+            "test-api-key",
+            vec![vec![
+                MockStep::Send(create_begin_message("session-1")),
+                MockStep::Send(create_turn_message(0, "hello", false)),
+                MockStep::Send(create_turn_message(0, "hello world", true)),
+                MockStep::Send(create_turn_message(1, "second turn", true)),
+            ]],
+        )
+        .await;
+        let provider = Arc::new(
+            AssemblyAIClient::new("test-api-key".to_string()).with_base_url(server.base_url()),
+        );
+
+        let app = tauri::test::mock_app();
+        let state = AppState::default();
+        let source = ToneSource::new(16000).with_duration(Duration::from_secs(1));
+        start_pipeline(
+            app.handle(),
+            &state,
+            Box::new(source),
+            provider,
+            None,
+            None,
+            RefinementConfig::default(),
+            VadConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        // The tone runs out after a second and the pipeline winds down on its own
+        let mut finished = false;
+        for _ in 0..100 {
+            if !*state.transcription_active.lock().await {
+                finished = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(finished, "pipeline did not finish");
+
+        let session = state.session_manager.get_session().await.unwrap();
+        if let Some(path) = &session.audio_path {
+            std::fs::remove_file(path).ok();
+        }
+        let texts: Vec<_> = session.turns.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["hello world", "second turn"]);
+        // Word timings are kept for saving with the recording
+        assert_eq!(session.recording_words().len(), 2);
+        // The provider's session id is kept for reconciling with its records
+        let provider_sessions = &session.metadata.provider_sessions;
+        assert_eq!(provider_sessions.len(), 1);
+        assert_eq!(provider_sessions[0].id, "session-1");
+        assert_eq!(provider_sessions[0].audio_duration_seconds, Some(10.5));
+
+        assert!(server.wait_for(|c| c.len() == 1 && c[0].terminated).await);
+        assert_eq!(server.connections()[0].query["sample_rate"], "16000");
+    }
+}
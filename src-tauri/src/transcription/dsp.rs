@@ -0,0 +1,523 @@
+//! # Audio DSP Chain
+//!
+//! Conditioning applied to captured audio after resampling and before PCM
+//! conversion, so quiet laptop microphones reach the streaming service at a
+//! usable level:
+//!
+//! 1. **High-pass**: removes DC offset and low-frequency rumble (fans, desk bumps)
+//! 2. **Noise gate**: lowers background noise between words
+//! 3. **AGC**: slowly normalises the speech level towards a target
+//! 4. **Soft limiter**: keeps peaks raised by the AGC from clipping
+//!
+//! Every stage can be enabled separately. Capture applies none of them unless a
+//! config is passed in, so the input level meter shows what the device
+//! actually delivers; [`DspConfig::default`] is the recommended set to opt
+//! into. Processing is per sample on mono f32
+//! audio in the range -1.0..=1.0 and allocates nothing, so it can run inside
+//! the audio callback.
+
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// How quickly the gate opens once the signal rises above the threshold
+const GATE_ATTACK_MS: f32 = 2.0;
+/// How quickly the gate closes after the hold time
+const GATE_RELEASE_MS: f32 = 150.0;
+/// Window of the level estimate the AGC follows
+const AGC_LEVEL_WINDOW_MS: f32 = 300.0;
+/// How quickly the AGC backs off when the level jumps
+const AGC_ATTACK_MS: f32 = 50.0;
+/// How quickly the AGC raises the gain for quieter speech
+const AGC_RELEASE_MS: f32 = 2000.0;
+
+/// High-pass filter settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HighPassConfig {
+    pub enabled: bool,
+    /// -3dB point; speech has little energy below ~100Hz
+    pub cutoff_hz: f32,
+}
+
+impl Default for HighPassConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cutoff_hz: 80.0,
+        }
+    }
+}
+
+/// Noise gate settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NoiseGateConfig {
+    pub enabled: bool,
+    /// The gate opens when the signal envelope rises above this (dBFS)
+    pub threshold_db: f32,
+    /// How far gated audio is lowered (dB, negative)
+    pub attenuation_db: f32,
+    /// How long the gate stays open after the signal drops, so word endings survive
+    pub hold_ms: u32,
+}
+
+impl Default for NoiseGateConfig {
+    fn default() -> Self {
+        Self {
+            // Off by default: the VAD already thins out silence
+            enabled: false,
+            threshold_db: -50.0,
+            attenuation_db: -20.0,
+            hold_ms: 200,
+        }
+    }
+}
+
+/// Automatic gain control settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AgcConfig {
+    pub enabled: bool,
+    /// RMS level (dBFS) speech is normalised to
+    pub target_db: f32,
+    /// Upper bound on the applied gain (dB)
+    pub max_gain_db: f32,
+    /// Below this level (dBFS) the input is treated as background and the gain
+    /// is held, so silence and hiss are not amplified
+    pub noise_floor_db: f32,
+}
+
+impl Default for AgcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target_db: -20.0,
+            max_gain_db: 24.0,
+            noise_floor_db: -55.0,
+        }
+    }
+}
+
+/// Soft limiter settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LimiterConfig {
+    pub enabled: bool,
+    /// Level (dBFS) above which peaks are progressively compressed
+    pub threshold_db: f32,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_db: -3.0,
+        }
+    }
+}
+
+/// Settings for the whole chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DspConfig {
+    pub high_pass: HighPassConfig,
+    pub noise_gate: NoiseGateConfig,
+    pub agc: AgcConfig,
+    pub limiter: LimiterConfig,
+}
+
+impl DspConfig {
+    /// Every stage disabled - audio passes through unchanged
+    pub fn bypass() -> Self {
+        Self {
+            high_pass: HighPassConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            noise_gate: NoiseGateConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            agc: AgcConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            limiter: LimiterConfig {
+                enabled: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+fn db_to_amplitude(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Coefficient of a one-pole smoother with the given time constant
+fn smoothing_coefficient(time_ms: f32, sample_rate: u32) -> f32 {
+    let samples = time_ms * sample_rate as f32 / 1000.0;
+    if samples <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / samples).exp()
+    }
+}
+
+/// Second-order Butterworth high-pass (RBJ cookbook biquad)
+struct HighPass {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPass {
+    fn new(cutoff_hz: f32, sample_rate: u32) -> Self {
+        // Keep the cutoff below Nyquist so the filter stays stable
+        let cutoff = cutoff_hz.clamp(1.0, sample_rate as f32 * 0.45);
+        let w0 = 2.0 * PI * cutoff / sample_rate as f32;
+        let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+        let cos_w0 = w0.cos();
+        let a0 = 1.0 + alpha;
+
+        Self {
+            b0: (1.0 + cos_w0) / 2.0 / a0,
+            b1: -(1.0 + cos_w0) / a0,
+            b2: (1.0 + cos_w0) / 2.0 / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Downward expander with hold: follows the peak envelope and lowers the
+/// signal by a fixed amount while it stays below the threshold
+struct NoiseGate {
+    threshold: f32,
+    closed_gain: f32,
+    hold_samples: u32,
+    attack: f32,
+    release: f32,
+    envelope_release: f32,
+    envelope: f32,
+    gain: f32,
+    held: u32,
+}
+
+impl NoiseGate {
+    fn new(config: &NoiseGateConfig, sample_rate: u32) -> Self {
+        Self {
+            threshold: db_to_amplitude(config.threshold_db),
+            closed_gain: db_to_amplitude(config.attenuation_db.min(0.0)),
+            hold_samples: config.hold_ms * sample_rate / 1000,
+            attack: smoothing_coefficient(GATE_ATTACK_MS, sample_rate),
+            release: smoothing_coefficient(GATE_RELEASE_MS, sample_rate),
+            envelope_release: smoothing_coefficient(GATE_RELEASE_MS / 3.0, sample_rate),
+            envelope: 0.0,
+            gain: 1.0,
+            held: 0,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        // Peak envelope: instant attack, smooth decay
+        let level = x.abs();
+        self.envelope = if level > self.envelope {
+            level
+        } else {
+            level + self.envelope_release * (self.envelope - level)
+        };
+
+        let target = if self.envelope >= self.threshold {
+            self.held = self.hold_samples;
+            1.0
+        } else if self.held > 0 {
+            self.held -= 1;
+            1.0
+        } else {
+            self.closed_gain
+        };
+
+        let coefficient = if target > self.gain {
+            self.attack
+        } else {
+            self.release
+        };
+        self.gain = target + coefficient * (self.gain - target);
+        x * self.gain
+    }
+}
+
+/// Slow RMS-following gain control. Gain drops quickly when the level jumps
+/// and rises slowly, and is held while the input is at background level.
+struct Agc {
+    target: f32,
+    max_gain: f32,
+    noise_floor_power: f32,
+    level_coefficient: f32,
+    attack: f32,
+    release: f32,
+    power: f32,
+    gain: f32,
+}
+
+impl Agc {
+    fn new(config: &AgcConfig, sample_rate: u32) -> Self {
+        let noise_floor = db_to_amplitude(config.noise_floor_db);
+        Self {
+            target: db_to_amplitude(config.target_db),
+            max_gain: db_to_amplitude(config.max_gain_db.max(0.0)),
+            noise_floor_power: noise_floor * noise_floor,
+            level_coefficient: smoothing_coefficient(AGC_LEVEL_WINDOW_MS, sample_rate),
+            attack: smoothing_coefficient(AGC_ATTACK_MS, sample_rate),
+            release: smoothing_coefficient(AGC_RELEASE_MS, sample_rate),
+            power: 0.0,
+            gain: 1.0,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        self.power = x * x + self.level_coefficient * (self.power - x * x);
+
+        if self.power > self.noise_floor_power {
+            let desired = (self.target / self.power.sqrt()).min(self.max_gain);
+            let coefficient = if desired < self.gain {
+                self.attack
+            } else {
+                self.release
+            };
+            self.gain = desired + coefficient * (self.gain - desired);
+        }
+        x * self.gain
+    }
+}
+
+/// Stateless soft-knee limiter: linear up to the threshold, then a tanh curve
+/// that approaches full scale without exceeding it
+struct SoftLimiter {
+    threshold: f32,
+}
+
+impl SoftLimiter {
+    fn new(config: &LimiterConfig) -> Self {
+        Self {
+            threshold: db_to_amplitude(config.threshold_db).clamp(0.1, 0.99),
+        }
+    }
+
+    fn process(&self, x: f32) -> f32 {
+        let level = x.abs();
+        if level <= self.threshold {
+            return x;
+        }
+        let headroom = 1.0 - self.threshold;
+        let limited = self.threshold + headroom * ((level - self.threshold) / headroom).tanh();
+        limited.copysign(x)
+    }
+}
+
+/// Configured chain of processing stages for one mono signal
+pub struct DspChain {
+    high_pass: Option<HighPass>,
+    noise_gate: Option<NoiseGate>,
+    agc: Option<Agc>,
+    limiter: Option<SoftLimiter>,
+}
+
+impl DspChain {
+    pub fn new(config: &DspConfig, sample_rate: u32) -> Self {
+        Self {
+            high_pass: config
+                .high_pass
+                .enabled
+                .then(|| HighPass::new(config.high_pass.cutoff_hz, sample_rate)),
+            noise_gate: config
+                .noise_gate
+                .enabled
+                .then(|| NoiseGate::new(&config.noise_gate, sample_rate)),
+            agc: config
+                .agc
+                .enabled
+                .then(|| Agc::new(&config.agc, sample_rate)),
+            limiter: config
+                .limiter
+                .enabled
+                .then(|| SoftLimiter::new(&config.limiter)),
+        }
+    }
+
+    /// Whether every stage is disabled
+    pub fn is_bypassed(&self) -> bool {
+        self.high_pass.is_none()
+            && self.noise_gate.is_none()
+            && self.agc.is_none()
+            && self.limiter.is_none()
+    }
+
+    /// Process samples in place
+    pub fn process(&mut self, samples: &mut [f32]) {
+        if self.is_bypassed() {
+            return;
+        }
+
+        for sample in samples.iter_mut() {
+            let mut x = *sample;
+            if let Some(high_pass) = self.high_pass.as_mut() {
+                x = high_pass.process(x);
+            }
+            if let Some(gate) = self.noise_gate.as_mut() {
+                x = gate.process(x);
+            }
+            if let Some(agc) = self.agc.as_mut() {
+                x = agc.process(x);
+            }
+            if let Some(limiter) = &self.limiter {
+                x = limiter.process(x);
+            }
+            *sample = x;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn only(configure: impl FnOnce(&mut DspConfig)) -> DspChain {
+        let mut config = DspConfig::bypass();
+        configure(&mut config);
+        DspChain::new(&config, RATE)
+    }
+
+    fn sine(frequency_hz: f32, amplitude: f32, secs: f32) -> Vec<f32> {
+        (0..(secs * RATE as f32) as usize)
+            .map(|i| amplitude * (2.0 * PI * frequency_hz * i as f32 / RATE as f32).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_bypass_leaves_audio_untouched() {
+        let mut chain = DspChain::new(&DspConfig::bypass(), RATE);
+        assert!(chain.is_bypassed());
+
+        let input = sine(440.0, 0.3, 0.1);
+        let mut output = input.clone();
+        chain.process(&mut output);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_high_pass_removes_dc_and_keeps_speech() {
+        let mut chain = only(|c| c.high_pass.enabled = true);
+
+        let mut dc = vec![0.5; RATE as usize];
+        chain.process(&mut dc);
+        assert!(rms(&dc[8000..]) < 0.001);
+
+        let mut voice = sine(1000.0, 0.5, 1.0);
+        chain.process(&mut voice);
+        assert!((rms(&voice[8000..]) - 0.5 / 2f32.sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_noise_gate_lowers_background_only() {
+        let mut chain = only(|c| c.noise_gate.enabled = true);
+
+        // -60dBFS hiss is lowered by the 20dB attenuation
+        let mut hiss = sine(3000.0, 0.001, 1.0);
+        chain.process(&mut hiss);
+        assert!(rms(&hiss[8000..]) < 0.001 / 2f32.sqrt() * 0.11);
+
+        let mut voice = sine(300.0, 0.2, 1.0);
+        chain.process(&mut voice);
+        assert!((rms(&voice[8000..]) - 0.2 / 2f32.sqrt()).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_agc_raises_quiet_speech_to_target() {
+        let mut chain = only(|c| c.agc.enabled = true);
+
+        // -40dBFS RMS speech should end up near the -20dBFS target
+        let amplitude = db_to_amplitude(-40.0) * 2f32.sqrt();
+        let mut voice = sine(300.0, amplitude, 15.0);
+        chain.process(&mut voice);
+
+        let level_db = 20.0 * rms(&voice[voice.len() - 16000..]).log10();
+        assert!((level_db - -20.0).abs() < 1.0, "got {}dB", level_db);
+    }
+
+    #[test]
+    fn test_agc_gain_is_capped_and_ignores_silence() {
+        let mut chain = only(|c| c.agc.enabled = true);
+
+        // Needs 30dB of gain, allowed 24dB
+        let amplitude = db_to_amplitude(-50.0) * 2f32.sqrt();
+        let mut faint = sine(300.0, amplitude, 20.0);
+        chain.process(&mut faint);
+        let level_db = 20.0 * rms(&faint[faint.len() - 16000..]).log10();
+        assert!(
+            (level_db - (-50.0 + 24.0)).abs() < 0.5,
+            "got {}dB",
+            level_db
+        );
+
+        let mut silence = vec![0.0; RATE as usize * 5];
+        chain.process(&mut silence);
+        assert!(silence.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_limiter_is_transparent_below_threshold_and_never_clips() {
+        let limiter = SoftLimiter::new(&LimiterConfig::default());
+
+        assert_eq!(limiter.process(0.5), 0.5);
+        assert_eq!(limiter.process(-0.5), -0.5);
+
+        for x in [0.8f32, 1.0, 2.0, 10.0] {
+            let y = limiter.process(x);
+            assert!(y > 0.7 && y <= 1.0, "{} -> {}", x, y);
+            assert_eq!(limiter.process(-x), -y);
+        }
+        assert!(limiter.process(1.0) > limiter.process(0.8));
+    }
+
+    #[test]
+    fn test_config_accepts_partial_settings() {
+        let config: DspConfig =
+            serde_json::from_str(r#"{"noise_gate": {"enabled": true}, "agc": {"target_db": -18}}"#)
+                .unwrap();
+
+        assert!(config.noise_gate.enabled);
+        assert_eq!(config.noise_gate.threshold_db, -50.0);
+        assert!(config.agc.enabled);
+        assert_eq!(config.agc.target_db, -18.0);
+        assert!(config.high_pass.enabled);
+    }
+}
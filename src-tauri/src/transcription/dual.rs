@@ -0,0 +1,418 @@
+//! # Dual Capture
+//!
+//! Captures a microphone and the system's audio output at the same time, so the
+//! local speaker ("me") and the remote side of a call ("them") are both
+//! transcribed. On Linux the output is captured through its PulseAudio/PipeWire
+//! monitor, which is listed as an ordinary input device; hosts that support
+//! loopback capture (WASAPI) can capture an output device directly.
+//!
+//! The two devices run on independent clocks, so their chunks are lined up by
+//! a [`TrackAligner`] before being either mixed into one mono track or
+//! interleaved as two labelled tracks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use tokio::sync::mpsc;
+
+use super::audio::{AudioCapture, AudioCaptureHandle};
+use super::source::{chunk_size_for, AudioSource};
+
+/// Track label for the microphone
+pub const MIC_TRACK_LABEL: &str = "me";
+
+/// Track label for the system audio
+pub const LOOPBACK_TRACK_LABEL: &str = "them";
+
+/// How far (in chunks) one device may run ahead before the other is padded
+/// with silence. Covers loopback devices that only deliver audio while
+/// something is playing, and slow clock drift between the devices.
+const MAX_TRACK_LAG_CHUNKS: usize = 4;
+
+/// Buffer between each device and the aligner
+const INPUT_CHANNEL_CHUNKS: usize = 10;
+
+/// Most padded chunks per track whose late audio is discarded if it turns up.
+/// A device can't deliver more late audio than fits its input channel; beyond
+/// that the track was idle rather than late.
+const MAX_LATE_CHUNKS: usize = INPUT_CHANNEL_CHUNKS;
+
+/// How the two captured signals are streamed
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DualCaptureMode {
+    /// Sum both into a single mono track
+    Mixed,
+    /// Keep them as separate tracks, transcribed and labelled independently
+    #[default]
+    Tracks,
+}
+
+/// Lines up chunks from inputs that are clocked independently.
+pub(crate) struct TrackAligner {
+    queues: Vec<VecDeque<Vec<i16>>>,
+    /// Silent chunks padded into each track whose real audio may still arrive
+    padded: Vec<usize>,
+    chunk_size: usize,
+    max_lag: usize,
+}
+
+impl TrackAligner {
+    pub(crate) fn new(tracks: usize, chunk_size: usize, max_lag: usize) -> Self {
+        Self {
+            queues: (0..tracks).map(|_| VecDeque::new()).collect(),
+            padded: vec![0; tracks],
+            chunk_size,
+            max_lag,
+        }
+    }
+
+    pub(crate) fn push(&mut self, track: usize, chunk: Vec<i16>) {
+        if let Some(queue) = self.queues.get_mut(track) {
+            queue.push_back(chunk);
+        }
+    }
+
+    /// One chunk per track, once every track has one. A track that falls more
+    /// than `max_lag` chunks behind another is padded with silence.
+    pub(crate) fn pop(&mut self) -> Option<Vec<Vec<i16>>> {
+        self.discard_late_chunks();
+        let all_ready = self.queues.iter().all(|q| !q.is_empty());
+        let lagging = self.queues.iter().any(|q| q.len() > self.max_lag);
+        (all_ready || lagging).then(|| self.take_frame())
+    }
+
+    /// Whatever is left once the inputs have closed, padded with silence
+    pub(crate) fn drain(&mut self) -> Vec<Vec<Vec<i16>>> {
+        let mut frames = Vec::new();
+        while self.queues.iter().any(|q| !q.is_empty()) {
+            frames.push(self.take_frame());
+        }
+        frames
+    }
+
+    /// A padded track whose late chunks then arrive ends up ahead of the
+    /// others. Its audio for that time already went out as silence, so drop
+    /// chunks until it is back in line instead of leaving it shifted for good.
+    fn discard_late_chunks(&mut self) {
+        for track in 0..self.queues.len() {
+            let others = self
+                .queues
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != track)
+                .map(|(_, q)| q.len())
+                .min()
+                .unwrap_or(0);
+            while self.padded[track] > 0 && self.queues[track].len() > others {
+                self.queues[track].pop_front();
+                self.padded[track] -= 1;
+            }
+        }
+    }
+
+    fn take_frame(&mut self) -> Vec<Vec<i16>> {
+        let chunk_size = self.chunk_size;
+        self.queues
+            .iter_mut()
+            .zip(&mut self.padded)
+            .map(|(q, padded)| {
+                q.pop_front().unwrap_or_else(|| {
+                    *padded = (*padded + 1).min(MAX_LATE_CHUNKS);
+                    vec![0; chunk_size]
+                })
+            })
+            .collect()
+    }
+}
+
+/// Sum tracks sample by sample, saturating at full scale
+fn mix(tracks: &[Vec<i16>]) -> Vec<i16> {
+    let len = tracks.iter().map(Vec::len).max().unwrap_or(0);
+    (0..len)
+        .map(|i| {
+            let sum: i32 = tracks
+                .iter()
+                .map(|t| t.get(i).copied().unwrap_or(0) as i32)
+                .sum();
+            sum.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        })
+        .collect()
+}
+
+/// Interleave tracks into multi-track frames
+fn interleave(tracks: &[Vec<i16>]) -> Vec<i16> {
+    let len = tracks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut frames = Vec::with_capacity(len * tracks.len());
+    for i in 0..len {
+        frames.extend(tracks.iter().map(|t| t.get(i).copied().unwrap_or(0)));
+    }
+    frames
+}
+
+/// Microphone and system audio captured together
+pub struct DualCapture {
+    mic: AudioCapture,
+    loopback: AudioCapture,
+    mode: DualCaptureMode,
+}
+
+impl DualCapture {
+    pub fn new(
+        mic: AudioCapture,
+        loopback: AudioCapture,
+        mode: DualCaptureMode,
+    ) -> Result<Self, String> {
+        if mic.sample_rate() != loopback.sample_rate() {
+            return Err(format!(
+                "Microphone and loopback must stream at the same rate ({}Hz vs {}Hz)",
+                mic.sample_rate(),
+                loopback.sample_rate()
+            ));
+        }
+
+        Ok(Self {
+            mic,
+            loopback,
+            mode,
+        })
+    }
+}
+
+impl AudioSource for DualCapture {
+    fn sample_rate(&self) -> u32 {
+        self.mic.sample_rate()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} + {} ({})",
+            AudioSource::describe(&self.mic),
+            AudioSource::describe(&self.loopback),
+            match self.mode {
+                DualCaptureMode::Mixed => "mixed",
+                DualCaptureMode::Tracks => "separate tracks",
+            }
+        )
+    }
+
+    fn track_labels(&self) -> Vec<String> {
+        match self.mode {
+            DualCaptureMode::Mixed => Vec::new(),
+            DualCaptureMode::Tracks => {
+                vec![
+                    MIC_TRACK_LABEL.to_string(),
+                    LOOPBACK_TRACK_LABEL.to_string(),
+                ]
+            }
+        }
+    }
+
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String> {
+        // The aligner thread waits on both captures' async channels
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| "Dual capture must be started from the async runtime".to_string())?;
+
+        let chunk_size = chunk_size_for(self.mic.sample_rate());
+        let mode = self.mode;
+
+        let (mic_tx, mut mic_rx) = mpsc::channel(INPUT_CHANNEL_CHUNKS);
+        let (loopback_tx, mut loopback_rx) = mpsc::channel(INPUT_CHANNEL_CHUNKS);
+        let mut mic_handle = self.mic.start(mic_tx)?;
+        let mut loopback_handle = match self.loopback.start(loopback_tx) {
+            Ok(handle) => handle,
+            Err(e) => {
+                let _ = mic_handle.stop();
+                return Err(e);
+            }
+        };
+
+        // Forward both captures' device events and statistics
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let mut mic_events = mic_handle.take_events();
+        let mut loopback_events = loopback_handle.take_events();
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let thread_stop_flag = stop_flag.clone();
+
+        let thread_handle = thread::spawn(move || {
+            runtime.block_on(async move {
+                let mut aligner = TrackAligner::new(2, chunk_size, MAX_TRACK_LAG_CHUNKS);
+                let (mut mic_open, mut loopback_open) = (true, true);
+
+                while mic_open || loopback_open {
+                    tokio::select! {
+                        chunk = mic_rx.recv(), if mic_open => match chunk {
+                            Some(chunk) => aligner.push(0, chunk),
+                            None => mic_open = false,
+                        },
+                        chunk = loopback_rx.recv(), if loopback_open => match chunk {
+                            Some(chunk) => aligner.push(1, chunk),
+                            None => loopback_open = false,
+                        },
+                        Some(event) = recv_event(&mut mic_events) => {
+                            let _ = event_tx.send(event);
+                        }
+                        Some(event) = recv_event(&mut loopback_events) => {
+                            let _ = event_tx.send(event);
+                        }
+                    }
+
+                    // One device ending early (e.g. it failed to start) leaves
+                    // the other track running with silence in its place
+                    let mut frames = Vec::new();
+                    while let Some(frame) = aligner.pop() {
+                        frames.push(frame);
+                    }
+                    if !mic_open && !loopback_open {
+                        frames.extend(aligner.drain());
+                    }
+
+                    for frame in frames {
+                        if thread_stop_flag.load(Ordering::Acquire) {
+                            return;
+                        }
+                        let chunk = match mode {
+                            DualCaptureMode::Mixed => mix(&frame),
+                            DualCaptureMode::Tracks => interleave(&frame),
+                        };
+                        if chunk_sender.send(chunk).await.is_err() {
+                            tracing::info!("Chunk receiver closed - stopping dual capture");
+                            return;
+                        }
+                    }
+                }
+            });
+        });
+
+        Ok(AudioCaptureHandle::combining(
+            thread_handle,
+            stop_flag,
+            vec![mic_handle, loopback_handle],
+            event_rx,
+        ))
+    }
+}
+
+/// Next event from an optional receiver; pending forever once it is gone
+async fn recv_event<T>(events: &mut Option<mpsc::UnboundedReceiver<T>>) -> Option<T> {
+    match events {
+        Some(rx) => match rx.recv().await {
+            Some(event) => Some(event),
+            None => {
+                *events = None;
+                std::future::pending().await
+            }
+        },
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligner_waits_for_every_track() {
+        let mut aligner = TrackAligner::new(2, 4, 4);
+        aligner.push(0, vec![1; 4]);
+        assert!(aligner.pop().is_none());
+
+        aligner.push(1, vec![2; 4]);
+        assert_eq!(aligner.pop(), Some(vec![vec![1; 4], vec![2; 4]]));
+        assert!(aligner.pop().is_none());
+    }
+
+    #[test]
+    fn test_aligner_pads_a_lagging_track() {
+        let mut aligner = TrackAligner::new(2, 4, 2);
+        for i in 0..3 {
+            aligner.push(1, vec![i; 4]);
+        }
+
+        // Track 1 is three chunks ahead, more than the allowed two
+        assert_eq!(aligner.pop(), Some(vec![vec![0; 4], vec![0; 4]]));
+        assert!(aligner.pop().is_none());
+
+        assert_eq!(
+            aligner.drain(),
+            vec![vec![vec![0; 4], vec![1; 4]], vec![vec![0; 4], vec![2; 4]],]
+        );
+    }
+
+    #[test]
+    fn test_aligner_discards_late_chunks_after_padding() {
+        let mut aligner = TrackAligner::new(2, 1, 2);
+        let mut frames = Vec::new();
+
+        // Track 1 stalls; track 0 runs three chunks ahead and it is padded once
+        for t in 0..3 {
+            aligner.push(0, vec![t]);
+        }
+        frames.extend(aligner.pop());
+        assert_eq!(frames, vec![vec![vec![0], vec![0]]]);
+
+        // Track 1's audio for that time turns up late, then both run in step
+        for t in 0..3 {
+            aligner.push(1, vec![t]);
+            while let Some(frame) = aligner.pop() {
+                frames.push(frame);
+            }
+        }
+        for t in 3..6 {
+            aligner.push(0, vec![t]);
+            aligner.push(1, vec![t]);
+            while let Some(frame) = aligner.pop() {
+                frames.push(frame);
+            }
+        }
+
+        // Back in line rather than a chunk behind for the rest of the capture
+        assert_eq!(
+            &frames[frames.len() - 3..],
+            &[
+                vec![vec![3], vec![3]],
+                vec![vec![4], vec![4]],
+                vec![vec![5], vec![5]]
+            ]
+        );
+        assert!(aligner.drain().is_empty());
+    }
+
+    #[test]
+    fn test_aligner_keeps_audio_from_a_slow_track() {
+        let mut aligner = TrackAligner::new(2, 1, 2);
+        // Track 1's clock runs slow: it is padded, but never gets ahead after
+        for t in 0..3 {
+            aligner.push(0, vec![t]);
+        }
+        assert_eq!(aligner.pop(), Some(vec![vec![0], vec![0]]));
+
+        for t in 3..6 {
+            aligner.push(0, vec![t]);
+            aligner.push(1, vec![t + 10]);
+            assert_eq!(aligner.pop(), Some(vec![vec![t - 2], vec![t + 10]]));
+        }
+    }
+
+    #[test]
+    fn test_mix_saturates() {
+        assert_eq!(
+            mix(&[vec![100, 30000, -30000], vec![-50, 10000, -10000]]),
+            vec![50, i16::MAX, i16::MIN]
+        );
+    }
+
+    #[test]
+    fn test_interleave_tracks() {
+        assert_eq!(
+            interleave(&[vec![1, 2, 3], vec![-1, -2, -3]]),
+            vec![1, -1, 2, -2, 3, -3]
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use crate::transcription::buffer::TranscriptionBuffer;
+use crate::transcription::language::{self, PromptSettings};
+use crate::transcription::vocabulary;
 use anthropic_sdk::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -18,6 +20,8 @@ pub struct EnhancedTranscript {
 pub struct EnhancementAgent {
     api_key: String,
     model: String,
+    /// Spoken language and key terms to correct the transcript with
+    prompt: PromptSettings,
 }
 
 impl EnhancementAgent {
@@ -26,11 +30,22 @@ impl EnhancementAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(), // Latest Claude Haiku 4.5 - fast and cost-effective
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language and key-term settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the enhancement prompt for the AI
-    fn build_enhancement_prompt(raw_text: &str) -> String {
+    fn build_enhancement_prompt(
+        raw_text: &str,
+        vocabulary: &[String],
+        language: Option<&str>,
+    ) -> String {
         format!(
             r#"You are a transcription enhancement assistant. Your task is to improve the quality of audio transcriptions while preserving the original meaning and speaker's intent.
 
@@ -48,15 +63,18 @@ Enhancement guidelines:
 - Capitalize proper nouns and sentence beginnings
 - Polish sentence structure for readability
 - Format technical terms correctly
-- Remove filler words (um, uh, like) only if excessive
+- Remove filler words (um, uh, like, or their equivalents) only if excessive
 - Preserve the speaker's tone and meaning exactly
 - Do NOT summarize - keep all content
 - Work with fragments - enhance whatever text is provided, even if incomplete
+- {}
 
-Raw transcription:
+{}Raw transcription:
 {}
 
 Enhanced transcription (start immediately, no preamble):"#,
+            language::correction_instruction(language),
+            vocabulary::prompt_section(vocabulary),
             raw_text
         )
     }
@@ -78,7 +96,11 @@ Enhanced transcription (start immediately, no preamble):"#,
         );
 
         // Build the prompt
-        let prompt = Self::build_enhancement_prompt(&raw_text);
+        let prompt = Self::build_enhancement_prompt(
+            &raw_text,
+            &self.prompt.vocabulary,
+            self.prompt.language.as_deref(),
+        );
 
         // Create the API request using builder pattern
         let request = Client::new()
@@ -143,10 +165,23 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = EnhancementAgent::build_enhancement_prompt("hello world");
+        let prompt = EnhancementAgent::build_enhancement_prompt("hello world", &[], None);
         assert!(prompt.contains("hello world"));
         assert!(prompt.contains("Fix grammar"));
         assert!(prompt.contains("Enhanced transcription"));
+        assert!(!prompt.contains("Key terms"));
+        assert!(prompt.contains("never translate"));
+
+        let prompt = EnhancementAgent::build_enhancement_prompt(
+            "mega pack",
+            &["Megapack".to_string()],
+            None,
+        );
+        assert!(prompt.contains("Key terms"));
+        assert!(prompt.contains("Megapack"));
+
+        let prompt = EnhancementAgent::build_enhancement_prompt("guten morgen", &[], Some("de"));
+        assert!(prompt.contains("The transcript is in German"));
     }
 
     #[test]
@@ -0,0 +1,144 @@
+//! # Language
+//!
+//! Languages a session can be recorded in, and the prompt wording that keeps
+//! LLM output in the language the user wants: the transcript's own unless an
+//! output language is chosen.
+
+/// Languages the multilingual streaming model recognizes, by ISO 639-1 code
+pub const SUPPORTED: [(&str, &str); 6] = [
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+];
+
+/// Language setting that detects the spoken language instead of fixing it
+pub const AUTO: &str = "auto";
+
+/// English name for a supported language code
+pub fn name(code: &str) -> Option<&'static str> {
+    SUPPORTED
+        .iter()
+        .find(|(supported, _)| *supported == code)
+        .map(|(_, name)| *name)
+}
+
+/// Check a spoken language setting: a supported code, or [`AUTO`]
+pub fn validate(language: &str) -> Result<(), String> {
+    if language == AUTO || name(language).is_some() {
+        return Ok(());
+    }
+    let codes: Vec<_> = SUPPORTED.iter().map(|(code, _)| *code).collect();
+    Err(format!(
+        "Unsupported language \"{}\" (expected {} or {})",
+        language,
+        codes.join(", "),
+        AUTO
+    ))
+}
+
+/// Check an output language for LLM responses: a supported code
+pub fn validate_output(language: &str) -> Result<(), String> {
+    name(language)
+        .map(|_| ())
+        .ok_or_else(|| format!("Unsupported output language \"{}\"", language))
+}
+
+/// The spoken language when fixed, or `None` when it is detected
+pub fn fixed(language: Option<&str>) -> Option<&str> {
+    language.filter(|language| *language != AUTO)
+}
+
+/// Prompt line for tasks that correct a transcript, which must never
+/// translate it
+pub fn correction_instruction(transcript_language: Option<&str>) -> String {
+    match transcript_language.and_then(name) {
+        Some(name) => format!(
+            "The transcript is in {}. Correct it in {} and never translate it.",
+            name, name
+        ),
+        None => "Correct the transcript in the language it is spoken in and never translate it."
+            .to_string(),
+    }
+}
+
+/// Prompt line for tasks that write about a transcript (summaries, analyses)
+pub fn response_instruction(output_language: Option<&str>) -> String {
+    match output_language.and_then(name) {
+        Some(name) => format!(
+            "Write your response in {}, whatever language the transcript is in.",
+            name
+        ),
+        None => "Write your response in the language of the transcript.".to_string(),
+    }
+}
+
+/// [`response_instruction`] for prompts that answer in JSON, whose keys and
+/// enumerated values the caller parses
+pub fn json_response_instruction(output_language: Option<&str>) -> String {
+    format!(
+        "{} Keep JSON keys and values picked from a fixed list in English.",
+        response_instruction(output_language)
+    )
+}
+
+/// Language and key-term settings the LLM agents write their prompts with
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromptSettings {
+    /// Spoken language code, or `None` when unknown
+    pub language: Option<String>,
+    /// Language for free-text responses, or `None` for the transcript's own
+    pub output_language: Option<String>,
+    /// Project key terms the prompt asks to spell correctly
+    pub vocabulary: Vec<String>,
+}
+
+impl PromptSettings {
+    /// Language the transcript is spoken in. Correcting prompts keep the
+    /// text in it rather than translating.
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Write responses in this language (e.g. "en") whatever the transcript
+    /// was spoken in
+    pub fn with_output_language(mut self, output_language: Option<String>) -> Self {
+        self.output_language = output_language;
+        self
+    }
+
+    /// Project key terms to spell correctly
+    pub fn with_vocabulary(mut self, vocabulary: Vec<String>) -> Self {
+        self.vocabulary = vocabulary;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("de").is_ok());
+        assert!(validate("FR").is_err());
+        assert!(validate(AUTO).is_ok());
+        assert!(validate("xx").is_err());
+        assert!(validate_output("it").is_ok());
+        assert!(validate_output(AUTO).is_err());
+        assert_eq!(fixed(Some(AUTO)), None);
+        assert_eq!(fixed(Some("de")), Some("de"));
+    }
+
+    #[test]
+    fn test_instructions_name_the_language() {
+        assert!(correction_instruction(Some("de")).contains("in German"));
+        assert!(correction_instruction(None).contains("language it is spoken in"));
+        assert!(response_instruction(Some("fr")).contains("in French"));
+        assert!(response_instruction(None).contains("language of the transcript"));
+        assert!(json_response_instruction(Some("de")).contains("JSON keys"));
+    }
+}
@@ -0,0 +1,313 @@
+//! # Audio Level Metering
+//!
+//! Measures RMS, peak and clipping on the streamed audio. Levels are aggregated
+//! into short windows for a live meter in the UI, and sustained problems (a muted
+//! microphone, input gain far too high) are reported as [`AudioHealth`] changes so
+//! users notice within seconds rather than after the meeting.
+//!
+//! Time is measured in samples, not wall-clock time, so the meter behaves the
+//! same for live capture and faster-than-real-time sources.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+
+use super::vad::chunk_level_db;
+
+/// Samples at or above this magnitude count as clipped
+const CLIP_LEVEL: i16 = 32_440; // ~ -0.1 dBFS
+
+/// Level metering settings
+#[derive(Debug, Clone)]
+pub struct LevelMeterConfig {
+    /// Length of each reported level window
+    pub window_ms: u32,
+    /// Windows below this RMS level (dBFS) count as silent
+    pub silence_threshold_db: f32,
+    /// Continuous silence before a `Silent` warning
+    pub silence_warning_secs: f64,
+    /// Span over which clipping is evaluated
+    pub clipping_window_secs: f64,
+    /// Fraction of chunks in the clipping window that must clip to warn
+    pub clipping_warning_ratio: f64,
+}
+
+impl Default for LevelMeterConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 200,
+            silence_threshold_db: -60.0,
+            silence_warning_secs: 10.0,
+            clipping_window_secs: 5.0,
+            clipping_warning_ratio: 0.25,
+        }
+    }
+}
+
+/// Audio level over one window
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AudioLevel {
+    /// RMS level in dBFS
+    pub rms_db: f32,
+    /// Peak level in dBFS
+    pub peak_db: f32,
+    /// Whether any sample in the window clipped
+    pub clipping: bool,
+}
+
+/// Input health as seen by the meter
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioHealthStatus {
+    Ok,
+    /// Nothing but silence for a while - muted or wrong microphone
+    Silent,
+    /// Sustained clipping - input gain too high
+    Clipping,
+}
+
+/// Change in input health
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AudioHealth {
+    pub status: AudioHealthStatus,
+    /// How long the condition has lasted when reported
+    pub duration_seconds: f64,
+    pub message: String,
+}
+
+/// Result of metering one chunk
+#[derive(Debug, Default)]
+pub struct MeterOutput {
+    /// Set when a level window completed
+    pub level: Option<AudioLevel>,
+    /// Set when the health status changed
+    pub health: Option<AudioHealth>,
+}
+
+/// Streaming level meter
+pub struct LevelMeter {
+    config: LevelMeterConfig,
+    sample_rate: u32,
+    window_samples: u64,
+    window_sum_squares: f64,
+    window_peak: i32,
+    window_clipping: bool,
+    silent_run_samples: u64,
+    clip_history: VecDeque<(u64, bool)>,
+    clip_history_samples: u64,
+    status: AudioHealthStatus,
+}
+
+impl LevelMeter {
+    pub fn new(config: LevelMeterConfig, sample_rate: u32) -> Self {
+        Self {
+            config,
+            sample_rate,
+            window_samples: 0,
+            window_sum_squares: 0.0,
+            window_peak: 0,
+            window_clipping: false,
+            silent_run_samples: 0,
+            clip_history: VecDeque::new(),
+            clip_history_samples: 0,
+            status: AudioHealthStatus::Ok,
+        }
+    }
+
+    fn secs_to_samples(&self, secs: f64) -> u64 {
+        (secs * self.sample_rate as f64) as u64
+    }
+
+    /// Meter a chunk of audio
+    pub fn process(&mut self, chunk: &[i16]) -> MeterOutput {
+        let mut output = MeterOutput::default();
+        if chunk.is_empty() {
+            return output;
+        }
+
+        let len = chunk.len() as u64;
+        let peak = chunk.iter().map(|&s| (s as i32).abs()).max().unwrap_or(0);
+        let clipped = peak >= CLIP_LEVEL as i32;
+
+        self.window_samples += len;
+        self.window_sum_squares += chunk
+            .iter()
+            .map(|&s| {
+                let s = s as f64 / 32768.0;
+                s * s
+            })
+            .sum::<f64>();
+        self.window_peak = self.window_peak.max(peak);
+        self.window_clipping |= clipped;
+
+        if self.window_samples * 1000 >= self.config.window_ms as u64 * self.sample_rate as u64 {
+            output.level = Some(self.take_window());
+        }
+
+        // Silence is judged per chunk so a single cough resets the warning
+        if chunk_level_db(chunk) < self.config.silence_threshold_db {
+            self.silent_run_samples += len;
+        } else {
+            self.silent_run_samples = 0;
+        }
+
+        self.clip_history.push_back((len, clipped));
+        self.clip_history_samples += len;
+        let clip_window = self.secs_to_samples(self.config.clipping_window_secs);
+        while self.clip_history_samples > clip_window {
+            let Some((oldest, _)) = self.clip_history.pop_front() else {
+                break;
+            };
+            self.clip_history_samples -= oldest;
+        }
+
+        output.health = self.update_status(clip_window);
+        output
+    }
+
+    fn take_window(&mut self) -> AudioLevel {
+        let rms = (self.window_sum_squares / self.window_samples as f64).sqrt();
+        let level = AudioLevel {
+            rms_db: to_db(rms),
+            peak_db: to_db(self.window_peak as f64 / 32768.0),
+            clipping: self.window_clipping,
+        };
+
+        self.window_samples = 0;
+        self.window_sum_squares = 0.0;
+        self.window_peak = 0;
+        self.window_clipping = false;
+        level
+    }
+
+    fn update_status(&mut self, clip_window: u64) -> Option<AudioHealth> {
+        let silent_secs = self.silent_run_samples as f64 / self.sample_rate as f64;
+        let clipped_samples: u64 = self
+            .clip_history
+            .iter()
+            .filter(|(_, clipped)| *clipped)
+            .map(|(len, _)| len)
+            .sum();
+        // Only judge clipping once a full window has been seen
+        let clipping = self.clip_history_samples >= clip_window
+            && clipped_samples as f64 / self.clip_history_samples as f64
+                >= self.config.clipping_warning_ratio;
+
+        let status = if silent_secs >= self.config.silence_warning_secs {
+            AudioHealthStatus::Silent
+        } else if clipping {
+            AudioHealthStatus::Clipping
+        } else {
+            AudioHealthStatus::Ok
+        };
+
+        if status == self.status {
+            return None;
+        }
+        self.status = status;
+
+        let (duration_seconds, message) = match status {
+            AudioHealthStatus::Silent => (
+                silent_secs,
+                format!(
+                    "No audio detected for {:.0}s. Check microphone selection, mute and volume.",
+                    silent_secs
+                ),
+            ),
+            AudioHealthStatus::Clipping => (
+                self.config.clipping_window_secs,
+                "Audio is clipping. Lower the input gain to avoid distorted transcripts."
+                    .to_string(),
+            ),
+            AudioHealthStatus::Ok => (0.0, "Audio input recovered".to_string()),
+        };
+
+        Some(AudioHealth {
+            status,
+            duration_seconds,
+            message,
+        })
+    }
+}
+
+fn to_db(amplitude: f64) -> f32 {
+    if amplitude > 0.0 {
+        (20.0 * amplitude.log10()).max(-100.0) as f32
+    } else {
+        -100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn feed(meter: &mut LevelMeter, chunk: &[i16], count: usize) -> Vec<MeterOutput> {
+        (0..count).map(|_| meter.process(chunk)).collect()
+    }
+
+    #[test]
+    fn test_levels_are_throttled_to_windows() {
+        let mut meter = LevelMeter::new(LevelMeterConfig::default(), RATE);
+        let outputs = feed(&mut meter, &[16384; 800], 20);
+
+        // 1s of 50ms chunks with 200ms windows
+        let levels: Vec<_> = outputs.iter().filter_map(|o| o.level.clone()).collect();
+        assert_eq!(levels.len(), 5);
+        assert!((levels[0].rms_db - -6.02).abs() < 0.01);
+        assert!((levels[0].peak_db - -6.02).abs() < 0.01);
+        assert!(!levels[0].clipping);
+    }
+
+    #[test]
+    fn test_sustained_silence_warns_and_recovers() {
+        let mut meter = LevelMeter::new(LevelMeterConfig::default(), RATE);
+
+        let outputs = feed(&mut meter, &[0; 800], 200);
+        let warnings: Vec<_> = outputs.iter().filter_map(|o| o.health.clone()).collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].status, AudioHealthStatus::Silent);
+        assert!((warnings[0].duration_seconds - 10.0).abs() < 1e-9);
+
+        let recovered = meter.process(&[4000; 800]).health.unwrap();
+        assert_eq!(recovered.status, AudioHealthStatus::Ok);
+    }
+
+    #[test]
+    fn test_sustained_clipping_warns() {
+        let mut meter = LevelMeter::new(LevelMeterConfig::default(), RATE);
+
+        // Every other chunk clips
+        let mut health = Vec::new();
+        for i in 0..200 {
+            let chunk = if i % 2 == 0 {
+                [32767; 800]
+            } else {
+                [8000; 800]
+            };
+            let output = meter.process(&chunk);
+            if let Some(level) = output.level {
+                assert!(level.clipping);
+            }
+            health.extend(output.health);
+        }
+
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].status, AudioHealthStatus::Clipping);
+    }
+
+    #[test]
+    fn test_occasional_clipping_is_tolerated() {
+        let mut meter = LevelMeter::new(LevelMeterConfig::default(), RATE);
+
+        for i in 0..200 {
+            let chunk = if i % 10 == 0 {
+                [32767; 800]
+            } else {
+                [8000; 800]
+            };
+            assert!(meter.process(&chunk).health.is_none());
+        }
+    }
+}
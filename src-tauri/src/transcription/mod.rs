@@ -1,13 +1,29 @@
 pub mod assemblyai;
 pub mod audio;
+pub mod audio_file;
 pub mod buffer;
 pub mod buffer_pool;
 pub mod commands;
+pub mod dsp;
+pub mod dual;
 pub mod enhancement;
+pub mod language;
+pub mod levels;
+pub mod processing;
+pub mod provider;
+pub mod reconnect;
+pub mod recorder;
 pub mod recording_commands;
 pub mod refinement;
+pub mod resampler;
 pub mod session;
+pub mod source;
+pub mod subtitles;
 pub mod summary;
+pub mod vad;
+pub mod vocabulary;
+#[cfg(feature = "whisper")]
+pub mod whisper;
 
 pub use commands::*;
 pub use recording_commands::*;
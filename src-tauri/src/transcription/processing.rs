@@ -0,0 +1,494 @@
+//! # Chunk Processing Stage
+//!
+//! Sits between an [`AudioSource`](super::source::AudioSource) and the streaming
+//! client. Every chunk passes through here before it is sent to AssemblyAI, which
+//! makes it the place for work that needs to see the session's audio: writing it
+//! to disk, metering levels and skipping silence with voice activity detection.
+//!
+//! Anything the frontend should hear about is reported as a [`StageEvent`].
+//!
+//! Sources with several tracks first pass through a [`TrackSplitter`], which
+//! records all tracks together and hands each one to its own [`ChunkStage`].
+//! In front of both, a [`PauseGate`] silences the audio while a session is paused.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use super::levels::{AudioHealth, AudioLevel, LevelMeter};
+use super::recorder::SessionAudioRecorder;
+use super::vad::{VadTransition, VoiceActivityDetector};
+
+/// Notifications from the processing stage
+#[derive(Debug, Clone, PartialEq)]
+pub enum StageEvent {
+    SpeechStart {
+        offset_secs: f64,
+    },
+    SpeechEnd {
+        offset_secs: f64,
+        silence_ratio: f64,
+    },
+    /// Throttled input level for live meters
+    Level(AudioLevel),
+    /// Input health changed (e.g. muted microphone detected)
+    Health(AudioHealth),
+}
+
+/// Outcome of a finished stage
+#[derive(Debug, Default)]
+pub struct StageSummary {
+    /// Finalized session recording
+    pub audio_path: Option<PathBuf>,
+    /// Fraction of the audio VAD classified as silence
+    pub silence_ratio: Option<f64>,
+    /// Seconds of silence that were not streamed
+    pub skipped_secs: f64,
+}
+
+/// Per-session processing applied to audio chunks on their way to the stream
+#[derive(Default)]
+pub struct ChunkStage {
+    recorder: Option<SessionAudioRecorder>,
+    meter: Option<LevelMeter>,
+    vad: Option<VoiceActivityDetector>,
+    events: Option<mpsc::UnboundedSender<StageEvent>>,
+}
+
+impl ChunkStage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also write every chunk to `recorder`
+    pub fn with_recorder(mut self, recorder: SessionAudioRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Report audio levels and input health measured by `meter`
+    pub fn with_level_meter(mut self, meter: LevelMeter) -> Self {
+        self.meter = Some(meter);
+        self
+    }
+
+    /// Only stream the chunks `vad` considers worth sending
+    pub fn with_vad(mut self, vad: VoiceActivityDetector) -> Self {
+        self.vad = Some(vad);
+        self
+    }
+
+    /// Report stage events to `events`
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<StageEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn emit(&self, event: StageEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+
+    /// Forward chunks from `input` to `output` until the input channel closes.
+    ///
+    /// Recording continues even if the stream stops accepting audio, so the
+    /// saved file covers the whole capture.
+    pub async fn run(
+        mut self,
+        mut input: mpsc::Receiver<Vec<i16>>,
+        output: mpsc::Sender<Vec<i16>>,
+    ) -> StageSummary {
+        let mut forwarding = true;
+
+        while let Some(chunk) = input.recv().await {
+            if let Some(recorder) = self.recorder.as_mut() {
+                if let Err(e) = recorder.write_chunk(&chunk) {
+                    // Losing the recording must not interrupt transcription
+                    tracing::error!("Session audio recording failed: {}", e);
+                    self.recorder = None;
+                }
+            }
+
+            if let Some(meter) = self.meter.as_mut() {
+                let metered = meter.process(&chunk);
+                if let Some(health) = metered.health {
+                    tracing::warn!("Audio health: {}", health.message);
+                    self.emit(StageEvent::Health(health));
+                }
+                if let Some(level) = metered.level {
+                    self.emit(StageEvent::Level(level));
+                }
+            }
+
+            let to_send = match self.vad.as_mut() {
+                Some(vad) => {
+                    let decision = vad.process(chunk);
+                    let event = decision.transition.map(|transition| match transition {
+                        VadTransition::SpeechStart => StageEvent::SpeechStart {
+                            offset_secs: vad.offset_secs(),
+                        },
+                        VadTransition::SpeechEnd => StageEvent::SpeechEnd {
+                            offset_secs: vad.offset_secs(),
+                            silence_ratio: vad.silence_ratio(),
+                        },
+                    });
+                    if let Some(event) = event {
+                        self.emit(event);
+                    }
+                    decision.forward
+                }
+                None => vec![chunk],
+            };
+
+            for chunk in to_send {
+                if forwarding && output.send(chunk).await.is_err() {
+                    tracing::debug!("Stream closed - no longer forwarding audio chunks");
+                    forwarding = false;
+                }
+            }
+        }
+
+        // Close the stream's channel before the (possibly slow) finalize
+        drop(output);
+
+        let mut summary = StageSummary::default();
+
+        if let Some(vad) = &self.vad {
+            if vad.in_speech() {
+                self.emit(StageEvent::SpeechEnd {
+                    offset_secs: vad.offset_secs(),
+                    silence_ratio: vad.silence_ratio(),
+                });
+            }
+            summary.silence_ratio = Some(vad.silence_ratio());
+            summary.skipped_secs = vad.dropped_secs();
+            tracing::info!(
+                "🔇 {:.0}% silence, {:.1}s not streamed",
+                vad.silence_ratio() * 100.0,
+                vad.dropped_secs()
+            );
+        }
+
+        summary.audio_path = self.recorder.take().and_then(finalize_recording);
+        summary
+    }
+}
+
+fn finalize_recording(recorder: SessionAudioRecorder) -> Option<PathBuf> {
+    let duration = recorder.duration_secs();
+    match recorder.finalize() {
+        Ok(path) => {
+            tracing::info!(
+                "💾 Session audio saved to {} ({:.1}s)",
+                path.display(),
+                duration
+            );
+            Some(path)
+        }
+        Err(e) => {
+            tracing::error!("{}", e);
+            None
+        }
+    }
+}
+
+/// Replaces the audio with silence while `paused` is set.
+///
+/// Nothing said off the record is streamed or recorded, yet chunks keep flowing:
+/// the streaming connection stays open (VAD thins the silence to keep-alives) and
+/// the recording keeps the session's timeline. Transcript timestamps follow it
+/// too, once mapped back through the VAD's
+/// [`StreamTimeline`](super::vad::StreamTimeline).
+pub struct PauseGate {
+    paused: Arc<AtomicBool>,
+}
+
+impl PauseGate {
+    pub fn new(paused: Arc<AtomicBool>) -> Self {
+        Self { paused }
+    }
+
+    /// Forward chunks from `input` to `output` until either channel closes
+    pub async fn run(self, mut input: mpsc::Receiver<Vec<i16>>, output: mpsc::Sender<Vec<i16>>) {
+        while let Some(mut chunk) = input.recv().await {
+            if self.paused.load(Ordering::Acquire) {
+                chunk.fill(0);
+            }
+            if output.send(chunk).await.is_err() {
+                tracing::debug!("Processing stage closed - no longer forwarding audio chunks");
+                break;
+            }
+        }
+    }
+}
+
+/// Splits chunks of interleaved tracks into one mono chunk stream per track
+#[derive(Default)]
+pub struct TrackSplitter {
+    recorder: Option<SessionAudioRecorder>,
+}
+
+impl TrackSplitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write the interleaved chunks to `recorder`, one channel per track
+    pub fn with_recorder(mut self, recorder: SessionAudioRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Split chunks from `input` into `outputs` (one per track, in order)
+    /// until the input channel closes. Returns the finalized recording.
+    pub async fn run(
+        mut self,
+        mut input: mpsc::Receiver<Vec<i16>>,
+        outputs: Vec<mpsc::Sender<Vec<i16>>>,
+    ) -> Option<PathBuf> {
+        let tracks = outputs.len().max(1);
+        let mut open = vec![true; outputs.len()];
+
+        while let Some(chunk) = input.recv().await {
+            if let Some(recorder) = self.recorder.as_mut() {
+                if let Err(e) = recorder.write_chunk(&chunk) {
+                    tracing::error!("Session audio recording failed: {}", e);
+                    self.recorder = None;
+                }
+            }
+
+            for (track, output) in outputs.iter().enumerate() {
+                if !open[track] {
+                    continue;
+                }
+                let samples = chunk.iter().skip(track).step_by(tracks).copied().collect();
+                if output.send(samples).await.is_err() {
+                    tracing::debug!("Track {} closed - no longer forwarding its audio", track);
+                    open[track] = false;
+                }
+            }
+        }
+
+        drop(outputs);
+        self.recorder.take().and_then(finalize_recording)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::levels::LevelMeterConfig;
+    use crate::transcription::vad::VadConfig;
+
+    #[tokio::test]
+    async fn test_stage_forwards_and_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+        let stage =
+            ChunkStage::new().with_recorder(SessionAudioRecorder::create(&path, 16000, 1).unwrap());
+
+        let (in_tx, in_rx) = mpsc::channel(4);
+        let (out_tx, mut out_rx) = mpsc::channel(4);
+        let task = tokio::spawn(stage.run(in_rx, out_tx));
+
+        in_tx.send(vec![1, 2]).await.unwrap();
+        in_tx.send(vec![3]).await.unwrap();
+        assert_eq!(out_rx.recv().await, Some(vec![1, 2]));
+        assert_eq!(out_rx.recv().await, Some(vec![3]));
+        drop(in_tx);
+
+        assert_eq!(task.await.unwrap().audio_path, Some(path.clone()));
+        assert_eq!(out_rx.recv().await, None);
+        assert_eq!(hound::WavReader::open(&path).unwrap().duration(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stage_keeps_recording_after_stream_closes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+        let stage =
+            ChunkStage::new().with_recorder(SessionAudioRecorder::create(&path, 16000, 1).unwrap());
+
+        let (in_tx, in_rx) = mpsc::channel(4);
+        let (out_tx, out_rx) = mpsc::channel(4);
+        drop(out_rx);
+        let task = tokio::spawn(stage.run(in_rx, out_tx));
+
+        in_tx.send(vec![0; 100]).await.unwrap();
+        in_tx.send(vec![0; 100]).await.unwrap();
+        drop(in_tx);
+
+        task.await.unwrap();
+        assert_eq!(hound::WavReader::open(&path).unwrap().duration(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_pause_gate_silences_audio_while_paused() {
+        let paused = Arc::new(AtomicBool::new(false));
+        let (in_tx, in_rx) = mpsc::channel(8);
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let handle = tokio::spawn(PauseGate::new(paused.clone()).run(in_rx, out_tx));
+
+        in_tx.send(vec![1000; 4]).await.unwrap();
+        assert_eq!(out_rx.recv().await, Some(vec![1000; 4]));
+
+        paused.store(true, Ordering::Release);
+        in_tx.send(vec![1000; 4]).await.unwrap();
+        assert_eq!(out_rx.recv().await, Some(vec![0; 4]));
+
+        paused.store(false, Ordering::Release);
+        in_tx.send(vec![1000; 4]).await.unwrap();
+        assert_eq!(out_rx.recv().await, Some(vec![1000; 4]));
+
+        drop(in_tx);
+        handle.await.unwrap();
+        assert_eq!(out_rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_splitter_separates_and_records_tracks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+        let splitter = TrackSplitter::new()
+            .with_recorder(SessionAudioRecorder::create(&path, 16000, 2).unwrap());
+
+        let (in_tx, in_rx) = mpsc::channel(4);
+        let (me_tx, mut me_rx) = mpsc::channel(4);
+        let (them_tx, mut them_rx) = mpsc::channel(4);
+        let task = tokio::spawn(splitter.run(in_rx, vec![me_tx, them_tx]));
+
+        in_tx.send(vec![1, -1, 2, -2, 3, -3]).await.unwrap();
+        drop(in_tx);
+
+        assert_eq!(me_rx.recv().await, Some(vec![1, 2, 3]));
+        assert_eq!(them_rx.recv().await, Some(vec![-1, -2, -3]));
+        assert_eq!(task.await.unwrap(), Some(path.clone()));
+        assert_eq!(me_rx.recv().await, None);
+
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.duration(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stage_reports_levels() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let stage = ChunkStage::new()
+            .with_level_meter(LevelMeter::new(LevelMeterConfig::default(), 16000))
+            .with_events(event_tx);
+
+        let (in_tx, in_rx) = mpsc::channel(8);
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let task = tokio::spawn(stage.run(in_rx, out_tx));
+
+        for _ in 0..4 {
+            in_tx.send(vec![32767; 800]).await.unwrap();
+            out_rx.recv().await.unwrap();
+        }
+        drop(in_tx);
+        task.await.unwrap();
+
+        match event_rx.recv().await {
+            Some(StageEvent::Level(level)) => assert!(level.clipping),
+            other => panic!("expected a level event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stage_skips_silence_and_reports_speech() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let stage = ChunkStage::new()
+            .with_vad(VoiceActivityDetector::new(VadConfig::default(), 16000))
+            .with_events(event_tx);
+
+        let (in_tx, in_rx) = mpsc::channel(64);
+        let (out_tx, mut out_rx) = mpsc::channel(64);
+        let task = tokio::spawn(stage.run(in_rx, out_tx));
+
+        // 1s silence, 0.5s speech, 1s silence
+        for _ in 0..20 {
+            in_tx.send(vec![0; 800]).await.unwrap();
+        }
+        for _ in 0..10 {
+            in_tx.send(vec![8000; 800]).await.unwrap();
+        }
+        for _ in 0..20 {
+            in_tx.send(vec![0; 800]).await.unwrap();
+        }
+        drop(in_tx);
+
+        let summary = task.await.unwrap();
+        let mut forwarded = 0;
+        while out_rx.recv().await.is_some() {
+            forwarded += 1;
+        }
+
+        // 300ms pre-roll + speech + full 1s of hangover (shorter than 1.5s)
+        assert_eq!(forwarded, 6 + 10 + 20);
+        assert!((summary.silence_ratio.unwrap() - 0.8).abs() < 1e-9);
+        assert!(summary.skipped_secs > 0.6);
+
+        assert!(matches!(
+            event_rx.recv().await,
+            Some(StageEvent::SpeechStart { offset_secs }) if (offset_secs - 1.05).abs() < 1e-9
+        ));
+        assert!(matches!(
+            event_rx.recv().await,
+            Some(StageEvent::SpeechEnd { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_silence_maps_back_to_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+        let vad = VoiceActivityDetector::new(VadConfig::default(), 16000);
+        let timeline = vad.timeline();
+        let stage = ChunkStage::new()
+            .with_recorder(SessionAudioRecorder::create(&path, 16000, 1).unwrap())
+            .with_vad(vad);
+
+        let (in_tx, in_rx) = mpsc::channel(128);
+        let (out_tx, mut out_rx) = mpsc::channel(128);
+        let task = tokio::spawn(stage.run(in_rx, out_tx));
+
+        // 0.5s speech, 3s silence (longer than hangover and pre-roll), 0.5s
+        // of different speech
+        for _ in 0..10 {
+            in_tx.send(vec![8000; 800]).await.unwrap();
+        }
+        for _ in 0..60 {
+            in_tx.send(vec![0; 800]).await.unwrap();
+        }
+        for _ in 0..10 {
+            in_tx.send(vec![9000; 800]).await.unwrap();
+        }
+        drop(in_tx);
+
+        let summary = task.await.unwrap();
+        assert!(summary.skipped_secs > 1.0);
+        let mut streamed = Vec::new();
+        while let Some(chunk) = out_rx.recv().await {
+            streamed.extend(chunk);
+        }
+        let recorded: Vec<i16> = hound::WavReader::open(&path)
+            .unwrap()
+            .samples::<i16>()
+            .map(Result::unwrap)
+            .collect();
+
+        // Where the second speech starts: earlier in the stream, but mapped
+        // back to the same place as in the recording
+        let onset_ms = |samples: &[i16]| {
+            samples.iter().position(|&s| s == 9000).unwrap() as u32 * 1000 / 16000
+        };
+        let stream_ms = onset_ms(&streamed);
+        let recording_ms = onset_ms(&recorded);
+        assert_eq!(recording_ms, 3500);
+        assert!(stream_ms < recording_ms);
+        assert_eq!(timeline.session_ms(stream_ms), recording_ms);
+        assert_eq!(timeline.session_ms(100), 100);
+    }
+}
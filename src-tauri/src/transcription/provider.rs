@@ -0,0 +1,179 @@
+//! # Transcription Providers
+//!
+//! A [`TranscriptionProvider`] turns a stream of PCM chunks into
+//! [`TranscriptResult`]s. The rest of the pipeline (session tracking, buffering,
+//! enhancement) only sees results, so providers are interchangeable:
+//!
+//! - [`AssemblyAIClient`] - AssemblyAI Universal Streaming over a WebSocket
+//! - [`WhisperProvider`](super::whisper::WhisperProvider) - local Whisper model
+//!   on the CPU, for setups where audio must not leave the machine. Requires
+//!   the `whisper` cargo feature.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use super::assemblyai::{AssemblyAIClient, StreamingConfig, TranscriptResult};
+
+/// A speech-to-text backend
+#[async_trait]
+pub trait TranscriptionProvider: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    /// Transcribe chunks of mono i16 PCM at `sample_rate` from `audio_receiver`
+    /// until it closes, sending results to `transcript_sender` and the
+    /// lifecycle of any vendor-side session to `events`.
+    ///
+    /// May be called several times concurrently, once per track.
+    async fn transcribe(
+        &self,
+        audio_receiver: mpsc::Receiver<Vec<i16>>,
+        transcript_sender: mpsc::UnboundedSender<TranscriptResult>,
+        events: mpsc::UnboundedSender<ProviderEvent>,
+        sample_rate: u32,
+    ) -> Result<(), String>;
+}
+
+/// A session opened with the provider's service. Hosted providers bill and
+/// log per session, so these let a recording be matched to the vendor's records.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderEvent {
+    /// A session began; the provider ends it at `expires_at` (Unix seconds)
+    SessionStarted { session_id: String, expires_at: u64 },
+    /// A session ended, with the durations the provider bills for
+    SessionEnded {
+        session_id: String,
+        audio_duration_seconds: f64,
+        session_duration_seconds: f64,
+    },
+}
+
+/// Provider selection passed from the frontend
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// AssemblyAI Universal Streaming, using the API key passed alongside
+    #[default]
+    #[serde(rename = "assemblyai")]
+    AssemblyAi,
+    /// Local Whisper model (ggml format, e.g. `ggml-base.en.bin`)
+    Whisper {
+        model_path: String,
+        /// Spoken language code (e.g. "en"), detected per window when unset
+        #[serde(default)]
+        language: Option<String>,
+        /// CPU threads used for inference, defaults to the available cores (max 8)
+        #[serde(default)]
+        threads: Option<u32>,
+    },
+}
+
+impl ProviderConfig {
+    /// Create the configured provider. `api_key` and `streaming` are only used
+    /// by AssemblyAI; `vocabulary` biases recognition towards the project's
+    /// key terms.
+    pub fn create(
+        &self,
+        api_key: Option<String>,
+        streaming: StreamingConfig,
+        vocabulary: Vec<String>,
+    ) -> Result<Arc<dyn TranscriptionProvider>, String> {
+        match self {
+            Self::AssemblyAi => {
+                let api_key = api_key.filter(|key| !key.trim().is_empty()).ok_or_else(|| {
+                    "An AssemblyAI API key is required unless a local transcription provider is selected"
+                        .to_string()
+                })?;
+                Ok(Arc::new(
+                    AssemblyAIClient::new(api_key)
+                        .with_config(streaming)
+                        .with_keyterms(vocabulary),
+                ))
+            }
+            #[cfg(feature = "whisper")]
+            Self::Whisper {
+                model_path,
+                language,
+                threads,
+            } => {
+                // The session's spoken language applies unless the model has its own
+                let language = language.clone().or_else(|| {
+                    super::language::fixed(streaming.language.as_deref()).map(str::to_string)
+                });
+                let mut provider = super::whisper::WhisperProvider::load(model_path)?
+                    .with_language(language)
+                    .with_vocabulary(&vocabulary);
+                if let Some(threads) = threads {
+                    provider = provider.with_threads(*threads);
+                }
+                Ok(Arc::new(provider))
+            }
+            #[cfg(not(feature = "whisper"))]
+            Self::Whisper { .. } => Err(
+                "Local Whisper transcription is not available in this build (enable the `whisper` feature)"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_config_serialization() {
+        let config: ProviderConfig = serde_json::from_str(r#"{"type": "assemblyai"}"#).unwrap();
+        assert_eq!(config, ProviderConfig::AssemblyAi);
+
+        let config: ProviderConfig = serde_json::from_str(
+            r#"{"type": "whisper", "model_path": "/models/ggml-base.en.bin"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            ProviderConfig::Whisper {
+                model_path: "/models/ggml-base.en.bin".to_string(),
+                language: None,
+                threads: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_assemblyai_requires_api_key() {
+        assert!(ProviderConfig::AssemblyAi
+            .create(None, StreamingConfig::default(), Vec::new())
+            .is_err());
+        assert!(ProviderConfig::AssemblyAi
+            .create(
+                Some("  ".to_string()),
+                StreamingConfig::default(),
+                Vec::new()
+            )
+            .is_err());
+
+        let provider = ProviderConfig::AssemblyAi
+            .create(
+                Some("key".to_string()),
+                StreamingConfig::default(),
+                vec!["Megapack".to_string()],
+            )
+            .unwrap();
+        assert_eq!(provider.name(), "AssemblyAI");
+    }
+
+    #[test]
+    fn test_missing_whisper_model_is_an_error() {
+        let config = ProviderConfig::Whisper {
+            model_path: "/nonexistent/ggml-base.en.bin".to_string(),
+            language: Some("en".to_string()),
+            threads: None,
+        };
+        assert!(config
+            .create(None, StreamingConfig::default(), Vec::new())
+            .is_err());
+    }
+}
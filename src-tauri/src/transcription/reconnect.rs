@@ -0,0 +1,171 @@
+//! # Reconnection
+//!
+//! Helpers for keeping a streaming session alive across dropped connections:
+//! an exponential [`Backoff`] between attempts, and a [`ReplayBuffer`] that
+//! holds the audio captured while disconnected so it can be sent once the
+//! connection is back.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Exponential backoff between reconnection attempts
+pub(crate) struct Backoff {
+    initial: Duration,
+    max: Duration,
+    max_attempts: u32,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub(crate) fn new(initial: Duration, max: Duration, max_attempts: u32) -> Self {
+        Self {
+            initial,
+            max,
+            max_attempts,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt, or `None` once every attempt is used up
+    pub(crate) fn next_delay(&mut self) -> Option<Duration> {
+        if self.attempt >= self.max_attempts {
+            return None;
+        }
+
+        let factor = 1u32.checked_shl(self.attempt).unwrap_or(u32::MAX);
+        self.attempt += 1;
+        Some(self.initial.saturating_mul(factor).min(self.max))
+    }
+
+    /// Start over after a connection has proven to work
+    pub(crate) fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    pub(crate) fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/// Audio waiting to be sent, bounded to `capacity` samples. When full, the
+/// oldest chunks are dropped.
+///
+/// Also tracks the stream position (in samples) of the next chunk to send, so
+/// results from a new connection can be placed on the original timeline.
+pub(crate) struct ReplayBuffer {
+    chunks: VecDeque<Vec<i16>>,
+    buffered: usize,
+    capacity: usize,
+    position: u64,
+    dropped: u64,
+}
+
+impl ReplayBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            buffered: 0,
+            capacity,
+            position: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Hold a chunk until it can be sent
+    pub(crate) fn push(&mut self, chunk: Vec<i16>) {
+        self.buffered += chunk.len();
+        self.chunks.push_back(chunk);
+
+        while self.buffered > self.capacity {
+            let Some(oldest) = self.chunks.pop_front() else {
+                break;
+            };
+            self.buffered -= oldest.len();
+            self.position += oldest.len() as u64;
+            self.dropped += oldest.len() as u64;
+        }
+    }
+
+    /// The oldest held chunk, left in place until [`Self::pop_front`]
+    pub(crate) fn front(&self) -> Option<&Vec<i16>> {
+        self.chunks.front()
+    }
+
+    /// Remove the oldest chunk once it has been sent
+    pub(crate) fn pop_front(&mut self) -> Option<Vec<i16>> {
+        let chunk = self.chunks.pop_front()?;
+        self.buffered -= chunk.len();
+        self.position += chunk.len() as u64;
+        Some(chunk)
+    }
+
+    /// Record a chunk that was sent directly, without being held
+    pub(crate) fn advance(&mut self, samples: usize) {
+        self.position += samples as u64;
+    }
+
+    /// Stream position of the next chunk to send, in samples
+    pub(crate) fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Samples currently held
+    pub(crate) fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Samples dropped because the buffer was full
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let mut backoff = Backoff::new(Duration::from_millis(500), Duration::from_secs(3), 5);
+
+        let delays: Vec<_> = std::iter::from_fn(|| backoff.next_delay()).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+                Duration::from_secs(3),
+            ]
+        );
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_replay_buffer_drops_oldest_when_full() {
+        let mut buffer = ReplayBuffer::new(10);
+        buffer.advance(100);
+
+        for i in 0..4 {
+            buffer.push(vec![i; 4]);
+        }
+
+        // Two of the four chunks fit
+        assert_eq!(buffer.buffered(), 8);
+        assert_eq!(buffer.dropped(), 8);
+        assert_eq!(buffer.position(), 108);
+
+        assert_eq!(buffer.front(), Some(&vec![2; 4]));
+        assert_eq!(buffer.pop_front(), Some(vec![2; 4]));
+        assert_eq!(buffer.pop_front(), Some(vec![3; 4]));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.position(), 116);
+    }
+}
@@ -0,0 +1,164 @@
+//! # Session Audio Recording
+//!
+//! Writes the audio streamed during a transcription session to a WAV file so
+//! reviewers can re-listen to passages where the transcript is doubtful.
+//!
+//! Chunks are appended as they arrive and the WAV header is refreshed
+//! periodically, so the file stays playable even if the app exits mid-session.
+
+use hound::{WavSpec, WavWriter};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// How often the WAV header is rewritten, in seconds of audio
+const HEADER_FLUSH_INTERVAL_SECS: u64 = 5;
+
+/// Directory session audio is stored in (`~/.causal/recordings`)
+pub fn recordings_dir() -> Result<PathBuf, String> {
+    Ok(crate::database::Database::app_data_dir()?.join("recordings"))
+}
+
+/// Incremental 16-bit WAV writer for one session.
+/// Multi-track sessions are written with one channel per track.
+pub struct SessionAudioRecorder {
+    writer: WavWriter<BufWriter<File>>,
+    path: PathBuf,
+    sample_rate: u32,
+    channels: u16,
+    samples_written: u64,
+    samples_since_flush: u64,
+}
+
+impl SessionAudioRecorder {
+    /// Create a recorder writing to `<recordings_dir>/<session_id>.wav`
+    pub fn for_session(session_id: &str, sample_rate: u32, channels: u16) -> Result<Self, String> {
+        let dir = recordings_dir()?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+        Self::create(
+            &dir.join(format!("{}.wav", session_id)),
+            sample_rate,
+            channels,
+        )
+    }
+
+    /// Create a recorder writing to `path`
+    pub fn create(path: &Path, sample_rate: u32, channels: u16) -> Result<Self, String> {
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = WavWriter::create(path, spec)
+            .map_err(|e| format!("Failed to create WAV file {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            writer,
+            path: path.to_path_buf(),
+            sample_rate,
+            channels,
+            samples_written: 0,
+            samples_since_flush: 0,
+        })
+    }
+
+    /// Location of the WAV file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a chunk of samples (interleaved when there are several channels)
+    pub fn write_chunk(&mut self, chunk: &[i16]) -> Result<(), String> {
+        let mut samples = self.writer.get_i16_writer(chunk.len() as u32);
+        for &sample in chunk {
+            samples.write_sample(sample);
+        }
+        samples
+            .flush()
+            .map_err(|e| format!("Failed to write audio: {}", e))?;
+
+        self.samples_written += chunk.len() as u64;
+        self.samples_since_flush += chunk.len() as u64;
+
+        if self.samples_since_flush
+            >= HEADER_FLUSH_INTERVAL_SECS * self.sample_rate as u64 * self.channels as u64
+        {
+            self.writer
+                .flush()
+                .map_err(|e| format!("Failed to flush audio file: {}", e))?;
+            self.samples_since_flush = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Seconds of audio written so far
+    pub fn duration_secs(&self) -> f64 {
+        self.samples_written as f64 / self.channels as f64 / self.sample_rate as f64
+    }
+
+    /// Finish the file, returning its path
+    pub fn finalize(self) -> Result<PathBuf, String> {
+        self.writer
+            .finalize()
+            .map_err(|e| format!("Failed to finalize audio file: {}", e))?;
+        Ok(self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_writes_readable_wav() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+
+        let mut recorder = SessionAudioRecorder::create(&path, 16000, 1).unwrap();
+        recorder.write_chunk(&[1, 2, 3]).unwrap();
+        recorder.write_chunk(&[-4, -5]).unwrap();
+        assert!((recorder.duration_secs() - 5.0 / 16000.0).abs() < f64::EPSILON);
+        let finalized = recorder.finalize().unwrap();
+
+        let mut reader = hound::WavReader::open(&finalized).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16000);
+        assert_eq!(reader.spec().channels, 1);
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        assert_eq!(samples, vec![1, 2, 3, -4, -5]);
+    }
+
+    #[test]
+    fn test_header_is_flushed_during_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+
+        let mut recorder = SessionAudioRecorder::create(&path, 8000, 1).unwrap();
+        let chunk = vec![100i16; 8000];
+        for _ in 0..HEADER_FLUSH_INTERVAL_SECS {
+            recorder.write_chunk(&chunk).unwrap();
+        }
+
+        // Readable before finalize, e.g. after a crash
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.duration() as u64, HEADER_FLUSH_INTERVAL_SECS * 8000);
+        drop(recorder);
+    }
+
+    #[test]
+    fn test_multi_track_recording_has_a_channel_per_track() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.wav");
+
+        let mut recorder = SessionAudioRecorder::create(&path, 16000, 2).unwrap();
+        recorder.write_chunk(&[1, -1, 2, -2]).unwrap();
+        assert!((recorder.duration_secs() - 2.0 / 16000.0).abs() < f64::EPSILON);
+        recorder.finalize().unwrap();
+
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.duration(), 2);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::database::{Database, Recording, Project};
 use crate::LoggingState;
 use crate::transcription::commands::AppState;
+use crate::transcription::language::PromptSettings;
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, State};
 
@@ -18,6 +19,7 @@ pub async fn save_recording(
     action_items: Vec<String>,
     claude_api_key: Option<String>,
     auto_generate_summary: Option<bool>,
+    output_language: Option<String>,
 ) -> Result<Recording, String> {
     // Get current project ID
     let project_id = state
@@ -36,6 +38,8 @@ pub async fn save_recording(
         .await
         .ok_or_else(|| "No active session".to_string())?;
 
+    let words = session.recording_words();
+
     // Convert session data to recording
     let mut recording = Recording::new(
         project_id.clone(),
@@ -45,7 +49,9 @@ pub async fn save_recording(
     );
 
     // Use metadata from session
-    recording = recording.with_metadata(session.metadata.to_recording_metadata());
+    recording = recording
+        .with_metadata(session.metadata.to_recording_metadata())
+        .with_audio_path(session.audio_path);
 
     // Add summary if provided
     if let Some(summary_text) = summary {
@@ -55,6 +61,11 @@ pub async fn save_recording(
     // Save to database
     let saved = db.create_recording(recording).await?;
 
+    // The recording is usable without word timings, so don't fail the save
+    if let Err(e) = db.save_recording_words(&saved.id, &words).await {
+        tracing::error!("Failed to save word timings for recording {}: {}", saved.id, e);
+    }
+
     // Track metrics
     if let Ok(log_state) = logging_state.lock() {
         log_state.metrics.recording_saved();
@@ -83,18 +94,15 @@ pub async fn save_recording(
                         // Get the recording
                         let mut recording = db_inner.get_recording(&recording_id).await?;
 
-                        // Use enhanced transcript if available, otherwise fall back to raw
-                        let transcript_text = if !recording.enhanced_transcript.is_empty() {
-                            recording.enhanced_transcript.clone()
-                        } else {
-                            recording.raw_transcript.clone()
-                        };
+                        // Use enhanced transcript if available, with speakers under their real names
+                        let transcript_text = recording.summary_transcript();
 
                         // Calculate approximate chunk count from metadata
                         let chunk_count = recording.metadata.turn_count.max(1) as u32;
 
                         // Generate summary using the summary service
-                        let summary_service = crate::transcription::summary::SummaryService::new(api_key.clone());
+                        let summary_service = crate::transcription::summary::SummaryService::new(api_key.clone())
+                            .with_prompt_settings(PromptSettings::default().with_output_language(output_language));
                         let summary = summary_service
                             .summarize(transcript_text, chunk_count)
                             .await?;
@@ -222,7 +230,13 @@ pub async fn get_current_project(
 #[tauri::command]
 pub async fn clear_current_session(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     tracing::info!("Clearing current session");
-    state.session_manager.clear_session().await;
+
+    // The session was discarded without saving, so its audio is no longer needed
+    if let Some(session) = state.session_manager.end_session().await {
+        if let Some(audio_path) = session.audio_path {
+            remove_session_audio(&audio_path);
+        }
+    }
 
     // Emit real-time event for session clear
     if let Err(e) = app.emit("session_cleared", serde_json::json!({})) {
@@ -231,3 +245,12 @@ pub async fn clear_current_session(app: AppHandle, state: State<'_, AppState>) -
 
     Ok(())
 }
+
+/// Delete a session audio file, logging rather than failing if it cannot be removed
+pub(crate) fn remove_session_audio(audio_path: &str) {
+    match std::fs::remove_file(audio_path) {
+        Ok(()) => tracing::info!("Deleted session audio {}", audio_path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("Failed to delete session audio {}: {}", audio_path, e),
+    }
+}
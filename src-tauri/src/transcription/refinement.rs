@@ -1,3 +1,5 @@
+use crate::transcription::language::{self, PromptSettings};
+use crate::transcription::vocabulary;
 use anthropic_sdk::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -17,6 +19,8 @@ pub struct RefinedTranscript {
 pub struct RefinementAgent {
     api_key: String,
     model: String,
+    /// Spoken language and key terms to correct the transcript with
+    prompt: PromptSettings,
 }
 
 impl RefinementAgent {
@@ -25,11 +29,22 @@ impl RefinementAgent {
         Self {
             api_key,
             model: "claude-haiku-4-5-20251001".to_string(), // Fast Haiku for quick refinement
+            prompt: PromptSettings::default(),
         }
     }
 
+    /// Language and key-term settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
     /// Build the refinement prompt for the AI
-    fn build_refinement_prompt(transcript: &str) -> String {
+    fn build_refinement_prompt(
+        transcript: &str,
+        vocabulary: &[String],
+        language: Option<&str>,
+    ) -> String {
         format!(
             r#"You are a professional transcript editor. Your task is to refine this raw speech-to-text transcript into clear, readable text while preserving all information and meaning.
 
@@ -37,19 +52,22 @@ Guidelines:
 1. Fix grammar, punctuation, and capitalization errors
 2. Break long run-on sentences into clear, concise sentences
 3. Add paragraph breaks for better readability (use double newlines)
-4. Fix word boundaries and run-together words (e.g., "fc supervised" → "FSD supervised")
+4. Fix word boundaries and run-together words (e.g., "face book" → "Facebook")
 5. Correct obvious transcription errors while preserving technical terms
 6. Format numbers and measurements correctly (e.g., "a million miles" → "1 million miles")
 7. Add proper punctuation for clarity (commas, periods, question marks)
 8. Preserve speaker intent and meaning exactly - do NOT summarize or omit content
-9. Format product names correctly (e.g., "megapack" → "Megapack", "powerwall" → "Powerwall")
+9. Format product names correctly, using the project's key terms where given
 10. Do NOT add information that wasn't spoken
 11. Return ONLY the refined transcript, no explanations or metadata
+12. {}
 
-Raw transcript:
+{}Raw transcript:
 {}
 
 Refined transcript:"#,
+            language::correction_instruction(language),
+            vocabulary::prompt_section(vocabulary),
             transcript
         )
     }
@@ -72,7 +90,11 @@ Refined transcript:"#,
         );
 
         // Build the prompt
-        let prompt = Self::build_refinement_prompt(&raw_text);
+        let prompt = Self::build_refinement_prompt(
+            &raw_text,
+            &self.prompt.vocabulary,
+            self.prompt.language.as_deref(),
+        );
 
         // Create the API request using builder pattern
         let request = Client::new()
@@ -138,10 +160,18 @@ mod tests {
 
     #[test]
     fn test_prompt_generation() {
-        let prompt = RefinementAgent::build_refinement_prompt("hello world test");
+        let prompt = RefinementAgent::build_refinement_prompt("hello world test", &[], None);
         assert!(prompt.contains("hello world test"));
         assert!(prompt.contains("Fix grammar"));
         assert!(prompt.contains("Refined transcript:"));
+
+        let prompt = RefinementAgent::build_refinement_prompt(
+            "the mega pack shipped",
+            &["Megapack".to_string(), "TSLA".to_string()],
+            Some("fr"),
+        );
+        assert!(prompt.contains("Megapack, TSLA"));
+        assert!(prompt.contains("The transcript is in French"));
     }
 
     #[test]
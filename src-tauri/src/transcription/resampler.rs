@@ -0,0 +1,171 @@
+//! Sample-rate conversion for mono f32 audio.
+//!
+//! Wraps rubato's synchronous FFT resampler behind a push-style API so callers can
+//! feed arbitrarily sized blocks (cpal callbacks, decoded files) and collect the
+//! resampled output as it becomes available.
+
+use rubato::{FftFixedInOut, Resampler};
+
+/// Preferred input block size handed to rubato. The actual size is rounded to a
+/// multiple of the rate ratio by the resampler itself.
+const RESAMPLER_BLOCK_FRAMES: usize = 1024;
+
+/// Streaming mono resampler with internal input buffering.
+pub struct StreamResampler {
+    resampler: Option<FftFixedInOut<f32>>,
+    input_rate: u32,
+    output_rate: u32,
+    pending: Vec<f32>,
+    input_block: Vec<Vec<f32>>,
+    output_block: Vec<Vec<f32>>,
+    /// Leading output frames still to discard to compensate for the filter delay
+    delay_remaining: usize,
+    frames_in: u64,
+    frames_out: u64,
+}
+
+impl StreamResampler {
+    /// Create a resampler converting `input_rate` to `output_rate`.
+    /// When the rates are equal the resampler is a cheap pass-through.
+    pub fn new(input_rate: u32, output_rate: u32) -> Result<Self, String> {
+        if input_rate == 0 || output_rate == 0 {
+            return Err("Sample rates must be greater than zero".to_string());
+        }
+
+        let resampler = if input_rate == output_rate {
+            None
+        } else {
+            Some(
+                FftFixedInOut::<f32>::new(
+                    input_rate as usize,
+                    output_rate as usize,
+                    RESAMPLER_BLOCK_FRAMES,
+                    1,
+                )
+                .map_err(|e| format!("Failed to create resampler: {}", e))?,
+            )
+        };
+
+        let (input_block, output_block, delay_remaining) = match &resampler {
+            Some(r) => (
+                r.input_buffer_allocate(true),
+                r.output_buffer_allocate(true),
+                r.output_delay(),
+            ),
+            None => (Vec::new(), Vec::new(), 0),
+        };
+
+        Ok(Self {
+            resampler,
+            input_rate,
+            output_rate,
+            pending: Vec::new(),
+            input_block,
+            output_block,
+            delay_remaining,
+            frames_in: 0,
+            frames_out: 0,
+        })
+    }
+
+    /// Feed input samples and append any resampled output to `output`.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) -> Result<(), String> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            output.extend_from_slice(input);
+            return Ok(());
+        };
+
+        self.pending.extend_from_slice(input);
+        self.frames_in += input.len() as u64;
+
+        let block_len = resampler.input_frames_next();
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= block_len {
+            self.input_block[0].copy_from_slice(&self.pending[consumed..consumed + block_len]);
+            let (_, produced) = resampler
+                .process_into_buffer(&self.input_block, &mut self.output_block, None)
+                .map_err(|e| format!("Resampling failed: {}", e))?;
+            consumed += block_len;
+
+            let skip = self.delay_remaining.min(produced);
+            self.delay_remaining -= skip;
+            output.extend_from_slice(&self.output_block[0][skip..produced]);
+            self.frames_out += (produced - skip) as u64;
+        }
+        self.pending.drain(..consumed);
+
+        Ok(())
+    }
+
+    /// Flush any buffered input, padding the final block with silence.
+    pub fn flush(&mut self, output: &mut Vec<f32>) -> Result<(), String> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return Ok(());
+        };
+
+        // Emit exactly as many frames as the total input duration calls for
+        let expected = self.frames_in * self.output_rate as u64 / self.input_rate as u64;
+        let mut remaining = expected.saturating_sub(self.frames_out) as usize;
+
+        let mut tail: Option<Vec<f32>> = Some(std::mem::take(&mut self.pending));
+        while remaining > 0 {
+            let chunk = tail.take();
+            let wave_in = chunk.as_ref().map(|c| vec![c.as_slice()]);
+            let (_, produced) = resampler
+                .process_partial_into_buffer(wave_in.as_deref(), &mut self.output_block, None)
+                .map_err(|e| format!("Resampling failed: {}", e))?;
+
+            let skip = self.delay_remaining.min(produced);
+            self.delay_remaining -= skip;
+            let take = (produced - skip).min(remaining);
+            output.extend_from_slice(&self.output_block[0][skip..skip + take]);
+            self.frames_out += take as u64;
+            remaining -= take;
+
+            if produced == 0 {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passthrough_when_rates_match() {
+        let mut resampler = StreamResampler::new(16000, 16000).unwrap();
+        let mut out = Vec::new();
+        resampler.process(&[0.1, 0.2, 0.3], &mut out).unwrap();
+        resampler.flush(&mut out).unwrap();
+        assert_eq!(out, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_downsample_preserves_duration() {
+        let mut resampler = StreamResampler::new(48000, 16000).unwrap();
+        let input: Vec<f32> = (0..48000)
+            .map(|i| (i as f32 * 440.0 * 2.0 * std::f32::consts::PI / 48000.0).sin() * 0.5)
+            .collect();
+
+        let mut out = Vec::new();
+        // Feed in uneven blocks like an audio callback would
+        for block in input.chunks(480) {
+            resampler.process(block, &mut out).unwrap();
+        }
+        resampler.flush(&mut out).unwrap();
+
+        assert_eq!(out.len(), 16000);
+        // The sine should survive resampling with similar energy
+        let rms = (out.iter().map(|s| s * s).sum::<f32>() / out.len() as f32).sqrt();
+        assert!((rms - 0.5 / 2f32.sqrt()).abs() < 0.05, "rms was {}", rms);
+    }
+
+    #[test]
+    fn test_rejects_zero_rate() {
+        assert!(StreamResampler::new(0, 16000).is_err());
+    }
+}
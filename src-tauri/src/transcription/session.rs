@@ -1,4 +1,6 @@
-use crate::database::{Recording, RecordingMetadata};
+use crate::database::{
+    PausedInterval, ProviderSession, Recording, RecordingMetadata, RecordingWord, SpeakerStats,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -13,6 +15,8 @@ pub struct SessionData {
     pub enhanced_buffers: Vec<EnhancedBufferData>,
     pub start_time: std::time::SystemTime,
     pub metadata: SessionMetadata,
+    /// WAV file the session audio is being recorded to
+    pub audio_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +24,79 @@ pub struct TurnData {
     pub turn_order: usize,
     pub text: String,
     pub confidence: f64,
+    /// Who spoke: the track it was heard on (e.g. "me" / "them") for
+    /// multi-track sources, otherwise the provider's label (e.g. "Speaker A")
+    #[serde(default)]
+    pub speaker: Option<String>,
+    /// Start of the first word, in ms from the start of the session
+    #[serde(default)]
+    pub start_ms: Option<u32>,
+    /// End of the last word, in ms from the start of the session
+    #[serde(default)]
+    pub end_ms: Option<u32>,
+    /// Timing of each word, when the provider reports it
+    #[serde(default)]
+    pub words: Vec<TurnWord>,
+    /// Spoken language code (e.g. "de"), when known
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// A word of a turn, timed from the start of the session (as in its recording)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TurnWord {
+    pub text: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub confidence: f64,
+}
+
+impl TurnData {
+    pub fn new(turn_order: usize, text: String, confidence: f64) -> Self {
+        Self {
+            turn_order,
+            text,
+            confidence,
+            speaker: None,
+            start_ms: None,
+            end_ms: None,
+            words: Vec::new(),
+            language: None,
+        }
+    }
+
+    /// Label the turn with who spoke it
+    pub fn with_speaker(mut self, speaker: Option<String>) -> Self {
+        self.speaker = speaker;
+        self
+    }
+
+    /// Set when the turn was spoken, so turns from separate tracks interleave correctly
+    pub fn with_span(mut self, start_ms: u32, end_ms: u32) -> Self {
+        self.start_ms = Some(start_ms);
+        self.end_ms = Some(end_ms);
+        self
+    }
+
+    /// Keep the timing of each word
+    pub fn with_words(mut self, words: Vec<TurnWord>) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Record the language the turn was spoken in
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Transcript line for the turn, naming the speaker when known
+    fn transcript_line(&self) -> String {
+        match &self.speaker {
+            Some(speaker) => format!("{}: {}", speaker, self.text),
+            None => self.text.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +114,21 @@ pub struct SessionMetadata {
     pub turn_count: usize,
     pub total_confidence: f64,
     pub confidence_count: usize,
+    /// Fraction of the captured audio detected as silence (0.0 - 1.0)
+    #[serde(default)]
+    pub silence_ratio: f64,
+    /// Off-the-record stretches, in order
+    #[serde(default)]
+    pub paused_intervals: Vec<PausedInterval>,
+    /// Per-speaker breakdown, in order of first appearance
+    #[serde(default)]
+    pub speakers: Vec<SpeakerStats>,
+    /// Words spoken per language code, in order of first appearance
+    #[serde(default)]
+    pub language_word_counts: Vec<(String, usize)>,
+    /// Sessions opened with the transcription provider, in order
+    #[serde(default)]
+    pub provider_sessions: Vec<ProviderSession>,
 }
 
 impl SessionMetadata {
@@ -49,6 +141,43 @@ impl SessionMetadata {
         }
     }
 
+    /// Stats for `label`, added if this is the speaker's first turn
+    fn speaker_mut(&mut self, label: &str) -> &mut SpeakerStats {
+        let index = match self.speakers.iter().position(|s| s.label == label) {
+            Some(index) => index,
+            None => {
+                self.speakers.push(SpeakerStats::new(label.to_string()));
+                self.speakers.len() - 1
+            }
+        };
+        &mut self.speakers[index]
+    }
+
+    /// Record the provider's figures for a session that has ended
+    pub fn end_provider_session(
+        &mut self,
+        id: &str,
+        audio_duration_seconds: f64,
+        session_duration_seconds: f64,
+    ) {
+        match self.provider_sessions.iter_mut().find(|s| s.id == id) {
+            Some(session) => {
+                session.audio_duration_seconds = Some(audio_duration_seconds);
+                session.session_duration_seconds = Some(session_duration_seconds);
+            }
+            None => tracing::warn!("Provider session {} ended but was never started", id),
+        }
+    }
+
+    /// The language most words were spoken in
+    pub fn language(&self) -> Option<String> {
+        self.language_word_counts
+            .iter()
+            .rev()
+            .max_by_key(|(_, words)| *words)
+            .map(|(language, _)| language.clone())
+    }
+
     pub fn to_recording_metadata(&self) -> RecordingMetadata {
         RecordingMetadata {
             duration_seconds: self.duration_seconds,
@@ -56,6 +185,11 @@ impl SessionMetadata {
             chunk_count: self.chunk_count,
             turn_count: self.turn_count,
             average_confidence: self.average_confidence(),
+            speakers: self.speakers.clone(),
+            language: self.language(),
+            provider_sessions: self.provider_sessions.clone(),
+            silence_ratio: Some(self.silence_ratio),
+            paused_intervals: self.paused_intervals.clone(),
         }
     }
 }
@@ -70,6 +204,7 @@ impl Default for SessionData {
             enhanced_buffers: Vec::new(),
             start_time: std::time::SystemTime::now(),
             metadata: SessionMetadata::default(),
+            audio_path: None,
         }
     }
 }
@@ -82,27 +217,64 @@ impl SessionData {
         }
     }
 
-    /// Add a turn to the session
-    pub fn add_turn(&mut self, turn_order: usize, text: String, confidence: f64) {
-        self.turns.push(TurnData {
-            turn_order,
-            text: text.clone(),
-            confidence,
-        });
-
-        // Update metadata
-        self.metadata.turn_count = self.turns.len();
-        self.metadata.total_confidence += confidence;
+    /// Add a turn to the session.
+    ///
+    /// Tracks are transcribed independently, so a turn can finish after one that
+    /// started later on another track. Timed turns are placed by start time,
+    /// which must be session time: each track's stream skips a different
+    /// amount of silence. Untimed turns are kept in arrival order.
+    pub fn add_turn(&mut self, turn: TurnData) {
+        // Update word count
+        let word_count = turn.text.split_whitespace().count();
+        self.metadata.word_count += word_count;
+        self.metadata.total_confidence += turn.confidence;
         self.metadata.confidence_count += 1;
 
-        // Update raw transcript
-        if !self.raw_transcript.is_empty() {
-            self.raw_transcript.push(' ');
+        if let Some(label) = &turn.speaker {
+            let stats = self.metadata.speaker_mut(label);
+            stats.word_count += word_count;
+            stats.turn_count += 1;
+            if let (Some(start), Some(end)) = (turn.start_ms, turn.end_ms) {
+                stats.talk_time_seconds += end.saturating_sub(start) as f64 / 1000.0;
+            }
         }
-        self.raw_transcript.push_str(&text);
 
-        // Update word count
-        self.metadata.word_count = self.raw_transcript.split_whitespace().count();
+        if let Some(language) = &turn.language {
+            let counts = &mut self.metadata.language_word_counts;
+            match counts.iter_mut().find(|(code, _)| code == language) {
+                Some((_, words)) => *words += word_count,
+                None => counts.push((language.clone(), word_count)),
+            }
+        }
+
+        let position = match turn.start_ms {
+            Some(start) => self
+                .turns
+                .iter()
+                .rposition(|t| t.start_ms.is_none_or(|s| s <= start))
+                .map_or(0, |i| i + 1),
+            None => self.turns.len(),
+        };
+
+        if position == self.turns.len() {
+            // Update raw transcript
+            if !self.raw_transcript.is_empty() {
+                self.raw_transcript.push(' ');
+            }
+            self.raw_transcript.push_str(&turn.transcript_line());
+            self.turns.push(turn);
+        } else {
+            self.turns.insert(position, turn);
+            self.raw_transcript = self
+                .turns
+                .iter()
+                .map(TurnData::transcript_line)
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+
+        // Update metadata
+        self.metadata.turn_count = self.turns.len();
     }
 
     /// Add an enhanced buffer to the session
@@ -127,6 +299,47 @@ impl SessionData {
             .join(" ");
     }
 
+    /// Seconds since the session started
+    fn elapsed_secs(&self) -> f64 {
+        self.start_time
+            .elapsed()
+            .map(|elapsed| elapsed.as_secs_f64())
+            .unwrap_or(0.0)
+    }
+
+    /// Whether the session is currently paused
+    pub fn is_paused(&self) -> bool {
+        self.metadata
+            .paused_intervals
+            .last()
+            .is_some_and(|interval| interval.end_secs.is_none())
+    }
+
+    /// Start a paused interval
+    pub fn pause(&mut self) -> Result<f64, String> {
+        if self.is_paused() {
+            return Err("Session is already paused".to_string());
+        }
+        let start_secs = self.elapsed_secs();
+        self.metadata.paused_intervals.push(PausedInterval {
+            start_secs,
+            end_secs: None,
+        });
+        Ok(start_secs)
+    }
+
+    /// End the current paused interval
+    pub fn resume(&mut self) -> Result<f64, String> {
+        let end_secs = self.elapsed_secs();
+        match self.metadata.paused_intervals.last_mut() {
+            Some(interval) if interval.end_secs.is_none() => {
+                interval.end_secs = Some(end_secs);
+                Ok(end_secs)
+            }
+            _ => Err("Session is not paused".to_string()),
+        }
+    }
+
     /// Update session duration
     pub fn update_duration(&mut self) {
         if let Ok(elapsed) = self.start_time.elapsed() {
@@ -146,6 +359,23 @@ impl SessionData {
         self.turns.is_empty() && self.enhanced_buffers.is_empty()
     }
 
+    /// Every timed word, in transcript order, for saving with a recording
+    pub fn recording_words(&self) -> Vec<RecordingWord> {
+        self.turns
+            .iter()
+            .flat_map(|turn| {
+                turn.words.iter().map(|word| RecordingWord {
+                    turn_order: turn.turn_order,
+                    speaker: turn.speaker.clone(),
+                    text: word.text.clone(),
+                    start_ms: word.start_ms,
+                    end_ms: word.end_ms,
+                    confidence: word.confidence,
+                })
+            })
+            .collect()
+    }
+
     /// Prepare session data for saving as a recording
     #[allow(dead_code)]
     pub fn to_recording(&self, project_id: String, name: String) -> Result<Recording, String> {
@@ -166,7 +396,8 @@ impl SessionData {
             self.raw_transcript.clone(),
             transcript_to_save,
         )
-        .with_metadata(self.metadata.to_recording_metadata()))
+        .with_metadata(self.metadata.to_recording_metadata())
+        .with_audio_path(self.audio_path.clone()))
     }
 }
 
@@ -183,10 +414,14 @@ impl SessionManager {
         }
     }
 
-    /// Start a new session
+    /// Start a new session. A session still open here was never saved, so
+    /// its audio is deleted as when it is cleared.
     pub async fn start_session(&self, project_id: Option<String>) {
         let mut session = self.current_session.lock().await;
-        *session = Some(SessionData::new(project_id));
+        let replaced = session.replace(SessionData::new(project_id));
+        if let Some(audio_path) = replaced.and_then(|s| s.audio_path) {
+            super::recording_commands::remove_session_audio(&audio_path);
+        }
     }
 
     /// Get a clone of the current session data
@@ -196,22 +431,35 @@ impl SessionManager {
     }
 
     /// Update the current session with a turn
-    pub async fn add_turn(
-        &self,
-        turn_order: usize,
-        text: String,
-        confidence: f64,
-    ) -> Result<(), String> {
+    pub async fn add_turn(&self, turn: TurnData) -> Result<(), String> {
         let mut session = self.current_session.lock().await;
         match session.as_mut() {
             Some(s) => {
-                s.add_turn(turn_order, text, confidence);
+                s.add_turn(turn);
                 Ok(())
             }
             None => Err("No active session".to_string()),
         }
     }
 
+    /// Pause the current session, returning the offset of the pause in seconds
+    pub async fn pause(&self) -> Result<f64, String> {
+        let mut session = self.current_session.lock().await;
+        match session.as_mut() {
+            Some(s) => s.pause(),
+            None => Err("No active session".to_string()),
+        }
+    }
+
+    /// Resume the current session, returning the offset of the resume in seconds
+    pub async fn resume(&self) -> Result<f64, String> {
+        let mut session = self.current_session.lock().await;
+        match session.as_mut() {
+            Some(s) => s.resume(),
+            None => Err("No active session".to_string()),
+        }
+    }
+
     /// Update the current session with an enhanced buffer
     pub async fn add_enhanced_buffer(
         &self,
@@ -275,6 +523,7 @@ impl Default for SessionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transcription::vad::{StreamTimeline, VadConfig, VoiceActivityDetector};
 
     #[tokio::test]
     async fn test_session_lifecycle() {
@@ -298,6 +547,25 @@ mod tests {
         assert!(!manager.has_active_session().await);
     }
 
+    #[tokio::test]
+    async fn test_replacing_unsaved_session_removes_its_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio_path = dir.path().join("session.wav");
+        std::fs::write(&audio_path, b"RIFF").unwrap();
+
+        let manager = SessionManager::new();
+        manager.start_session(None).await;
+        let path = audio_path.to_string_lossy().into_owned();
+        manager
+            .update_metadata(|session| session.audio_path = Some(path))
+            .await
+            .unwrap();
+
+        manager.start_session(None).await;
+        assert!(!audio_path.exists());
+        assert!(manager.get_session().await.unwrap().audio_path.is_none());
+    }
+
     #[tokio::test]
     async fn test_add_turns_to_session() {
         let manager = SessionManager::new();
@@ -305,11 +573,11 @@ mod tests {
 
         // Add turns
         manager
-            .add_turn(1, "First turn.".to_string(), 0.95)
+            .add_turn(TurnData::new(1, "First turn.".to_string(), 0.95))
             .await
             .unwrap();
         manager
-            .add_turn(2, "Second turn.".to_string(), 0.92)
+            .add_turn(TurnData::new(2, "Second turn.".to_string(), 0.92))
             .await
             .unwrap();
 
@@ -356,11 +624,15 @@ mod tests {
 
         // Add turns with different confidences
         manager
-            .add_turn(1, "Turn one with ten words in it here.".to_string(), 0.9)
+            .add_turn(TurnData::new(
+                1,
+                "Turn one with ten words in it here.".to_string(),
+                0.9,
+            ))
             .await
             .unwrap();
         manager
-            .add_turn(2, "Turn two.".to_string(), 0.8)
+            .add_turn(TurnData::new(2, "Turn two.".to_string(), 0.8))
             .await
             .unwrap();
 
@@ -389,7 +661,7 @@ mod tests {
     async fn test_session_to_recording() {
         let mut session = SessionData::new(Some("project-123".to_string()));
 
-        session.add_turn(1, "Hello world.".to_string(), 0.95);
+        session.add_turn(TurnData::new(1, "Hello world.".to_string(), 0.95));
         session.add_enhanced_buffer(1, "Hello world.".to_string(), "Hello, world!".to_string());
 
         let recording =
@@ -430,10 +702,238 @@ mod tests {
     async fn test_session_word_count_updates() {
         let mut session = SessionData::new(None);
 
-        session.add_turn(1, "One two three.".to_string(), 0.9);
+        session.add_turn(TurnData::new(1, "One two three.".to_string(), 0.9));
         assert_eq!(session.metadata.word_count, 3);
 
-        session.add_turn(2, "Four five.".to_string(), 0.9);
+        session.add_turn(TurnData::new(2, "Four five.".to_string(), 0.9));
         assert_eq!(session.metadata.word_count, 5);
     }
+
+    #[tokio::test]
+    async fn test_speaker_turns_are_labelled() {
+        let mut session = SessionData::new(None);
+
+        session.add_turn(
+            TurnData::new(1, "Can you hear me?".to_string(), 0.9)
+                .with_speaker(Some("me".to_string())),
+        );
+        session.add_turn(
+            TurnData::new(1, "Yes, loud and clear.".to_string(), 0.9)
+                .with_speaker(Some("them".to_string())),
+        );
+
+        assert_eq!(session.turns[1].speaker.as_deref(), Some("them"));
+        assert_eq!(
+            session.raw_transcript,
+            "me: Can you hear me? them: Yes, loud and clear."
+        );
+        // Labels are not counted as words
+        assert_eq!(session.metadata.word_count, 8);
+    }
+
+    #[tokio::test]
+    async fn test_speaker_stats_are_aggregated() {
+        let mut session = SessionData::new(None);
+        let speaker_a = Some("Speaker A".to_string());
+        let speaker_b = Some("Speaker B".to_string());
+
+        session.add_turn(
+            TurnData::new(0, "Shall we start?".to_string(), 0.9)
+                .with_speaker(speaker_a.clone())
+                .with_span(0, 1200),
+        );
+        session.add_turn(
+            TurnData::new(1, "Sure.".to_string(), 0.9)
+                .with_speaker(speaker_b)
+                .with_span(1500, 2000),
+        );
+        session.add_turn(
+            TurnData::new(2, "First the budget.".to_string(), 0.9)
+                .with_speaker(speaker_a)
+                .with_span(2500, 4000),
+        );
+        session.add_turn(TurnData::new(3, "Unattributed.".to_string(), 0.9));
+
+        let speakers = &session.metadata.speakers;
+        assert_eq!(speakers.len(), 2);
+        assert_eq!(speakers[0].label, "Speaker A");
+        assert_eq!(speakers[0].word_count, 6);
+        assert_eq!(speakers[0].turn_count, 2);
+        assert!((speakers[0].talk_time_seconds - 2.7).abs() < 1e-9);
+        assert_eq!(speakers[1].label, "Speaker B");
+        assert!((speakers[1].talk_time_seconds - 0.5).abs() < 1e-9);
+
+        assert_eq!(session.metadata.to_recording_metadata().speakers, *speakers);
+    }
+
+    #[test]
+    fn test_provider_sessions_are_recorded() {
+        let mut metadata = SessionMetadata::default();
+        metadata.provider_sessions.push(ProviderSession::new(
+            "session-1".to_string(),
+            None,
+            1_700_000_000,
+        ));
+        metadata.provider_sessions.push(ProviderSession::new(
+            "session-2".to_string(),
+            None,
+            1_700_000_600,
+        ));
+
+        metadata.end_provider_session("session-1", 10.5, 11.0);
+        metadata.end_provider_session("unknown", 1.0, 1.0);
+
+        let sessions = metadata.to_recording_metadata().provider_sessions;
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].audio_duration_seconds, Some(10.5));
+        assert_eq!(sessions[0].session_duration_seconds, Some(11.0));
+        assert_eq!(sessions[1].audio_duration_seconds, None);
+    }
+
+    #[tokio::test]
+    async fn test_session_language_follows_most_words() {
+        let mut session = SessionData::new(None);
+        assert_eq!(session.metadata.language(), None);
+
+        session.add_turn(
+            TurnData::new(0, "Good morning everyone.".to_string(), 0.9)
+                .with_language(Some("en".to_string())),
+        );
+        session.add_turn(
+            TurnData::new(
+                1,
+                "Guten Morgen, fangen wir mit dem Budget an.".to_string(),
+                0.9,
+            )
+            .with_language(Some("de".to_string())),
+        );
+        session.add_turn(TurnData::new(2, "Untagged turn here.".to_string(), 0.9));
+
+        assert_eq!(
+            session.metadata.to_recording_metadata().language.as_deref(),
+            Some("de")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recording_words_follow_turn_order() {
+        let mut session = SessionData::new(None);
+        let word = |text: &str, start_ms, end_ms| TurnWord {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: 0.9,
+        };
+
+        session.add_turn(
+            TurnData::new(1, "See you.".to_string(), 0.9)
+                .with_speaker(Some("them".to_string()))
+                .with_span(3000, 3600)
+                .with_words(vec![word("See", 3000, 3200), word("you.", 3250, 3600)]),
+        );
+        session.add_turn(
+            TurnData::new(1, "Bye.".to_string(), 0.9)
+                .with_speaker(Some("me".to_string()))
+                .with_span(1000, 1400)
+                .with_words(vec![word("Bye.", 1000, 1400)]),
+        );
+
+        let words = session.recording_words();
+        let texts: Vec<_> = words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, ["Bye.", "See", "you."]);
+        assert_eq!(words[1].speaker.as_deref(), Some("them"));
+        assert_eq!((words[2].start_ms, words[2].end_ms), (3250, 3600));
+    }
+
+    #[tokio::test]
+    async fn test_turns_from_separate_tracks_merge_chronologically() {
+        let mut session = SessionData::new(None);
+
+        // A long turn on channel 2 finishes after a reply on channel 1 started
+        session.add_turn(
+            TurnData::new(1, "Hi.".to_string(), 0.9)
+                .with_speaker(Some("channel 1".to_string()))
+                .with_span(0, 400),
+        );
+        session.add_turn(
+            TurnData::new(1, "Sorry I'm late.".to_string(), 0.9)
+                .with_speaker(Some("channel 1".to_string()))
+                .with_span(5000, 6000),
+        );
+        session.add_turn(
+            TurnData::new(1, "Let's start with the budget.".to_string(), 0.9)
+                .with_speaker(Some("channel 2".to_string()))
+                .with_span(1000, 5500),
+        );
+
+        let speakers: Vec<_> = session
+            .turns
+            .iter()
+            .map(|t| t.speaker.as_deref().unwrap())
+            .collect();
+        assert_eq!(speakers, ["channel 1", "channel 2", "channel 1"]);
+        assert_eq!(
+            session.raw_transcript,
+            "channel 1: Hi. channel 2: Let's start with the budget. channel 1: Sorry I'm late."
+        );
+        assert_eq!(session.metadata.turn_count, 3);
+        assert_eq!(session.metadata.word_count, 9);
+    }
+
+    #[test]
+    fn test_tracks_merge_on_session_time() {
+        // "them" talks for the first 4s; "me" is silent until 4.5s, which VAD
+        // mostly skips, so the reply comes early in the "me" stream
+        let mut me = VoiceActivityDetector::new(VadConfig::default(), 16000);
+        let mut them = VoiceActivityDetector::new(VadConfig::default(), 16000);
+        let (me_timeline, them_timeline) = (me.timeline(), them.timeline());
+        for chunk in 0..120 {
+            let level = |speaking: bool| if speaking { 8000 } else { 0 };
+            me.process(vec![level(chunk >= 90); 800]);
+            them.process(vec![level(chunk < 80); 800]);
+        }
+
+        let turn = |speaker: &str, text: &str, timeline: &StreamTimeline, start, end| {
+            TurnData::new(0, text.to_string(), 0.9)
+                .with_speaker(Some(speaker.to_string()))
+                .with_span(timeline.session_ms(start), timeline.session_end_ms(end))
+        };
+        let mut session = SessionData::new(None);
+        session.add_turn(turn("them", "First point.", &them_timeline, 0, 1800));
+        // Stream time 300ms, but 4.5s into the session
+        session.add_turn(turn("me", "Agreed.", &me_timeline, 300, 1000));
+        session.add_turn(turn("them", "Second point.", &them_timeline, 2000, 4000));
+
+        let texts: Vec<_> = session.turns.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["First point.", "Second point.", "Agreed."]);
+        assert_eq!(session.turns[2].start_ms, Some(4500));
+    }
+
+    #[tokio::test]
+    async fn test_paused_intervals_are_recorded() {
+        let mut session = SessionData::new(None);
+        assert!(!session.is_paused());
+        assert!(session.resume().is_err());
+
+        let start = session.pause().unwrap();
+        assert!(session.is_paused());
+        assert!(session.pause().is_err());
+
+        let end = session.resume().unwrap();
+        assert!(!session.is_paused());
+        assert!(end >= start);
+
+        session.pause().unwrap();
+        session.resume().unwrap();
+
+        let intervals = &session.metadata.paused_intervals;
+        assert_eq!(intervals.len(), 2);
+        assert!(intervals.iter().all(|i| i.end_secs.is_some()));
+
+        // Kept when the session is saved as a recording
+        session.metadata.silence_ratio = 0.4;
+        let metadata = session.metadata.to_recording_metadata();
+        assert_eq!(&metadata.paused_intervals, intervals);
+        assert_eq!(metadata.silence_ratio, Some(0.4));
+    }
 }
@@ -0,0 +1,682 @@
+//! # Audio Sources
+//!
+//! The transcription pipeline only cares about a stream of mono 16-bit PCM chunks at
+//! a known sample rate. [`AudioSource`] captures that contract so live microphones,
+//! recorded files, synthetic tones and network streams are interchangeable, and the
+//! pipeline can run without audio hardware (e.g. on CI machines).
+//!
+//! ## Implementations
+//!
+//! - [`AudioCapture`](super::audio::AudioCapture) - live input device via cpal
+//! - [`FilePlayback`](super::audio_file::FilePlayback) - WAV/PCM file replay
+//! - [`ToneSource`] - synthetic sine tone, mainly for tests
+//! - [`PcmStreamSource`] - raw s16le PCM from any reader, e.g. a TCP socket
+//! - [`DualCapture`](super::dual::DualCapture) - microphone plus system loopback
+//!
+//! ## Tracks
+//!
+//! A source may produce several tracks that are transcribed separately, such as
+//! "me" and "them" on a call or the channels of a multi-channel recorder. Their
+//! samples are interleaved within each chunk (like channels in a WAV file) and
+//! [`AudioSource::track_labels`] names them.
+
+use serde::{Deserialize, Serialize};
+use std::io::{ErrorKind, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::audio::{self, AudioCapture, AudioCaptureHandle, InputConfig, CHUNK_DURATION_MS};
+use super::dsp::DspConfig;
+use super::dual::{DualCapture, DualCaptureMode};
+
+/// A producer of i16 PCM chunks at a fixed sample rate (mono unless the source
+/// has several tracks).
+pub trait AudioSource: Send {
+    /// Sample rate of the chunks this source produces
+    fn sample_rate(&self) -> u32;
+
+    /// Short human-readable description for logs and events
+    fn describe(&self) -> String;
+
+    /// Whether the source ends on its own (files, finite tones).
+    /// Finite sources close the chunk channel when exhausted, which ends the
+    /// transcription session naturally.
+    fn is_finite(&self) -> bool {
+        false
+    }
+
+    /// Speaker labels of the interleaved tracks in each chunk.
+    /// Empty for the usual single mono track.
+    fn track_labels(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Start producing chunks into `chunk_sender` on a dedicated thread.
+    /// Returns a handle that stops the producer.
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String>;
+}
+
+/// Lowest sample rate a source may be configured with (telephony audio)
+pub const MIN_SAMPLE_RATE: u32 = 8_000;
+
+/// Highest sample rate a source may be configured with
+pub const MAX_SAMPLE_RATE: u32 = 192_000;
+
+/// Check a configured sample rate, naming the setting in the error
+pub fn validate_sample_rate(name: &str, sample_rate: u32) -> Result<(), String> {
+    if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&sample_rate) {
+        return Err(format!(
+            "{} must be between {} and {} Hz (got {})",
+            name, MIN_SAMPLE_RATE, MAX_SAMPLE_RATE, sample_rate
+        ));
+    }
+    Ok(())
+}
+
+/// How long a PCM stream read waits before checking whether it was stopped
+const READ_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Source selection passed from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AudioSourceConfig {
+    /// Live input device, as listed by `list_audio_devices`
+    Device {
+        device_id: String,
+        /// Rate to resample to before streaming, defaults to 16kHz
+        #[serde(default)]
+        target_sample_rate: Option<u32>,
+        /// Device configuration to capture with, defaults to the device default
+        #[serde(default)]
+        input_config: Option<InputConfig>,
+        /// Transcribe each channel as its own speaker instead of downmixing
+        #[serde(default)]
+        separate_channels: bool,
+        /// Input conditioning, defaults to none
+        #[serde(default = "DspConfig::bypass")]
+        dsp: DspConfig,
+    },
+    /// Synthetic sine tone; runs until stopped unless `duration_secs` is set
+    Tone {
+        sample_rate: u32,
+        frequency_hz: f32,
+        /// Relative to full scale, defaults to 0.5
+        amplitude: Option<f32>,
+        duration_secs: Option<f32>,
+    },
+    /// Raw s16le mono PCM pushed over TCP
+    PcmStream { address: String, sample_rate: u32 },
+    /// Microphone and system audio captured together. The loopback device is
+    /// either an input (e.g. a PulseAudio/PipeWire monitor) or an output device
+    /// on hosts that support loopback capture.
+    Dual {
+        mic_device_id: String,
+        loopback_device_id: String,
+        #[serde(default)]
+        mode: DualCaptureMode,
+        #[serde(default)]
+        target_sample_rate: Option<u32>,
+    },
+}
+
+impl AudioSourceConfig {
+    /// Check the settings without opening anything
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Device {
+                target_sample_rate, ..
+            }
+            | Self::Dual {
+                target_sample_rate, ..
+            } => {
+                if let Some(rate) = target_sample_rate {
+                    validate_sample_rate("Target sample rate", *rate)?;
+                }
+            }
+            Self::Tone {
+                sample_rate,
+                frequency_hz,
+                amplitude,
+                duration_secs,
+            } => {
+                validate_sample_rate("Tone sample rate", *sample_rate)?;
+                let nyquist = *sample_rate as f32 / 2.0;
+                if !(*frequency_hz > 0.0 && *frequency_hz < nyquist) {
+                    return Err(format!(
+                        "Tone frequency must be between 0 and {} Hz (got {})",
+                        nyquist, frequency_hz
+                    ));
+                }
+                if amplitude.is_some_and(|a| !(0.0..=1.0).contains(&a)) {
+                    return Err("Tone amplitude must be between 0.0 and 1.0".to_string());
+                }
+                if duration_secs.is_some_and(|d| !(d.is_finite() && d > 0.0)) {
+                    return Err("Tone duration must be a positive number of seconds".to_string());
+                }
+            }
+            Self::PcmStream { sample_rate, .. } => {
+                validate_sample_rate("PCM stream sample rate", *sample_rate)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate and open the configured source
+    pub fn open(&self) -> Result<Box<dyn AudioSource>, String> {
+        self.validate()?;
+        match self {
+            Self::Device {
+                device_id,
+                target_sample_rate,
+                input_config,
+                separate_channels,
+                dsp,
+            } => {
+                // Get the audio device and validate it's an input device
+                if device_id.starts_with("output_") {
+                    return Err(
+                        "Cannot use output devices (speakers) for recording. Please select an input device (microphone) instead. \
+                        Note: To capture system audio on macOS, you need special loopback software like BlackHole or Soundflower."
+                            .to_string(),
+                    );
+                }
+                let device = audio::get_device_by_id(device_id)?;
+                let mut capture = match input_config {
+                    Some(config) => AudioCapture::new_with_config(device, config)?,
+                    None => AudioCapture::new(device)?,
+                };
+                if let Some(rate) = target_sample_rate {
+                    capture = capture.with_target_sample_rate(*rate);
+                }
+                if *separate_channels {
+                    capture = capture.with_separate_channels();
+                }
+                Ok(Box::new(capture.with_dsp(dsp.clone())))
+            }
+            Self::Tone {
+                sample_rate,
+                frequency_hz,
+                amplitude,
+                duration_secs,
+            } => {
+                let mut tone = ToneSource::new(*sample_rate).with_frequency(*frequency_hz);
+                if let Some(amplitude) = amplitude {
+                    tone = tone.with_amplitude(*amplitude);
+                }
+                if let Some(secs) = duration_secs {
+                    tone = tone.with_duration(Duration::from_secs_f32(secs.max(0.0)));
+                }
+                Ok(Box::new(tone))
+            }
+            Self::PcmStream {
+                address,
+                sample_rate,
+            } => Ok(Box::new(PcmStreamSource::connect_tcp(
+                address.as_str(),
+                *sample_rate,
+            )?)),
+            Self::Dual {
+                mic_device_id,
+                loopback_device_id,
+                mode,
+                target_sample_rate,
+            } => {
+                if mic_device_id.starts_with("output_") {
+                    return Err("The microphone must be an input device".to_string());
+                }
+                let mut mic = AudioCapture::new(audio::get_device_by_id(mic_device_id)?)?;
+                let loopback_device = audio::get_device_by_id(loopback_device_id)?;
+                let mut loopback = if loopback_device_id.starts_with("output_") {
+                    AudioCapture::new_loopback(loopback_device)?
+                } else {
+                    AudioCapture::new(loopback_device)?
+                };
+                if let Some(rate) = target_sample_rate {
+                    mic = mic.with_target_sample_rate(*rate);
+                    loopback = loopback.with_target_sample_rate(*rate);
+                }
+                Ok(Box::new(DualCapture::new(mic, loopback, *mode)?))
+            }
+        }
+    }
+}
+
+/// Number of samples in one streaming chunk at `sample_rate`
+pub fn chunk_size_for(sample_rate: u32) -> usize {
+    ((sample_rate as f32 * CHUNK_DURATION_MS) / 1000.0) as usize
+}
+
+/// Run a chunk generator on a dedicated thread.
+///
+/// `next_chunk` is called until it returns `None` or the handle is stopped. It
+/// gets the stop flag, so a producer that blocks (e.g. on a socket) can give up
+/// once stopped. When `realtime` is set, chunks are paced against the wall
+/// clock so that audio arrives at the rate it would from a live device.
+pub(crate) fn spawn_producer<F>(
+    sample_rate: u32,
+    realtime: bool,
+    chunk_sender: mpsc::Sender<Vec<i16>>,
+    mut next_chunk: F,
+) -> AudioCaptureHandle
+where
+    F: FnMut(&AtomicBool) -> Option<Vec<i16>> + Send + 'static,
+{
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let thread_stop_flag = stop_flag.clone();
+
+    let thread_handle = thread::spawn(move || {
+        let started = Instant::now();
+        let mut samples_sent = 0u64;
+
+        while !thread_stop_flag.load(Ordering::Acquire) {
+            let Some(chunk) = next_chunk(&thread_stop_flag) else {
+                break;
+            };
+            samples_sent += chunk.len() as u64;
+
+            // Block rather than drop: generated audio must arrive intact
+            if chunk_sender.blocking_send(chunk).is_err() {
+                tracing::info!("Chunk receiver closed - stopping audio producer");
+                return;
+            }
+
+            // Pace against the wall clock so drift does not accumulate
+            if realtime {
+                let deadline =
+                    started + Duration::from_secs_f64(samples_sent as f64 / sample_rate as f64);
+                if let Some(wait) = deadline.checked_duration_since(Instant::now()) {
+                    thread::sleep(wait);
+                }
+            }
+        }
+    });
+
+    AudioCaptureHandle::from_thread(thread_handle, stop_flag)
+}
+
+/// Synthetic sine tone source.
+///
+/// Useful for exercising the whole pipeline without a microphone.
+#[derive(Debug, Clone)]
+pub struct ToneSource {
+    sample_rate: u32,
+    frequency_hz: f32,
+    amplitude: f32,
+    duration: Option<Duration>,
+    realtime: bool,
+}
+
+impl ToneSource {
+    /// A 440 Hz tone at half scale, running until stopped
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            frequency_hz: 440.0,
+            amplitude: 0.5,
+            duration: None,
+            realtime: true,
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency_hz: f32) -> Self {
+        self.frequency_hz = frequency_hz;
+        self
+    }
+
+    /// Amplitude relative to full scale (0.0 - 1.0)
+    pub fn with_amplitude(mut self, amplitude: f32) -> Self {
+        self.amplitude = amplitude.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Stop after `duration` instead of running until stopped
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Produce chunks as fast as the receiver accepts them instead of in real time
+    #[cfg(test)]
+    pub fn unpaced(mut self) -> Self {
+        self.realtime = false;
+        self
+    }
+}
+
+impl AudioSource for ToneSource {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn describe(&self) -> String {
+        format!("{}Hz test tone", self.frequency_hz)
+    }
+
+    fn is_finite(&self) -> bool {
+        self.duration.is_some()
+    }
+
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String> {
+        if self.sample_rate == 0 {
+            return Err("Tone sample rate must be greater than zero".to_string());
+        }
+
+        let chunk_size = chunk_size_for(self.sample_rate);
+        let total_samples = self
+            .duration
+            .map(|d| (d.as_secs_f64() * self.sample_rate as f64) as u64);
+        let step = 2.0 * std::f32::consts::PI * self.frequency_hz / self.sample_rate as f32;
+        let scale = self.amplitude * 32767.0;
+        let mut position = 0u64;
+
+        Ok(spawn_producer(
+            self.sample_rate,
+            self.realtime,
+            chunk_sender,
+            move |_| {
+                let remaining = total_samples.map_or(chunk_size as u64, |total| total - position);
+                let len = remaining.min(chunk_size as u64) as usize;
+                if len == 0 {
+                    return None;
+                }
+
+                let chunk = (0..len as u64)
+                    .map(|i| (((position + i) as f32 * step).sin() * scale) as i16)
+                    .collect();
+                position += len as u64;
+                Some(chunk)
+            },
+        ))
+    }
+}
+
+/// Raw PCM (signed 16-bit little endian, mono) read from any byte stream.
+///
+/// The producer on the other end sets the pace, so chunks are forwarded as soon
+/// as they are read. Covers network streams such as RTP bridges or SIP recorders
+/// that push PCM over TCP.
+///
+/// Readers that time out (see [`connect_tcp`](Self::connect_tcp)) let the source
+/// stop while the other end is quiet; others stop at their next read.
+pub struct PcmStreamSource<R: Read + Send + 'static> {
+    reader: R,
+    sample_rate: u32,
+    description: String,
+}
+
+impl<R: Read + Send + 'static> PcmStreamSource<R> {
+    pub fn new(reader: R, sample_rate: u32) -> Self {
+        Self {
+            reader,
+            sample_rate,
+            description: "PCM stream".to_string(),
+        }
+    }
+}
+
+impl PcmStreamSource<TcpStream> {
+    /// Connect to a TCP endpoint that streams raw PCM
+    pub fn connect_tcp<A: ToSocketAddrs + std::fmt::Display>(
+        addr: A,
+        sample_rate: u32,
+    ) -> Result<Self, String> {
+        let description = format!("PCM stream from {}", addr);
+        let stream = TcpStream::connect(&addr)
+            .map_err(|e| format!("Failed to connect to {}: {}", addr, e))?;
+        // Wake up regularly to notice a stop while the peer sends nothing
+        stream
+            .set_read_timeout(Some(READ_POLL_INTERVAL))
+            .map_err(|e| format!("Failed to configure {}: {}", addr, e))?;
+
+        Ok(Self {
+            description,
+            ..Self::new(stream, sample_rate)
+        })
+    }
+}
+
+impl<R: Read + Send + 'static> AudioSource for PcmStreamSource<R> {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+
+    fn is_finite(&self) -> bool {
+        // The stream ends when the remote side closes the connection
+        true
+    }
+
+    fn start(
+        self: Box<Self>,
+        chunk_sender: mpsc::Sender<Vec<i16>>,
+    ) -> Result<AudioCaptureHandle, String> {
+        let chunk_size = chunk_size_for(self.sample_rate);
+        let mut reader = self.reader;
+        let mut bytes = vec![0u8; chunk_size * 2];
+
+        Ok(spawn_producer(
+            self.sample_rate,
+            false,
+            chunk_sender,
+            move |stop| {
+                // Fill a whole chunk unless the stream ends first
+                let mut filled = 0;
+                while filled < bytes.len() {
+                    match reader.read(&mut bytes[filled..]) {
+                        Ok(0) => break,
+                        Ok(n) => filled += n,
+                        Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                        Err(e)
+                            if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                        {
+                            if stop.load(Ordering::Acquire) {
+                                tracing::info!("PCM stream stopped while waiting for data");
+                                return None;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("PCM stream read error: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                if filled < 2 {
+                    tracing::info!("PCM stream ended");
+                    return None;
+                }
+
+                Some(
+                    bytes[..filled - filled % 2]
+                        .chunks_exact(2)
+                        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                        .collect(),
+                )
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    async fn collect(source: Box<dyn AudioSource>) -> Vec<Vec<i16>> {
+        let (tx, mut rx) = mpsc::channel(4);
+        let handle = source.start(tx).unwrap();
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            chunks.push(chunk);
+        }
+        handle.stop().unwrap();
+        chunks
+    }
+
+    #[tokio::test]
+    async fn test_tone_source_produces_expected_duration() {
+        let source = ToneSource::new(16000)
+            .with_duration(Duration::from_millis(500))
+            .unpaced();
+        assert!(source.is_finite());
+
+        let chunks = collect(Box::new(source)).await;
+
+        // 500ms of 50ms chunks at 16kHz
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks.iter().all(|c| c.len() == 800));
+        assert!(chunks.iter().flatten().any(|&s| s.abs() > 10000));
+    }
+
+    #[tokio::test]
+    async fn test_pcm_stream_source_reads_until_eof() {
+        let samples: Vec<i16> = (0..1000).map(|i| i as i16).collect();
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let source = PcmStreamSource::new(Cursor::new(bytes), 16000);
+
+        let chunks = collect(Box::new(source)).await;
+
+        assert_eq!(chunks.len(), 2); // 800 + 200 samples
+        assert_eq!(chunks.concat(), samples);
+    }
+
+    #[tokio::test]
+    async fn test_stopping_quiet_pcm_stream() {
+        // A peer that connects but never sends anything
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let peer = thread::spawn(move || listener.accept().unwrap());
+
+        let source = PcmStreamSource::connect_tcp(address, 16000).unwrap();
+        let (tx, _rx) = mpsc::channel(4);
+        let handle = Box::new(source).start(tx).unwrap();
+        let _connection = peer.join().unwrap();
+
+        let stopped = tokio::task::spawn_blocking(move || handle.stop());
+        tokio::time::timeout(Duration::from_secs(5), stopped)
+            .await
+            .expect("stopping a quiet PCM stream hung")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_source_config_deserializes_tagged() {
+        let config: AudioSourceConfig = serde_json::from_str(
+            r#"{"type":"tone","sample_rate":16000,"frequency_hz":440.0,"duration_secs":1.0}"#,
+        )
+        .unwrap();
+        let source = config.open().unwrap();
+
+        assert_eq!(source.sample_rate(), 16000);
+        assert!(source.is_finite());
+    }
+
+    #[test]
+    fn test_source_config_validation() {
+        let tone = |sample_rate, frequency_hz| AudioSourceConfig::Tone {
+            sample_rate,
+            frequency_hz,
+            amplitude: None,
+            duration_secs: Some(1.0),
+        };
+        assert!(tone(16000, 440.0).validate().is_ok());
+        assert!(tone(0, 440.0).validate().is_err());
+        assert!(tone(10_000_000, 440.0).validate().is_err());
+        assert!(tone(16000, 9000.0).validate().is_err());
+        assert!(tone(16000, f32::NAN).validate().is_err());
+
+        let pcm = |sample_rate| AudioSourceConfig::PcmStream {
+            address: "127.0.0.1:1".to_string(),
+            sample_rate,
+        };
+        assert!(pcm(8000).validate().is_ok());
+        // Rejected before trying to connect
+        assert!(pcm(0).open().err().unwrap().contains("sample rate"));
+
+        let device = AudioSourceConfig::Device {
+            device_id: "input_0".to_string(),
+            target_sample_rate: Some(1),
+            input_config: None,
+            separate_channels: false,
+            dsp: DspConfig::default(),
+        };
+        assert!(device.validate().is_err());
+    }
+
+    #[test]
+    fn test_dual_config_defaults_to_separate_tracks() {
+        let config: AudioSourceConfig = serde_json::from_str(
+            r#"{"type":"dual","mic_device_id":"input_0","loopback_device_id":"input_1"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config,
+            AudioSourceConfig::Dual {
+                mode: DualCaptureMode::Tracks,
+                target_sample_rate: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_device_config_defaults_to_no_dsp() {
+        let config: AudioSourceConfig =
+            serde_json::from_str(r#"{"type":"device","device_id":"input_0"}"#).unwrap();
+
+        match config {
+            AudioSourceConfig::Device { dsp, .. } => assert_eq!(dsp, DspConfig::bypass()),
+            other => panic!("unexpected config: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_output_device_is_rejected() {
+        let config = AudioSourceConfig::Device {
+            device_id: "output_0".to_string(),
+            target_sample_rate: None,
+            input_config: None,
+            separate_channels: false,
+            dsp: DspConfig::default(),
+        };
+        assert!(config.open().is_err());
+    }
+
+    #[test]
+    fn test_chunk_size_for_common_rates() {
+        assert_eq!(chunk_size_for(16000), 800);
+        assert_eq!(chunk_size_for(48000), 2400);
+    }
+
+    #[tokio::test]
+    async fn test_stopping_infinite_source() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let handle = Box::new(ToneSource::new(16000).unpaced())
+            .start(tx)
+            .unwrap();
+
+        assert!(rx.recv().await.is_some());
+        // Drain concurrently so the producer is not blocked on a full channel
+        let drain = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+        tokio::task::spawn_blocking(move || handle.stop().unwrap())
+            .await
+            .unwrap();
+        drain.await.unwrap();
+    }
+}
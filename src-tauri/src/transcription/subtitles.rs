@@ -0,0 +1,451 @@
+//! # Subtitles
+//!
+//! Builds SRT and WebVTT captions from timed transcript words. Word times are
+//! in session time, so the captions line up with the saved recording.
+//!
+//! - **Cues** never span two turns, end at sentence boundaries, and are split
+//!   further whenever the text would need more lines than allowed or the cue
+//!   would stay on screen too long.
+//! - **Speakers** are named at the start of a cue whenever the speaker
+//!   changes, as `Name: ` in SRT and a `<v Name>` voice tag in WebVTT.
+
+use crate::database::RecordingWord;
+use serde::{Deserialize, Serialize};
+
+/// Caption file format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+/// Limits for splitting a transcript into cues
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SubtitleConfig {
+    /// Characters per line, including a speaker prefix
+    pub max_line_chars: usize,
+    /// Lines per cue
+    pub max_lines: usize,
+    /// Longest a cue stays on screen
+    pub max_cue_duration_ms: u32,
+    /// Shortest a cue stays on screen, unless the next cue starts sooner
+    pub min_cue_duration_ms: u32,
+    /// Name the speaker when it changes
+    pub speaker_labels: bool,
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            max_line_chars: 42,
+            max_lines: 2,
+            max_cue_duration_ms: 6000,
+            min_cue_duration_ms: 1000,
+            speaker_labels: true,
+        }
+    }
+}
+
+impl SubtitleConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(10..=200).contains(&self.max_line_chars) {
+            return Err(format!(
+                "Line length must be between 10 and 200 characters (got {})",
+                self.max_line_chars
+            ));
+        }
+        if !(1..=4).contains(&self.max_lines) {
+            return Err(format!(
+                "Lines per cue must be between 1 and 4 (got {})",
+                self.max_lines
+            ));
+        }
+        if self.max_cue_duration_ms < 1000 {
+            return Err(format!(
+                "Maximum cue duration must be at least 1000ms (got {}ms)",
+                self.max_cue_duration_ms
+            ));
+        }
+        if self.min_cue_duration_ms > self.max_cue_duration_ms {
+            return Err(format!(
+                "Minimum cue duration ({}ms) can't exceed the maximum ({}ms)",
+                self.min_cue_duration_ms, self.max_cue_duration_ms
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One caption, timed from the start of the recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_ms: u32,
+    pub end_ms: u32,
+    /// Set on the first cue of each speaker's stretch
+    pub speaker: Option<String>,
+    pub lines: Vec<String>,
+}
+
+/// Greedily wrap `text` into lines of at most `max_chars`, with the first
+/// `first_line_used` characters of the first line already taken (by a speaker
+/// prefix). A word longer than a line gets a line of its own.
+fn wrap(text: &str, max_chars: usize, first_line_used: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut used = first_line_used;
+
+    for word in text.split_whitespace() {
+        let width = word.chars().count();
+        if !current.is_empty() && used + 1 + width > max_chars {
+            lines.push(std::mem::take(&mut current));
+            used = 0;
+        } else if current.is_empty() && used > 0 && used + width > max_chars {
+            // Not even the first word fits after the prefix
+            lines.push(String::new());
+            used = 0;
+        }
+
+        if !current.is_empty() {
+            current.push(' ');
+            used += 1;
+        }
+        current.push_str(word);
+        used += width;
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', ')'])
+        .ends_with(['.', '?', '!'])
+}
+
+/// Words of a cue being built
+struct PendingCue<'a> {
+    words: Vec<&'a RecordingWord>,
+    speaker: Option<String>,
+}
+
+impl PendingCue<'_> {
+    fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|word| word.text.trim())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn prefix_len(&self) -> usize {
+        self.speaker
+            .as_ref()
+            .map_or(0, |speaker| speaker.chars().count() + 2)
+    }
+
+    fn into_cue(self, config: &SubtitleConfig) -> Cue {
+        let lines = wrap(&self.text(), config.max_line_chars, self.prefix_len());
+        Cue {
+            start_ms: self.words[0].start_ms,
+            end_ms: self.words[self.words.len() - 1].end_ms,
+            speaker: self.speaker,
+            lines,
+        }
+    }
+}
+
+/// Split timed words into cues. Each word may also be a longer timed unit,
+/// such as a whole turn. `speaker_name` maps a speaker label to the name shown.
+pub fn build_cues(
+    words: &[RecordingWord],
+    speaker_name: impl Fn(&str) -> String,
+    config: &SubtitleConfig,
+) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut pending: Option<PendingCue> = None;
+    let mut last_speaker: Option<&str> = None;
+
+    for word in words.iter().filter(|word| !word.text.trim().is_empty()) {
+        if let Some(cue) = pending.take() {
+            let first = cue.words[0];
+            let mut candidate = cue.text();
+            candidate.push(' ');
+            candidate.push_str(word.text.trim());
+
+            let fits = first.turn_order == word.turn_order
+                && first.speaker == word.speaker
+                && word.end_ms.saturating_sub(first.start_ms) <= config.max_cue_duration_ms
+                && wrap(&candidate, config.max_line_chars, cue.prefix_len()).len()
+                    <= config.max_lines;
+
+            if fits {
+                pending = Some(cue);
+            } else {
+                cues.push(cue.into_cue(config));
+            }
+        }
+
+        let cue = pending.get_or_insert_with(|| {
+            let speaker = word
+                .speaker
+                .as_deref()
+                .filter(|speaker| config.speaker_labels && last_speaker != Some(*speaker))
+                .map(&speaker_name);
+            last_speaker = word.speaker.as_deref();
+            PendingCue {
+                words: Vec::new(),
+                speaker,
+            }
+        });
+        cue.words.push(word);
+
+        if ends_sentence(&word.text) {
+            if let Some(cue) = pending.take() {
+                cues.push(cue.into_cue(config));
+            }
+        }
+    }
+    if let Some(cue) = pending {
+        cues.push(cue.into_cue(config));
+    }
+
+    // Give short cues time to be read, without overlapping the next one
+    for i in 0..cues.len() {
+        let limit = cues.get(i + 1).map_or(u32::MAX, |next| next.start_ms);
+        let cue = &mut cues[i];
+        let min_end = cue.start_ms.saturating_add(config.min_cue_duration_ms);
+        if cue.end_ms < min_end {
+            cue.end_ms = min_end.min(limit).max(cue.end_ms);
+        }
+    }
+
+    cues
+}
+
+/// `hh:mm:ss,mmm` for SRT, `hh:mm:ss.mmm` for WebVTT
+fn timestamp(ms: u32, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Escape text for a WebVTT cue payload
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Write cues as an SRT or WebVTT file
+pub fn render(cues: &[Cue], format: SubtitleFormat) -> String {
+    let mut out = String::new();
+    if format == SubtitleFormat::Vtt {
+        out.push_str("WEBVTT\n\n");
+    }
+
+    for (index, cue) in cues.iter().enumerate() {
+        let mut lines = cue.lines.clone();
+        match format {
+            SubtitleFormat::Srt => {
+                out.push_str(&format!(
+                    "{}\n{} --> {}\n",
+                    index + 1,
+                    timestamp(cue.start_ms, ','),
+                    timestamp(cue.end_ms, ',')
+                ));
+                if let (Some(speaker), Some(first)) = (&cue.speaker, lines.first_mut()) {
+                    *first = format!("{}: {}", speaker, first).trim_end().to_string();
+                }
+            }
+            SubtitleFormat::Vtt => {
+                out.push_str(&format!(
+                    "{} --> {}\n",
+                    timestamp(cue.start_ms, '.'),
+                    timestamp(cue.end_ms, '.')
+                ));
+                for line in &mut lines {
+                    *line = escape_vtt(line);
+                }
+                if let (Some(speaker), Some(first)) = (&cue.speaker, lines.first_mut()) {
+                    *first = format!("<v {}>{}", escape_vtt(speaker), first);
+                }
+            }
+        }
+
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::vad::{VadConfig, VoiceActivityDetector};
+
+    fn words(speaker: &str, turn_order: usize, start_ms: u32, text: &str) -> Vec<RecordingWord> {
+        text.split_whitespace()
+            .enumerate()
+            .map(|(i, word)| RecordingWord {
+                turn_order,
+                speaker: Some(speaker.to_string()),
+                text: word.to_string(),
+                start_ms: start_ms + i as u32 * 300,
+                end_ms: start_ms + i as u32 * 300 + 250,
+                confidence: 0.9,
+            })
+            .collect()
+    }
+
+    fn texts(cues: &[Cue]) -> Vec<String> {
+        cues.iter().map(|cue| cue.lines.join(" | ")).collect()
+    }
+
+    #[test]
+    fn test_cues_split_at_sentences_turns_and_limits() {
+        let mut transcript = words(
+            "Speaker A",
+            0,
+            0,
+            "Welcome everyone. Today we look at the quarterly numbers \
+             and what they mean for hiring next year.",
+        );
+        transcript.extend(words("Speaker B", 1, 10_000, "Thanks."));
+
+        let config = SubtitleConfig {
+            max_line_chars: 20,
+            ..Default::default()
+        };
+        let cues = build_cues(&transcript, |label| label.to_string(), &config);
+
+        assert_eq!(
+            texts(&cues),
+            [
+                "Welcome | everyone.",
+                "Today we look at the | quarterly numbers",
+                "and what they mean | for hiring next",
+                "year.",
+                "Thanks.",
+            ]
+        );
+        // The speaker prefix counts towards the first line
+        assert_eq!(cues[0].speaker.as_deref(), Some("Speaker A"));
+        assert_eq!(cues[1].speaker, None);
+        assert_eq!(cues[4].speaker.as_deref(), Some("Speaker B"));
+
+        // A short cue is held for the minimum duration
+        assert_eq!((cues[4].start_ms, cues[4].end_ms), (10_000, 11_000));
+        // ...but not into the next cue
+        assert_eq!(cues[0].end_ms, cues[1].start_ms);
+    }
+
+    #[test]
+    fn test_long_sentences_respect_max_duration() {
+        let transcript = words(
+            "Speaker A",
+            0,
+            0,
+            "one two three four five six seven eight nine ten",
+        );
+        let config = SubtitleConfig {
+            max_cue_duration_ms: 1000,
+            speaker_labels: false,
+            ..Default::default()
+        };
+        let cues = build_cues(&transcript, |label| label.to_string(), &config);
+
+        assert_eq!(
+            texts(&cues),
+            ["one two three", "four five six", "seven eight nine", "ten"]
+        );
+        assert!(cues.iter().all(|cue| cue.speaker.is_none()));
+    }
+
+    #[test]
+    fn test_cues_keep_silence_skipped_by_vad() {
+        // 1s of speech, 5s of silence, 1s of speech. VAD streams the first
+        // 1.5s of the silence and 300ms of pre-roll, and skips the rest.
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), 16000);
+        let timeline = vad.timeline();
+        let chunks = [(8000, 20), (0, 100), (8000, 20)];
+        for (level, count) in chunks {
+            for _ in 0..count {
+                vad.process(vec![level; 800]);
+            }
+        }
+
+        // The provider times the second turn from the thinned stream
+        let mut transcript = words("Speaker A", 0, 100, "Hello there.");
+        transcript.extend(words("Speaker A", 1, 2_800, "Welcome back."));
+        for word in &mut transcript {
+            word.start_ms = timeline.session_ms(word.start_ms);
+            word.end_ms = timeline.session_end_ms(word.end_ms);
+        }
+        let cues = build_cues(&transcript, |label| label.to_string(), &Default::default());
+
+        assert_eq!(texts(&cues), ["Hello there.", "Welcome back."]);
+        assert_eq!(cues[0].start_ms, 100);
+        assert!(cues[0].end_ms < 2_800);
+        // Where the speech is in the recording
+        assert_eq!((cues[1].start_ms, cues[1].end_ms), (6_000, 7_000));
+        assert!(render(&cues, SubtitleFormat::Srt).contains("00:00:06,000 --> 00:00:07,000"));
+    }
+
+    #[test]
+    fn test_render_srt_and_vtt() {
+        let cues = vec![
+            Cue {
+                start_ms: 1_500,
+                end_ms: 3_723_004,
+                speaker: Some("Dana".to_string()),
+                lines: vec!["Profit <up> & growing.".to_string()],
+            },
+            Cue {
+                start_ms: 3_724_000,
+                end_ms: 3_725_000,
+                speaker: None,
+                lines: vec!["Next.".to_string()],
+            },
+        ];
+
+        assert_eq!(
+            render(&cues, SubtitleFormat::Srt),
+            "1\n00:00:01,500 --> 01:02:03,004\nDana: Profit <up> & growing.\n\n\
+             2\n01:02:04,000 --> 01:02:05,000\nNext.\n\n"
+        );
+        assert_eq!(
+            render(&cues, SubtitleFormat::Vtt),
+            "WEBVTT\n\n\
+             00:00:01.500 --> 01:02:03.004\n<v Dana>Profit &lt;up&gt; &amp; growing.\n\n\
+             01:02:04.000 --> 01:02:05.000\nNext.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(SubtitleConfig::default().validate().is_ok());
+        assert!(SubtitleConfig {
+            max_lines: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(SubtitleConfig {
+            min_cue_duration_ms: 8000,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
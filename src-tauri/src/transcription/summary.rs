@@ -1,3 +1,4 @@
+use crate::transcription::language::{self, PromptSettings};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
@@ -43,12 +44,24 @@ struct ClaudeContent {
 pub struct SummaryService {
     api_key: String,
     client: reqwest::Client,
+    /// Language to write the summary in
+    prompt: PromptSettings,
 }
 
 impl SummaryService {
     pub fn new(api_key: String) -> Self {
         let client = reqwest::Client::new();
-        Self { api_key, client }
+        Self {
+            api_key,
+            client,
+            prompt: PromptSettings::default(),
+        }
+    }
+
+    /// Language settings for the prompt
+    pub fn with_prompt_settings(mut self, prompt: PromptSettings) -> Self {
+        self.prompt = prompt;
+        self
     }
 
     /// Generate a summary using Claude API
@@ -74,6 +87,9 @@ impl SummaryService {
 2. Key points (bullet points)
 3. Action items (if any are mentioned)
 
+{}
+Keep the section headings below (and "None") exactly as written, in English.
+
 Please format your response EXACTLY as follows:
 
 SUMMARY:
@@ -92,6 +108,7 @@ ACTION ITEMS:
 Transcript:
 {}
 "#,
+            language::response_instruction(self.prompt.output_language.as_deref()),
             transcript_text
         );
 
@@ -202,24 +219,20 @@ Transcript:
             }
 
             match current_section {
-                "summary" => {
-                    if !trimmed.is_empty() {
-                        if !summary.is_empty() {
-                            summary.push('\n');
-                        }
-                        summary.push_str(trimmed);
+                "summary" if !trimmed.is_empty() => {
+                    if !summary.is_empty() {
+                        summary.push('\n');
                     }
+                    summary.push_str(trimmed);
                 }
-                "key_points" => {
-                    if trimmed.starts_with('-') || trimmed.starts_with('•') {
-                        let point = trimmed
-                            .trim_start_matches('-')
-                            .trim_start_matches('•')
-                            .trim()
-                            .to_string();
-                        if !point.is_empty() {
-                            key_points.push(point);
-                        }
+                "key_points" if trimmed.starts_with('-') || trimmed.starts_with('•') => {
+                    let point = trimmed
+                        .trim_start_matches('-')
+                        .trim_start_matches('•')
+                        .trim()
+                        .to_string();
+                    if !point.is_empty() {
+                        key_points.push(point);
                     }
                 }
                 "action_items" => {
@@ -0,0 +1,487 @@
+//! # Voice Activity Detection
+//!
+//! Energy-based VAD that decides which chunks are worth streaming. Long silent
+//! stretches are thinned out to occasional keep-alive chunks so they are not
+//! billed as streaming time.
+//!
+//! - **Hangover**: audio keeps flowing for a while after speech stops, so the
+//!   streaming service still sees the pause it needs to end the turn.
+//! - **Pre-roll**: the most recent silent chunks are held back and sent just
+//!   before speech resumes, so soft word onsets are not clipped.
+//!
+//! Skipping audio shortens the stream, so times reported by the streaming
+//! service run ahead of the session. A [`StreamTimeline`] records the skipped
+//! spans to map them back.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Accepted speech threshold, in dBFS
+const THRESHOLD_RANGE_DB: std::ops::RangeInclusive<f32> = -100.0..=0.0;
+/// Accepted hangover, in ms
+const HANGOVER_RANGE_MS: std::ops::RangeInclusive<u32> = 0..=10_000;
+/// Accepted pre-roll, in ms
+const PRE_ROLL_RANGE_MS: std::ops::RangeInclusive<u32> = 0..=2_000;
+/// Accepted keep-alive interval, in ms
+const KEEPALIVE_RANGE_MS: std::ops::RangeInclusive<u32> = 500..=30_000;
+
+/// Voice activity detection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VadConfig {
+    pub enabled: bool,
+    /// Chunks with an RMS level above this (dBFS) count as speech
+    pub threshold_db: f32,
+    /// How long to keep streaming after speech stops
+    pub hangover_ms: u32,
+    /// Audio held back during silence and sent when speech starts
+    pub pre_roll_ms: u32,
+    /// During silence, forward one chunk this often to keep the stream alive
+    pub keepalive_interval_ms: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_db: -45.0,
+            // Longer than the default max_turn_silence so turns still end
+            hangover_ms: 1500,
+            pre_roll_ms: 300,
+            keepalive_interval_ms: 5000,
+        }
+    }
+}
+
+impl VadConfig {
+    /// Check the settings are usable before a session starts
+    pub fn validate(&self) -> Result<(), String> {
+        if !THRESHOLD_RANGE_DB.contains(&self.threshold_db) {
+            return Err(format!(
+                "VAD threshold must be between {} and {}dBFS (got {})",
+                THRESHOLD_RANGE_DB.start(),
+                THRESHOLD_RANGE_DB.end(),
+                self.threshold_db
+            ));
+        }
+
+        for (name, value, range) in [
+            ("VAD hangover", self.hangover_ms, HANGOVER_RANGE_MS),
+            ("VAD pre-roll", self.pre_roll_ms, PRE_ROLL_RANGE_MS),
+            (
+                "VAD keep-alive interval",
+                self.keepalive_interval_ms,
+                KEEPALIVE_RANGE_MS,
+            ),
+        ] {
+            if !range.contains(&value) {
+                return Err(format!(
+                    "{} must be between {} and {}ms (got {}ms)",
+                    name,
+                    range.start(),
+                    range.end(),
+                    value
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Change in speech state caused by a chunk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VadTransition {
+    SpeechStart,
+    SpeechEnd,
+}
+
+/// What to do with a processed chunk
+#[derive(Debug, Default)]
+pub struct VadOutput {
+    /// Chunks to stream, in order (may include held-back pre-roll)
+    pub forward: Vec<Vec<i16>>,
+    pub transition: Option<VadTransition>,
+}
+
+/// RMS level of a chunk in dBFS
+pub fn chunk_level_db(chunk: &[i16]) -> f32 {
+    if chunk.is_empty() {
+        return -100.0;
+    }
+    let sum_squares: f64 = chunk
+        .iter()
+        .map(|&s| {
+            let s = s as f64 / 32768.0;
+            s * s
+        })
+        .sum();
+    let rms = (sum_squares / chunk.len() as f64).sqrt();
+    if rms > 0.0 {
+        (20.0 * rms.log10()).max(-100.0) as f32
+    } else {
+        -100.0
+    }
+}
+
+/// Maps positions in the streamed (thinned) audio back to the session's
+/// timeline, which the recording follows. Clones share the same spans, so the
+/// detector can record them while transcripts are mapped elsewhere.
+#[derive(Debug, Clone)]
+pub struct StreamTimeline {
+    sample_rate: u32,
+    /// (stream position, samples skipped before it in total), by position
+    skips: Arc<Mutex<Vec<(u64, u64)>>>,
+}
+
+impl StreamTimeline {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            skips: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Note that `samples` of session audio were left out just before stream
+    /// sample `stream_position`
+    fn skip(&self, stream_position: u64, samples: u64) {
+        let mut skips = self.skips.lock().unwrap_or_else(|e| e.into_inner());
+        match skips.last_mut() {
+            Some((position, total)) if *position == stream_position => *total += samples,
+            _ => {
+                let total = skips.last().map_or(0, |(_, total)| *total) + samples;
+                skips.push((stream_position, total));
+            }
+        }
+    }
+
+    /// Session time of a stream time, both in ms
+    pub fn session_ms(&self, stream_ms: u32) -> u32 {
+        self.map(stream_ms, |position, at| at <= position)
+    }
+
+    /// [`session_ms`](Self::session_ms) for the end of a span, which stays
+    /// before audio skipped right where it ends
+    pub fn session_end_ms(&self, stream_ms: u32) -> u32 {
+        self.map(stream_ms, |position, at| at < position)
+    }
+
+    fn map(&self, stream_ms: u32, skipped_before: impl Fn(u64, u64) -> bool) -> u32 {
+        let rate = self.sample_rate.max(1) as u64;
+        let position = stream_ms as u64 * rate / 1000;
+        let skips = self.skips.lock().unwrap_or_else(|e| e.into_inner());
+        let skipped = match skips.partition_point(|(at, _)| skipped_before(position, *at)) {
+            0 => 0,
+            i => skips[i - 1].1,
+        };
+        (stream_ms as u64 + skipped * 1000 / rate).min(u32::MAX as u64) as u32
+    }
+}
+
+/// Streaming energy-based voice activity detector
+pub struct VoiceActivityDetector {
+    config: VadConfig,
+    sample_rate: u32,
+    in_speech: bool,
+    silence_run: u64,
+    since_keepalive: u64,
+    pre_roll: VecDeque<Vec<i16>>,
+    pre_roll_len: usize,
+    total_samples: u64,
+    silent_samples: u64,
+    dropped_samples: u64,
+    streamed_samples: u64,
+    timeline: StreamTimeline,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(config: VadConfig, sample_rate: u32) -> Self {
+        Self {
+            config,
+            sample_rate,
+            in_speech: false,
+            silence_run: 0,
+            since_keepalive: 0,
+            pre_roll: VecDeque::new(),
+            pre_roll_len: 0,
+            total_samples: 0,
+            silent_samples: 0,
+            dropped_samples: 0,
+            streamed_samples: 0,
+            timeline: StreamTimeline::new(sample_rate),
+        }
+    }
+
+    /// Timeline of the audio this detector streams
+    pub fn timeline(&self) -> StreamTimeline {
+        self.timeline.clone()
+    }
+
+    fn ms_to_samples(&self, ms: u32) -> u64 {
+        ms as u64 * self.sample_rate as u64 / 1000
+    }
+
+    /// Classify a chunk and decide what to stream
+    pub fn process(&mut self, chunk: Vec<i16>) -> VadOutput {
+        let len = chunk.len() as u64;
+        let voiced = chunk_level_db(&chunk) > self.config.threshold_db;
+        self.total_samples += len;
+        if !voiced {
+            self.silent_samples += len;
+        }
+
+        if !self.config.enabled {
+            self.streamed_samples += len;
+            return VadOutput {
+                forward: vec![chunk],
+                transition: None,
+            };
+        }
+
+        let mut output = VadOutput::default();
+
+        if voiced {
+            self.silence_run = 0;
+            if !self.in_speech {
+                self.in_speech = true;
+                output.transition = Some(VadTransition::SpeechStart);
+                output.forward.extend(self.pre_roll.drain(..));
+                self.pre_roll_len = 0;
+            }
+            output.forward.push(chunk);
+        } else if self.in_speech {
+            // Hangover: keep streaming the pause
+            self.silence_run += len;
+            output.forward.push(chunk);
+            if self.silence_run >= self.ms_to_samples(self.config.hangover_ms) {
+                self.in_speech = false;
+                self.since_keepalive = 0;
+                output.transition = Some(VadTransition::SpeechEnd);
+            }
+        } else {
+            self.since_keepalive += len;
+            if self.since_keepalive >= self.ms_to_samples(self.config.keepalive_interval_ms) {
+                self.since_keepalive = 0;
+                // The held-back audio came before the keep-alive, so it can no
+                // longer be sent in order
+                while let Some(held) = self.pre_roll.pop_front() {
+                    self.drop_chunk(&held);
+                }
+                self.pre_roll_len = 0;
+                output.forward.push(chunk);
+            } else {
+                self.hold_pre_roll(chunk);
+            }
+        }
+
+        self.streamed_samples += output.forward.iter().map(|c| c.len() as u64).sum::<u64>();
+        output
+    }
+
+    fn drop_chunk(&mut self, chunk: &[i16]) {
+        let len = chunk.len() as u64;
+        self.dropped_samples += len;
+        self.timeline.skip(self.streamed_samples, len);
+    }
+
+    /// Keep the latest silent audio, dropping whatever falls out of the window
+    fn hold_pre_roll(&mut self, chunk: Vec<i16>) {
+        self.pre_roll_len += chunk.len();
+        self.pre_roll.push_back(chunk);
+
+        let limit = self.ms_to_samples(self.config.pre_roll_ms) as usize;
+        while self.pre_roll_len > limit {
+            let Some(oldest) = self.pre_roll.pop_front() else {
+                break;
+            };
+            self.pre_roll_len -= oldest.len();
+            self.drop_chunk(&oldest);
+        }
+    }
+
+    pub fn in_speech(&self) -> bool {
+        self.in_speech
+    }
+
+    /// Position in the audio seen so far, in seconds
+    pub fn offset_secs(&self) -> f64 {
+        self.total_samples as f64 / self.sample_rate as f64
+    }
+
+    /// Fraction of the audio classified as silence
+    pub fn silence_ratio(&self) -> f64 {
+        if self.total_samples == 0 {
+            0.0
+        } else {
+            self.silent_samples as f64 / self.total_samples as f64
+        }
+    }
+
+    /// Seconds of audio that were not streamed
+    pub fn dropped_secs(&self) -> f64 {
+        self.dropped_samples as f64 / self.sample_rate as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn speech() -> Vec<i16> {
+        (0..800)
+            .map(|i| if i % 2 == 0 { 8000 } else { -8000 })
+            .collect()
+    }
+
+    fn silence() -> Vec<i16> {
+        vec![3; 800]
+    }
+
+    fn config() -> VadConfig {
+        VadConfig {
+            hangover_ms: 200,
+            pre_roll_ms: 100,
+            keepalive_interval_ms: 1000,
+            ..VadConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_chunk_level_db() {
+        assert_eq!(chunk_level_db(&[]), -100.0);
+        assert_eq!(chunk_level_db(&[0; 10]), -100.0);
+        assert!((chunk_level_db(&[32767; 10])).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_speech_start_sends_pre_roll() {
+        let mut vad = VoiceActivityDetector::new(config(), RATE);
+
+        // 10 silent chunks: only the last 100ms (2 chunks) are held back
+        for _ in 0..10 {
+            assert!(vad.process(silence()).forward.is_empty());
+        }
+
+        let output = vad.process(speech());
+        assert_eq!(output.transition, Some(VadTransition::SpeechStart));
+        assert_eq!(output.forward.len(), 3);
+        assert!((vad.dropped_secs() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hangover_before_speech_end() {
+        let mut vad = VoiceActivityDetector::new(config(), RATE);
+        vad.process(speech());
+
+        // 200ms hangover = 4 chunks still streamed, the 4th ends speech
+        for i in 0..4 {
+            let output = vad.process(silence());
+            assert_eq!(output.forward.len(), 1);
+            let expected = (i == 3).then_some(VadTransition::SpeechEnd);
+            assert_eq!(output.transition, expected);
+        }
+        assert!(!vad.in_speech());
+        assert!(vad.process(silence()).forward.is_empty());
+    }
+
+    #[test]
+    fn test_keepalive_during_long_silence() {
+        let mut vad = VoiceActivityDetector::new(config(), RATE);
+
+        let forwarded: usize = (0..100).map(|_| vad.process(silence()).forward.len()).sum();
+
+        // 5s of silence with a 1s keep-alive interval
+        assert_eq!(forwarded, 5);
+        assert!((vad.silence_ratio() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_timeline_maps_stream_back_to_session() {
+        let mut vad = VoiceActivityDetector::new(config(), RATE);
+        let timeline = vad.timeline();
+
+        // Speech, then 2s of silence: 200ms of hangover, a keep-alive after
+        // 1s and the final 100ms of pre-roll are streamed, the rest skipped
+        let mut chunks: Vec<Vec<i16>> = vec![speech(); 4];
+        chunks.extend(vec![silence(); 40]);
+        chunks.extend(vec![speech(); 4]);
+
+        let mut session_ms = 0;
+        let mut stream_ms = 0;
+        let mut second_speech = None;
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let output = vad.process(chunk);
+            if i == 44 {
+                // Pre-roll comes first, the speech chunk last
+                let pre_roll: usize = output.forward[..output.forward.len() - 1]
+                    .iter()
+                    .map(Vec::len)
+                    .sum();
+                second_speech = Some((stream_ms + pre_roll as u32 * 1000 / RATE, session_ms));
+            }
+            for forwarded in &output.forward {
+                stream_ms += forwarded.len() as u32 * 1000 / RATE;
+            }
+            session_ms += 50;
+        }
+
+        let (stream_start, session_start) = second_speech.unwrap();
+        assert!(stream_start < session_start);
+        assert_eq!(timeline.session_ms(stream_start), session_start);
+        // Before the first skip the clocks agree, up to the end of the hangover
+        assert_eq!(timeline.session_ms(100), 100);
+        assert_eq!(timeline.session_end_ms(400), 400);
+        let streamed_ms = stream_ms as f64 / 1000.0;
+        assert!((streamed_ms + vad.dropped_secs() - 2.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(VadConfig::default().validate().is_ok());
+
+        let config: VadConfig = serde_json::from_str(r#"{"threshold_db": -30}"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.hangover_ms, 1500);
+
+        for invalid in [
+            VadConfig {
+                threshold_db: 6.0,
+                ..Default::default()
+            },
+            VadConfig {
+                threshold_db: f32::NAN,
+                ..Default::default()
+            },
+            VadConfig {
+                pre_roll_ms: 60_000,
+                ..Default::default()
+            },
+            VadConfig {
+                keepalive_interval_ms: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_disabled_forwards_everything() {
+        let mut vad = VoiceActivityDetector::new(
+            VadConfig {
+                enabled: false,
+                ..config()
+            },
+            RATE,
+        );
+
+        vad.process(speech());
+        let output = vad.process(silence());
+        assert_eq!(output.forward.len(), 1);
+        assert!(output.transition.is_none());
+        assert!((vad.silence_ratio() - 0.5).abs() < f64::EPSILON);
+    }
+}
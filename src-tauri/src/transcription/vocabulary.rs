@@ -0,0 +1,85 @@
+//! # Vocabulary
+//!
+//! Product names, tickers and other jargon a project expects to hear. The
+//! list boosts recognition of those terms in the streaming provider and tells
+//! the enhancement and refinement prompts how to spell them.
+
+/// Most terms AssemblyAI accepts for key-term boosting
+pub const MAX_TERMS: usize = 100;
+
+/// Longest term AssemblyAI accepts, in characters
+pub const MAX_TERM_CHARS: usize = 50;
+
+/// Trim terms and drop blanks and duplicates (ignoring case), keeping the
+/// first spelling. Fails if the list is longer than the providers accept.
+pub fn normalize(terms: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for term in terms {
+        let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+        if term.is_empty()
+            || normalized
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&term))
+        {
+            continue;
+        }
+        if term.chars().count() > MAX_TERM_CHARS {
+            return Err(format!(
+                "Vocabulary terms can be at most {} characters (\"{}\")",
+                MAX_TERM_CHARS, term
+            ));
+        }
+        normalized.push(term);
+    }
+
+    if normalized.len() > MAX_TERMS {
+        return Err(format!(
+            "Vocabulary can have at most {} terms (got {})",
+            MAX_TERMS,
+            normalized.len()
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Prompt paragraph listing the terms, or nothing for an empty vocabulary
+pub fn prompt_section(terms: &[String]) -> String {
+    if terms.is_empty() {
+        return String::new();
+    }
+    format!(
+        "Key terms for this project - when the transcript contains one of these, possibly misheard, spell it exactly like this:\n{}\n\n",
+        terms.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_trims_and_deduplicates() {
+        let terms = vec![
+            " Megapack ".to_string(),
+            "".to_string(),
+            "megapack".to_string(),
+            "Full  Self Driving".to_string(),
+            "TSLA".to_string(),
+        ];
+        assert_eq!(
+            normalize(terms).unwrap(),
+            ["Megapack", "Full Self Driving", "TSLA"]
+        );
+
+        assert!(normalize(vec!["x".repeat(MAX_TERM_CHARS + 1)]).is_err());
+        let too_many = (0..=MAX_TERMS).map(|i| format!("term {}", i)).collect();
+        assert!(normalize(too_many).is_err());
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        assert_eq!(prompt_section(&[]), "");
+        let section = prompt_section(&["Megapack".to_string(), "TSLA".to_string()]);
+        assert!(section.contains("Megapack, TSLA"));
+    }
+}
@@ -0,0 +1,506 @@
+//! # Local Whisper Transcription
+//!
+//! Runs a Whisper model (whisper.cpp ggml format) on the CPU, so audio never
+//! leaves the machine. Whisper transcribes whole windows of audio rather than a
+//! live stream: incoming chunks are cut into utterance windows at pauses, or
+//! after [`MAX_WINDOW_SECS`], and each window is reported as one final turn.
+//! There are no partial results.
+
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use whisper_rs::{
+    get_lang_str, FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters,
+    WhisperState,
+};
+
+use super::assemblyai::{TranscriptResult, WordResult};
+use super::provider::{ProviderEvent, TranscriptionProvider};
+use super::resampler::StreamResampler;
+use super::vad::chunk_level_db;
+
+/// Whisper models are trained on 16kHz audio
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Longest window sent to the model (its context is 30s)
+const MAX_WINDOW_SECS: u32 = 20;
+
+/// Shortest window that is cut at a pause
+const MIN_WINDOW_MS: u32 = 1000;
+
+/// Trailing silence that ends an utterance
+const END_OF_UTTERANCE_MS: u32 = 800;
+
+/// Silence kept in front of an utterance, so its first word isn't clipped
+const PRE_ROLL_MS: u32 = 300;
+
+/// Chunks quieter than this count as silence
+const SILENCE_THRESHOLD_DB: f32 = -45.0;
+
+/// Upper bound for the default thread count
+const MAX_DEFAULT_THREADS: u32 = 8;
+
+fn ms_to_samples(ms: u32) -> usize {
+    (WHISPER_SAMPLE_RATE as usize * ms as usize) / 1000
+}
+
+fn samples_to_ms(samples: u64) -> u32 {
+    (samples * 1000 / WHISPER_SAMPLE_RATE as u64) as u32
+}
+
+/// A stretch of 16kHz audio to transcribe
+#[derive(Debug, PartialEq)]
+pub(crate) struct UtteranceWindow {
+    /// Start of the window in ms from the start of the stream
+    pub(crate) offset_ms: u32,
+    pub(crate) samples: Vec<f32>,
+}
+
+/// Cuts a stream of 16kHz chunks into utterance windows.
+///
+/// Leading silence is dropped rather than transcribed, since Whisper tends to
+/// hallucinate text for silent input.
+pub(crate) struct UtteranceSegmenter {
+    window: Vec<f32>,
+    /// Samples consumed before the current window
+    window_start: u64,
+    trailing_silence: usize,
+    has_speech: bool,
+}
+
+impl UtteranceSegmenter {
+    pub(crate) fn new() -> Self {
+        Self {
+            window: Vec::new(),
+            window_start: 0,
+            trailing_silence: 0,
+            has_speech: false,
+        }
+    }
+
+    /// Add a chunk, returning a window once an utterance ends
+    pub(crate) fn push(&mut self, samples: &[f32], silent: bool) -> Option<UtteranceWindow> {
+        self.window.extend_from_slice(samples);
+
+        if silent {
+            self.trailing_silence += samples.len();
+        } else {
+            self.trailing_silence = 0;
+            self.has_speech = true;
+        }
+
+        if !self.has_speech {
+            let pre_roll = ms_to_samples(PRE_ROLL_MS);
+            if self.window.len() > pre_roll {
+                let excess = self.window.len() - pre_roll;
+                self.window.drain(..excess);
+                self.window_start += excess as u64;
+            }
+            return None;
+        }
+
+        let full = self.window.len() >= ms_to_samples(MAX_WINDOW_SECS * 1000);
+        let paused = self.trailing_silence >= ms_to_samples(END_OF_UTTERANCE_MS)
+            && self.window.len() >= ms_to_samples(MIN_WINDOW_MS);
+
+        (full || paused).then(|| self.take_window())
+    }
+
+    /// The remaining utterance once the stream has ended
+    pub(crate) fn finish(&mut self) -> Option<UtteranceWindow> {
+        self.has_speech.then(|| self.take_window())
+    }
+
+    fn take_window(&mut self) -> UtteranceWindow {
+        let samples = std::mem::take(&mut self.window);
+        let offset_ms = samples_to_ms(self.window_start);
+
+        self.window_start += samples.len() as u64;
+        self.trailing_silence = 0;
+        self.has_speech = false;
+
+        UtteranceWindow { offset_ms, samples }
+    }
+}
+
+/// A decoded token: raw text bytes, start and end in 10ms units relative to
+/// its window, and probability. Multi-byte characters can be split across
+/// tokens, so text is only decoded once a word is complete.
+pub(crate) struct Token {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) t0: i64,
+    pub(crate) t1: i64,
+    pub(crate) p: f32,
+}
+
+/// Merge sub-word tokens into words. A token starting with a space starts a
+/// new word; special tokens (`[_BEG_]`, `<|endoftext|>`, ...) are skipped.
+pub(crate) fn merge_tokens(tokens: &[Token], offset_ms: u32) -> Vec<WordResult> {
+    let to_ms = |t: i64| offset_ms + (t.max(0) as u32) * 10;
+    // Word bytes, start, end, summed probability and token count
+    let mut words: Vec<(Vec<u8>, u32, u32, f32, usize)> = Vec::new();
+
+    for token in tokens {
+        if token.bytes.starts_with(b"[_") || token.bytes.starts_with(b"<|") {
+            continue;
+        }
+
+        match words.last_mut() {
+            Some((bytes, _, end, p, count)) if !token.bytes.starts_with(b" ") => {
+                bytes.extend_from_slice(&token.bytes);
+                *end = to_ms(token.t1);
+                *p += token.p;
+                *count += 1;
+            }
+            _ => words.push((
+                token.bytes.clone(),
+                to_ms(token.t0),
+                to_ms(token.t1),
+                token.p,
+                1,
+            )),
+        }
+    }
+
+    words
+        .into_iter()
+        .filter_map(|(bytes, start, end, p, count)| {
+            let text = String::from_utf8_lossy(&bytes).trim().to_string();
+            (!text.is_empty()).then(|| WordResult {
+                text,
+                start,
+                end,
+                confidence: p / count as f32,
+                is_final: true,
+            })
+        })
+        .collect()
+}
+
+/// Inference settings shared with the worker thread
+#[derive(Clone)]
+struct InferenceSettings {
+    language: Option<String>,
+    threads: u32,
+    /// Text the model treats as preceding each window, listing key terms
+    initial_prompt: Option<String>,
+}
+
+/// Transcribe one window, returning `None` when nothing was recognized
+fn transcribe_window(
+    state: &mut WhisperState,
+    settings: &InferenceSettings,
+    window: &UtteranceWindow,
+) -> Result<Option<TranscriptResult>, String> {
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(settings.language.as_deref());
+    params.set_n_threads(settings.threads as i32);
+    if let Some(prompt) = &settings.initial_prompt {
+        params.set_initial_prompt(prompt);
+    }
+    params.set_token_timestamps(true);
+    params.set_no_context(true);
+    params.set_suppress_blank(true);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+
+    state
+        .full(params, &window.samples)
+        .map_err(|e| format!("Whisper inference failed: {}", e))?;
+
+    let segments = state
+        .full_n_segments()
+        .map_err(|e| format!("Failed to read Whisper segments: {}", e))?;
+
+    let mut tokens = Vec::new();
+    for segment in 0..segments {
+        let n_tokens = state
+            .full_n_tokens(segment)
+            .map_err(|e| format!("Failed to read Whisper tokens: {}", e))?;
+        for token in 0..n_tokens {
+            let bytes = state
+                .full_get_token_bytes(segment, token)
+                .map_err(|e| format!("Failed to read Whisper token: {}", e))?;
+            let data = state
+                .full_get_token_data(segment, token)
+                .map_err(|e| format!("Failed to read Whisper token: {}", e))?;
+            tokens.push(Token {
+                bytes,
+                t0: data.t0,
+                t1: data.t1,
+                p: data.p,
+            });
+        }
+    }
+
+    let words = merge_tokens(&tokens, window.offset_ms);
+    if words.is_empty() {
+        return Ok(None);
+    }
+
+    let text = words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let confidence = words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32;
+    let language = settings.language.clone().or_else(|| {
+        state
+            .full_lang_id_from_state()
+            .ok()
+            .and_then(get_lang_str)
+            .map(str::to_string)
+    });
+
+    Ok(Some(TranscriptResult {
+        text,
+        confidence,
+        is_final: true,
+        turn_order: 0,
+        end_of_turn: true,
+        words,
+        speaker: None,
+        language,
+    }))
+}
+
+/// Transcription with a local Whisper model
+pub struct WhisperProvider {
+    context: Arc<WhisperContext>,
+    settings: InferenceSettings,
+}
+
+impl WhisperProvider {
+    /// Load a ggml model file
+    pub fn load(model_path: &str) -> Result<Self, String> {
+        if !Path::new(model_path).is_file() {
+            return Err(format!("Whisper model not found: {}", model_path));
+        }
+
+        let context =
+            WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+                .map_err(|e| format!("Failed to load Whisper model {}: {}", model_path, e))?;
+
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(4)
+            .min(MAX_DEFAULT_THREADS);
+
+        tracing::info!("Loaded Whisper model {}", model_path);
+
+        Ok(Self {
+            context: Arc::new(context),
+            settings: InferenceSettings {
+                language: None,
+                threads,
+                initial_prompt: None,
+            },
+        })
+    }
+
+    /// Spoken language code, or `None` to detect it per window
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.settings.language = language.filter(|l| !l.trim().is_empty());
+        self
+    }
+
+    pub fn with_threads(mut self, threads: u32) -> Self {
+        self.settings.threads = threads.max(1);
+        self
+    }
+
+    /// Key terms to prime the model with, so it spells them as given
+    pub fn with_vocabulary(mut self, vocabulary: &[String]) -> Self {
+        self.settings.initial_prompt =
+            (!vocabulary.is_empty()).then(|| format!("{}.", vocabulary.join(", ")));
+        self
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for WhisperProvider {
+    fn name(&self) -> &'static str {
+        "Whisper"
+    }
+
+    async fn transcribe(
+        &self,
+        mut audio_receiver: mpsc::Receiver<Vec<i16>>,
+        transcript_sender: mpsc::UnboundedSender<TranscriptResult>,
+        // Runs locally, so there is no vendor session to report
+        _events: mpsc::UnboundedSender<ProviderEvent>,
+        sample_rate: u32,
+    ) -> Result<(), String> {
+        let mut resampler = StreamResampler::new(sample_rate, WHISPER_SAMPLE_RATE)?;
+        let mut state = self
+            .context
+            .create_state()
+            .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+        let settings = self.settings.clone();
+
+        // Inference blocks for roughly as long as the window itself, so it
+        // runs on its own thread while audio keeps arriving
+        let (window_tx, mut window_rx) = mpsc::unbounded_channel::<UtteranceWindow>();
+        let worker = tokio::task::spawn_blocking(move || {
+            let mut turn_order = 0;
+            while let Some(window) = window_rx.blocking_recv() {
+                match transcribe_window(&mut state, &settings, &window) {
+                    Ok(Some(mut result)) => {
+                        result.turn_order = turn_order;
+                        turn_order += 1;
+                        if transcript_sender.send(result).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::error!("{}", e),
+                }
+            }
+        });
+
+        let mut segmenter = UtteranceSegmenter::new();
+        let mut samples = Vec::new();
+
+        while let Some(chunk) = audio_receiver.recv().await {
+            let silent = chunk_level_db(&chunk) < SILENCE_THRESHOLD_DB;
+            let input: Vec<f32> = chunk.iter().map(|&s| s as f32 / 32768.0).collect();
+
+            samples.clear();
+            resampler.process(&input, &mut samples)?;
+
+            if let Some(window) = segmenter.push(&samples, silent) {
+                if window_tx.send(window).is_err() {
+                    break;
+                }
+            }
+        }
+
+        samples.clear();
+        resampler.flush(&mut samples)?;
+        let last = segmenter
+            .push(&samples, true)
+            .or_else(|| segmenter.finish());
+        if let Some(window) = last {
+            let _ = window_tx.send(window);
+        }
+        drop(window_tx);
+
+        worker
+            .await
+            .map_err(|e| format!("Whisper worker failed: {}", e))?;
+
+        tracing::info!("Whisper transcription finished");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(ms: u32) -> Vec<f32> {
+        vec![0.1; ms_to_samples(ms)]
+    }
+
+    fn token(text: &str, t0: i64, t1: i64, p: f32) -> Token {
+        Token {
+            bytes: text.as_bytes().to_vec(),
+            t0,
+            t1,
+            p,
+        }
+    }
+
+    #[test]
+    fn test_segmenter_cuts_at_pauses() {
+        let mut segmenter = UtteranceSegmenter::new();
+
+        // Leading silence is dropped down to the pre-roll
+        for _ in 0..20 {
+            assert!(segmenter.push(&chunk(100), true).is_none());
+        }
+        for _ in 0..15 {
+            assert!(segmenter.push(&chunk(100), false).is_none());
+        }
+        for _ in 0..7 {
+            assert!(segmenter.push(&chunk(100), true).is_none());
+        }
+
+        let window = segmenter.push(&chunk(100), true).unwrap();
+        assert_eq!(window.offset_ms, 2000 - PRE_ROLL_MS);
+        assert_eq!(
+            window.samples.len(),
+            ms_to_samples(PRE_ROLL_MS + 1500 + 800)
+        );
+
+        // Nothing left once the utterance has been taken
+        assert!(segmenter.finish().is_none());
+    }
+
+    #[test]
+    fn test_segmenter_limits_window_length() {
+        let mut segmenter = UtteranceSegmenter::new();
+        let chunks = MAX_WINDOW_SECS * 10;
+
+        for _ in 0..chunks - 1 {
+            assert!(segmenter.push(&chunk(100), false).is_none());
+        }
+        let window = segmenter.push(&chunk(100), false).unwrap();
+        assert_eq!(window.offset_ms, 0);
+
+        segmenter.push(&chunk(100), false);
+        let rest = segmenter.finish().unwrap();
+        assert_eq!(rest.offset_ms, MAX_WINDOW_SECS * 1000);
+        assert_eq!(rest.samples.len(), ms_to_samples(100));
+    }
+
+    #[test]
+    fn test_merge_tokens_into_words() {
+        let tokens = vec![
+            token("[_BEG_]", 0, 0, 1.0),
+            token(" Hel", 10, 20, 0.8),
+            token("lo", 20, 30, 0.6),
+            token(" world", 40, 60, 0.9),
+            token(".", 60, 61, 0.5),
+            token("<|endoftext|>", 61, 61, 1.0),
+        ];
+
+        let words = merge_tokens(&tokens, 5000);
+        assert_eq!(words.len(), 2);
+
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!((words[0].start, words[0].end), (5100, 5300));
+        assert!((words[0].confidence - 0.7).abs() < 1e-6);
+
+        assert_eq!(words[1].text, "world.");
+        assert_eq!((words[1].start, words[1].end), (5400, 5610));
+    }
+
+    #[test]
+    fn test_merge_tokens_joins_split_characters() {
+        // "café" with the two bytes of "é" in separate tokens
+        let tokens = vec![
+            token(" caf", 0, 10, 0.9),
+            Token {
+                bytes: vec![0xC3],
+                t0: 10,
+                t1: 15,
+                p: 0.9,
+            },
+            Token {
+                bytes: vec![0xA9],
+                t0: 15,
+                t1: 20,
+                p: 0.9,
+            },
+        ];
+
+        let words = merge_tokens(&tokens, 0);
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].text, "café");
+        assert_eq!(words[0].end, 200);
+    }
+}
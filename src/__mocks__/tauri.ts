@@ -18,6 +18,12 @@ export const mockTauriCommands = {
   create_project: vi.fn((project) => Promise.resolve({ id: 1, ...project })),
   get_project: vi.fn((id) => Promise.resolve({ id, name: "Test Project" })),
   update_project: vi.fn((project) => Promise.resolve(project)),
+  update_project_streaming_config: vi.fn((args) =>
+    Promise.resolve({ id: args?.id, streaming_config: args?.streamingConfig }),
+  ),
+  update_project_vocabulary: vi.fn((args) =>
+    Promise.resolve({ id: args?.id, vocabulary: args?.vocabulary }),
+  ),
   delete_project: vi.fn(() => Promise.resolve()),
 
   // Recording commands
@@ -26,7 +32,11 @@ export const mockTauriCommands = {
     Promise.resolve({ id: 1, ...recording }),
   ),
   get_recording: vi.fn((id) => Promise.resolve({ id, name: "Test Recording" })),
+  get_recording_words: vi.fn(() => Promise.resolve([])),
   update_recording_name: vi.fn((id, name) => Promise.resolve({ id, name })),
+  rename_speaker: vi.fn((args) =>
+    Promise.resolve({ id: args?.recordingId, metadata: { speakers: [] } }),
+  ),
   delete_recording: vi.fn(() => Promise.resolve()),
 
   // Transcription commands
@@ -35,6 +45,8 @@ export const mockTauriCommands = {
   ),
   start_transcription: vi.fn(() => Promise.resolve()),
   stop_transcription: vi.fn(() => Promise.resolve()),
+  pause_transcription: vi.fn(() => Promise.resolve()),
+  resume_transcription: vi.fn(() => Promise.resolve()),
   get_transcription_status: vi.fn(() => Promise.resolve({ is_active: false })),
 
   // Settings commands
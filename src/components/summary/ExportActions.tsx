@@ -6,6 +6,7 @@ import {
   DropdownMenuTrigger,
 } from "@/components/ui/dropdown-menu";
 import { useRecordings } from "@/contexts/RecordingsContext";
+import type { ExportFormat } from "@/lib/types";
 import { Download } from "lucide-react";
 import { toast } from "sonner";
 
@@ -16,7 +17,7 @@ interface ExportActionsProps {
 export function ExportActions({ recordingId }: ExportActionsProps) {
   const { exportRecording } = useRecordings();
 
-  const handleExport = async (format: "txt" | "json") => {
+  const handleExport = async (format: ExportFormat) => {
     try {
       await exportRecording(recordingId, format);
       toast.success(`Exported as ${format.toUpperCase()}`);
@@ -38,8 +39,11 @@ export function ExportActions({ recordingId }: ExportActionsProps) {
         <DropdownMenuItem onClick={() => handleExport("txt")}>
           Export as TXT
         </DropdownMenuItem>
-        <DropdownMenuItem onClick={() => handleExport("json")}>
-          Export as JSON
+        <DropdownMenuItem onClick={() => handleExport("srt")}>
+          Export as SRT subtitles
+        </DropdownMenuItem>
+        <DropdownMenuItem onClick={() => handleExport("vtt")}>
+          Export as WebVTT subtitles
         </DropdownMenuItem>
       </DropdownMenuContent>
     </DropdownMenu>
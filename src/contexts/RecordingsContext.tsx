@@ -1,6 +1,6 @@
 import { createContext, useContext, useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Recording, TranscriptSummary, Project } from "@/lib/types";
+import { Recording, TranscriptSummary, Project, ExportFormat } from "@/lib/types";
 import { useProjects } from "./ProjectsContext";
 import { useSettings } from "./SettingsContext";
 import { useRecordingEvents } from "@/hooks/use-realtime-events";
@@ -19,7 +19,7 @@ interface RecordingsContextType {
   renameRecording: (recordingId: string, newName: string) => Promise<void>;
   deleteRecording: (recordingId: string) => Promise<void>;
   generateSummary: (recordingId: string) => Promise<void>;
-  exportRecording: (recordingId: string, format: "txt" | "json") => Promise<void>;
+  exportRecording: (recordingId: string, format: ExportFormat) => Promise<void>;
   setCurrentRecording: (recording: Recording | null) => void;
   refreshRecordings: () => Promise<void>;
   attachIntelligenceAnalysis?: (recordingId: string, snapshot: RecordingAnalysisSnapshot) => Promise<void>;
@@ -382,6 +382,7 @@ export function RecordingsProvider({
       await invoke<Recording>("generate_recording_summary", {
         recordingId: recordingId,
         claudeApiKey: claudeApiKey,
+        outputLanguage: settings.output_language || null,
       });
     } catch (error) {
       const errorMessage = error instanceof Error ? error.message : String(error);
@@ -392,28 +393,23 @@ export function RecordingsProvider({
 
   const exportRecording = async (
     recordingId: string,
-    format: "txt" | "json"
+    format: ExportFormat
   ) => {
     try {
-      // Use Tauri save dialog to get output path
+      // Use Tauri save dialog to get output path; the format doubles as the extension
       const { save } = await import("@tauri-apps/plugin-dialog");
-      const extension = format === "txt" ? "txt" : "json";
       const outputPath = await save({
-        defaultPath: `recording.${extension}`,
+        defaultPath: `recording.${format}`,
         filters: [
           {
             name: format.toUpperCase(),
-            extensions: [extension],
+            extensions: [format],
           },
         ],
       });
 
       if (outputPath) {
-        // WORKAROUND: Tauri transforms snake_case to camelCase, so send camelCase directly
-        await invoke("export_recording", {
-          recordingId: recordingId,
-          outputPath: outputPath,
-        });
+        await tauri.exportRecording(recordingId, outputPath, format);
       }
     } catch (error) {
       const errorMessage = error instanceof Error ? error.message : String(error);
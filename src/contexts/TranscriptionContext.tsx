@@ -5,11 +5,13 @@ import {
   EnhancedTranscript,
   TurnBufferItem,
   RecordingStatus,
+  ProviderSessionExpiring,
 } from "@/lib/types";
 import * as tauri from "@/lib/tauri";
 import { useSettings } from "./SettingsContext";
 import { useProjects } from "./ProjectsContext";
 import { useTranscriptionEvents } from "@/hooks/use-realtime-events";
+import { toast } from "sonner";
 
 interface TranscriptionState {
   isRecording: boolean;
@@ -92,12 +94,24 @@ export function TranscriptionProvider({
     });
   }, []);
 
+  // The provider ends streaming sessions at a fixed time
+  const handleProviderSessionExpiring = useCallback(
+    (expiring: ProviderSessionExpiring) => {
+      const minutes = Math.max(1, Math.round(expiring.seconds_remaining / 60));
+      toast.warning(
+        `The transcription session ends in about ${minutes} minute${minutes === 1 ? "" : "s"}`
+      );
+    },
+    []
+  );
+
   // Set up real-time event listeners for transcription lifecycle
   useTranscriptionEvents({
     onTranscriptionStarted: handleTranscriptionStarted,
     onTranscriptionStopped: handleTranscriptionStopped,
     onTranscriptionError: handleTranscriptionError,
     onSessionCleared: handleSessionCleared,
+    onProviderSessionExpiring: handleProviderSessionExpiring,
   });
 
   // Listen for transcript results (keeping existing logic but making it more robust)
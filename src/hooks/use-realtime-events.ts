@@ -180,6 +180,7 @@ export function useTranscriptionEvents(callbacks: {
   onEnhancedTranscript?: EventCallback;
   onTranscriptionError?: EventCallback;
   onSessionCleared?: EventCallback;
+  onProviderSessionExpiring?: EventCallback;
 }) {
   const events: Record<string, EventCallback> = {};
 
@@ -201,6 +202,9 @@ export function useTranscriptionEvents(callbacks: {
   if (callbacks.onSessionCleared) {
     events.session_cleared = callbacks.onSessionCleared;
   }
+  if (callbacks.onProviderSessionExpiring) {
+    events.provider_session_expiring = callbacks.onProviderSessionExpiring;
+  }
 
   return useRealtimeEvents({ events });
 }
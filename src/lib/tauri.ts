@@ -9,9 +9,14 @@ import {
   Project,
   CreateProjectRequest,
   Recording,
+  RecordingWord,
   TranscriptSummary,
   AppSettings,
   RefinementConfig,
+  StreamingConfig,
+  ExportFormat,
+  SubtitleConfig,
+  LanguageCode,
 } from "./types";
 
 // Audio Device Commands
@@ -46,6 +51,40 @@ export async function selectProject(projectId: string): Promise<void> {
   return await invoke("set_current_project", { project_id: projectId });
 }
 
+/**
+ * Sets the turn detection settings new sessions in a project start with
+ * @param projectId - Project to update
+ * @param streamingConfig - Settings to save, or null to use the defaults
+ * @throws Error if a setting is out of range
+ */
+export async function updateProjectStreamingConfig(
+  projectId: string,
+  streamingConfig: StreamingConfig | null,
+): Promise<Project> {
+  return await invoke<Project>("update_project_streaming_config", {
+    id: projectId,
+    streamingConfig: streamingConfig,
+  });
+}
+
+/**
+ * Replace a project's key terms (product names, tickers, jargon). They boost
+ * recognition while streaming and are spelled as given in enhanced and
+ * refined transcripts.
+ * @param projectId - Project to update
+ * @param vocabulary - Terms to save; blanks and duplicates are dropped
+ * @throws Error if there are more than 100 terms or one is over 50 characters
+ */
+export async function updateProjectVocabulary(
+  projectId: string,
+  vocabulary: string[],
+): Promise<Project> {
+  return await invoke<Project>("update_project_vocabulary", {
+    id: projectId,
+    vocabulary,
+  });
+}
+
 export async function deleteProject(projectId: string): Promise<void> {
   return await invoke("delete_project", { id: projectId });
 }
@@ -76,6 +115,7 @@ export async function listRecordings(projectId: string): Promise<Recording[]> {
  * @param actionItems - Optional array of action items identified in the transcript
  * @param claudeApiKey - Optional Claude API key for automatic summary generation
  * @param autoGenerateSummary - Whether to automatically generate a summary after saving
+ * @param outputLanguage - Language for the generated summary; the transcript's own when omitted
  * @returns Promise that resolves to the saved Recording object
  * @throws Error if saving fails or no active transcription session exists
  */
@@ -86,6 +126,7 @@ export async function saveRecording(
   actionItems?: string[],
   claudeApiKey?: string,
   autoGenerateSummary?: boolean,
+  outputLanguage?: LanguageCode,
 ): Promise<Recording> {
   return await invoke<Recording>("save_recording", {
     name,
@@ -94,6 +135,7 @@ export async function saveRecording(
     actionItems: actionItems || [],
     claudeApiKey: claudeApiKey || null,
     autoGenerateSummary: autoGenerateSummary || false,
+    outputLanguage: outputLanguage || null,
   });
 }
 
@@ -107,6 +149,40 @@ export async function renameRecording(
   });
 }
 
+/**
+ * Gets the timed words of a recording, e.g. for seeking or subtitles
+ * @param startMs - Only words ending at or after this offset
+ * @param endMs - Only words starting at or before this offset
+ */
+export async function getRecordingWords(
+  recordingId: string,
+  startMs?: number,
+  endMs?: number,
+): Promise<RecordingWord[]> {
+  return await invoke<RecordingWord[]>("get_recording_words", {
+    recordingId,
+    startMs: startMs ?? null,
+    endMs: endMs ?? null,
+  });
+}
+
+/**
+ * Shows a speaker in a recording under a real name
+ * @param label - Speaker label from transcription, e.g. "Speaker A"
+ * @param name - Real name, or null to show the label again
+ */
+export async function renameSpeaker(
+  recordingId: string,
+  label: string,
+  name: string | null,
+): Promise<Recording> {
+  return await invoke<Recording>("rename_speaker", {
+    recordingId,
+    label,
+    name,
+  });
+}
+
 export async function deleteRecording(recordingId: string): Promise<void> {
   return await invoke("delete_recording", { id: recordingId });
 }
@@ -120,6 +196,7 @@ export async function deleteRecording(recordingId: string): Promise<void> {
  * @param claudeApiKey - Optional Claude API key for text enhancement
  * @param projectId - Optional project ID for organizing recordings
  * @param refinementConfig - Configuration for text enhancement options
+ * @param streamingConfig - Turn detection settings, defaults to the project's saved settings
  * @throws Error if transcription fails to start or API keys are invalid
  */
 export async function startTranscription(
@@ -128,6 +205,7 @@ export async function startTranscription(
   claudeApiKey?: string,
   projectId?: string,
   refinementConfig?: RefinementConfig,
+  streamingConfig?: StreamingConfig,
 ): Promise<void> {
   // WORKAROUND: Tauri is transforming snake_case back to camelCase, so send camelCase
   const params = {
@@ -136,6 +214,7 @@ export async function startTranscription(
     claudeApiKey: claudeApiKey,
     projectId: projectId,
     refinementConfig: refinementConfig,
+    streamingConfig: streamingConfig,
   };
 
   try {
@@ -150,6 +229,18 @@ export async function stopTranscription(): Promise<void> {
   return await invoke("stop_transcription");
 }
 
+/**
+ * Pause the active session without ending it. Audio is silenced until
+ * resumed; the session, its turns and the streaming connection are kept.
+ */
+export async function pauseTranscription(): Promise<void> {
+  return await invoke("pause_transcription");
+}
+
+export async function resumeTranscription(): Promise<void> {
+  return await invoke("resume_transcription");
+}
+
 // Summary Commands
 export async function generateSummary(
   recordingId: string,
@@ -158,11 +249,24 @@ export async function generateSummary(
 }
 
 // Export Commands
+/**
+ * Exports a recording as plain text or as SRT/WebVTT captions
+ * @param outputPath - File to write
+ * @param subtitleConfig - Cue limits for "srt" / "vtt" (defaults if omitted)
+ * @returns Promise that resolves to a confirmation message
+ */
 export async function exportRecording(
   recordingId: string,
-  format: "txt" | "json",
-): Promise<void> {
-  return await invoke("export_recording", { recordingId, format });
+  outputPath: string,
+  format: ExportFormat = "txt",
+  subtitleConfig?: SubtitleConfig,
+): Promise<string> {
+  return await invoke<string>("export_recording", {
+    recordingId,
+    outputPath,
+    format,
+    subtitleConfig: subtitleConfig ?? null,
+  });
 }
 
 // Settings Commands
@@ -22,6 +22,8 @@ export interface TranscriptResult {
   turn_order: number;
   end_of_turn: boolean;
   words: WordResult[];
+  speaker?: string; // e.g. "Speaker A", or the track ("me" / "them")
+  language?: string; // Spoken language code, e.g. "de"
 }
 
 export interface EnhancedTranscript {
@@ -68,6 +70,8 @@ export interface Project {
   name: string;
   description: string;
   api_key_reference?: string | null; // References key in secure_settings table
+  streaming_config?: StreamingConfig | null; // Default turn detection settings
+  vocabulary?: string[]; // Key terms boosted in recognition and prompts
   created_at: number; // SystemTime as Unix timestamp
   updated_at: number;
   intelligence?: IntelligenceConfig; // Optional BI configuration
@@ -87,6 +91,42 @@ export interface RecordingMetadata {
   chunk_count: number;
   turn_count: number;
   average_confidence: number;
+  speakers?: SpeakerStats[]; // In order of first appearance
+  language?: string | null; // Language most of it was spoken in, e.g. "de"
+  provider_sessions?: ProviderSession[]; // Transcription provider sessions, in order
+  silence_ratio?: number | null; // Fraction of the audio detected as silence, 0-1
+  paused_intervals?: PausedInterval[]; // Off-the-record stretches, in order
+}
+
+// A stretch of the session during which no audio was transcribed
+export interface PausedInterval {
+  start_secs: number; // From the session start
+  end_secs?: number | null; // Unset while still paused
+}
+
+// Session with the transcription provider, matching the vendor's usage records
+export interface ProviderSession {
+  id: string;
+  track?: string | null; // Track it transcribed, for multi-track sources
+  expires_at: number; // Unix timestamp (seconds)
+  audio_duration_seconds?: number | null; // Reported once the session ended
+  session_duration_seconds?: number | null;
+}
+
+// Payload of the provider_session_expiring event
+export interface ProviderSessionExpiring {
+  session_id: string;
+  expires_at: number; // Unix timestamp (seconds)
+  seconds_remaining: number;
+  speaker?: string | null;
+}
+
+export interface SpeakerStats {
+  label: string; // e.g. "Speaker A"
+  name?: string | null; // Real name given by the user
+  talk_time_seconds: number;
+  word_count: number;
+  turn_count: number;
 }
 
 export interface Recording {
@@ -103,6 +143,16 @@ export interface Recording {
   created_at: number;
 }
 
+// One transcribed word, timed from the start of the session
+export interface RecordingWord {
+  turn_order: number;
+  speaker?: string | null;
+  text: string;
+  start_ms: number;
+  end_ms: number;
+  confidence: number;
+}
+
 // Refinement Configuration
 export type RefinementMode = "disabled" | "realtime" | "chunked";
 
@@ -111,12 +161,41 @@ export interface RefinementConfig {
   chunk_duration_secs: number;
 }
 
+// Turn detection settings for AssemblyAI streaming
+export interface StreamingConfig {
+  format_turns: boolean;
+  end_of_turn_confidence_threshold: number; // 0-1
+  min_end_of_turn_silence_when_confident: number; // ms, 100-10000
+  max_turn_silence: number; // ms, 100-10000
+  speaker_labels: boolean; // Label turns "Speaker A", "Speaker B", ...
+  language?: SpokenLanguage | null; // English when unset
+}
+
+// Languages the streaming model recognizes
+export type LanguageCode = "en" | "es" | "fr" | "de" | "it" | "pt";
+
+// A fixed spoken language, or "auto" to detect it
+export type SpokenLanguage = LanguageCode | "auto";
+
+// Recording export
+export type ExportFormat = "txt" | "srt" | "vtt";
+
+// Cue limits for subtitle export
+export interface SubtitleConfig {
+  max_line_chars: number; // 10-200, including a speaker prefix
+  max_lines: number; // 1-4
+  max_cue_duration_ms: number; // at least 1000
+  min_cue_duration_ms: number;
+  speaker_labels: boolean; // Name the speaker when it changes
+}
+
 // Settings Types
 export interface AppSettings {
   selected_device_id: string | null;
   assembly_api_key: string;
   claude_api_key: string;
   refinement_config: RefinementConfig;
+  output_language?: LanguageCode | null; // Summaries follow the transcript when unset
 }
 
 // UI State Types